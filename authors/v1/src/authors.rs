@@ -12,7 +12,7 @@ impl Authors {
         let mut contributors: Vec<Contributor> = Vec::new();
 
         for (index, line) in authors_string.lines().enumerate() {
-            match Contributor::parse_string(&line.to_string()) {
+            match Contributor::parse_string(line) {
                 Ok(contributor) => {
                     if index == 0 {
                         author = Some(contributor);
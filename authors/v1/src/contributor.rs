@@ -21,7 +21,7 @@ impl Default for Contributor {
 
 impl Contributor {
     // not sure if I'll make this public yet
-    pub(crate) fn parse_string(contributor_string: &String) -> Result<Self> {
+    pub(crate) fn parse_string(contributor_string: &str) -> Result<Self> {
         let mut name_end_index = contributor_string.len();
 
         let git_tag = match contributor_string.find(")") {
@@ -29,7 +29,7 @@ impl Contributor {
                 let start_index = contributor_string.find("(")
                     .ok_or_else(
                         || Error::IncorrectSyntax {
-                            troubled_line: contributor_string.clone(),
+                            troubled_line: contributor_string.to_string(),
                             error: "Missing opening ('(') bracket to enclose git tag.".into()
                         }
                     )?;
@@ -50,7 +50,7 @@ impl Contributor {
                 let start_index = contributor_string.find("<")
                     .ok_or_else(
                         || Error::IncorrectSyntax {
-                            troubled_line: contributor_string.clone(),
+                            troubled_line: contributor_string.to_string(),
                             error: "Missing opening ('<') arrow bracket to enclose email.".into()
                         }
                     )?;
@@ -5,4 +5,18 @@ pub struct EditArgs {
     /// Edit the config file.
     #[arg(short, long)]
     pub edit: bool,
-}
\ No newline at end of file
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigOverrideArgs {
+    /// Override a config key for this run only, e.g: `--set misc.enable_custom_folder=true`.
+    #[arg(long = "set", value_name = "KEY.PATH=VALUE", value_parser = parse_config_override)]
+    pub set: Vec<String>,
+}
+
+fn parse_config_override(argument: &str) -> Result<String, String> {
+    match argument.split_once('=') {
+        Some((key_path, _)) if !key_path.trim().is_empty() => Ok(argument.to_string()),
+        _ => Err(String::from("expected 'key.path=value'")),
+    }
+}
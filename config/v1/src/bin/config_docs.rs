@@ -0,0 +1,45 @@
+//! Generates the Markdown, HTML and JSON Schema reference of a config template.
+//!
+//! ```text
+//! cargo run -p cirrus_config_v1 --bin config_docs -- <template.toml> <output dir> [title]
+//! ```
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use cirrus_config_v1::template::write_config_docs;
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+
+    let (template_path, output_dir_path) = match (arguments.first(), arguments.get(1)) {
+        (Some(template_path), Some(output_dir_path)) => (template_path, output_dir_path),
+        _ => {
+            eprintln!("Usage: config_docs <template.toml> <output dir> [title]");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let title = arguments.get(2).map(String::as_str).unwrap_or("Config reference");
+
+    let template_config_toml_string = match fs::read_to_string(template_path) {
+        Ok(template_config_toml_string) => template_config_toml_string,
+        Err(error) => {
+            eprintln!("Failed to read the template at '{template_path}': {error}");
+            return ExitCode::FAILURE;
+        },
+    };
+
+    match write_config_docs(&template_config_toml_string, Path::new(output_dir_path), title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("{error} ({error:?})");
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,372 @@
+use std::{fs, io::ErrorKind, path::{Path, PathBuf}};
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+use cirrus_path::get_user_cloudy_themes_folder_path;
+
+use crate::{config::{format::{ConfigFormat, TomlFormat}, get_config_file_path_with_format, history::ConfigKeyChange, lenient::ConfigDiagnosticReason, parse_config, persist::{write_config_file, DEFAULT_BACKUP_COUNT}, reset::diff_leaf_values, CConfig}, error::Error, global::{get_global_config_file_path, parse_global_config}};
+
+/// The version of the bundle format, bundles from a newer version can't be imported.
+pub const CONFIG_BUNDLE_VERSION: i64 = 1;
+
+/// What to put in the bundle along with the app's config, see [`export_config_bundle`].
+#[derive(Debug, Clone, Default)]
+pub struct BundleExportOptions {
+    /// Also bundle the global cloudy config (`~/.config/cloudy/config.toml`).
+    pub include_global_config: bool,
+    /// Names of theme packs in the user's themes folder to bundle (e.g: `~/.local/share/cloudy/themes/{pack}`).
+    pub theme_packs: Vec<String>,
+}
+
+/// An app's config (and optionally the global config and theme packs) in one portable toml file, for moving
+/// settings between machines. The configs are kept as they were written so the user's comments come along too.
+///
+/// The app's config is always bundled as TOML, whatever [`ConfigFormat`] it's written in on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: i64,
+    pub app_name: String,
+    pub config: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_config: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub theme_files: Vec<BundledFile>,
+}
+
+/// A file of a theme pack, `path` is relative to the themes folder and always separated with `/`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledFile {
+    pub path: String,
+    pub contents: String,
+}
+
+/// What importing a bundle would change, to be shown to the user before it's applied.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBundleDiff {
+    pub config_changes: Vec<ConfigKeyChange>,
+    pub global_config_changes: Vec<ConfigKeyChange>,
+    /// Paths (relative to the themes folder) of the theme files that would be written.
+    pub theme_files: Vec<String>,
+}
+
+impl ConfigBundle {
+    pub fn new(app_name: &str, config_toml_string: &str) -> Self {
+        Self {
+            version: CONFIG_BUNDLE_VERSION,
+            app_name: app_name.to_string(),
+            config: config_toml_string.to_string(),
+            global_config: None,
+            theme_files: Vec::new(),
+        }
+    }
+
+    pub fn with_global_config(mut self, global_config_toml_string: &str) -> Self {
+        self.global_config = Some(global_config_toml_string.to_string());
+        self
+    }
+
+    /// Bundles every file of the theme pack at `{themes_dir_path}/{pack_name}`. Files that
+    /// aren't text (e.g: images) are skipped as they can't be kept in a toml bundle.
+    pub fn add_theme_pack(&mut self, themes_dir_path: &Path, pack_name: &str) -> Result<(), Error> {
+        validate_bundled_path(pack_name)?;
+
+        let pack_path = themes_dir_path.join(pack_name);
+
+        if !pack_path.is_dir() {
+            return Err(Error::FailedToExportConfigBundle { error: format!("The theme pack '{pack_name}' does not exist!") });
+        }
+
+        debug!("Bundling theme pack at '{}'...", pack_path.display());
+
+        let mut dir_paths = vec![(pack_path, pack_name.to_string())];
+
+        while let Some((dir_path, bundled_dir_path)) = dir_paths.pop() {
+            let read_dir = fs::read_dir(&dir_path)
+                .map_err(|error| Error::FailedToExportConfigBundle { error: error.to_string() })?;
+
+            let mut entries: Vec<PathBuf> = read_dir.filter_map(|entry| Some(entry.ok()?.path())).collect();
+            entries.sort();
+
+            for path in entries {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let bundled_path = format!("{}/{}", bundled_dir_path, file_name);
+
+                if path.is_dir() {
+                    dir_paths.push((path, bundled_path));
+                    continue;
+                }
+
+                match fs::read_to_string(&path) {
+                    Ok(contents) => self.theme_files.push(BundledFile { path: bundled_path, contents }),
+                    Err(error) if error.kind() == ErrorKind::InvalidData => warn!(
+                        "Skipping '{}' as it's not a text file!", path.display()
+                    ),
+                    Err(error) => return Err(Error::FailedToExportConfigBundle { error: error.to_string() }),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, Error> {
+        toml::to_string(self)
+            .map_err(|error| Error::FailedToExportConfigBundle { error: error.to_string() })
+    }
+
+    pub fn from_toml_string(bundle_toml_string: &str) -> Result<Self, Error> {
+        let bundle: Self = toml::from_str(bundle_toml_string)
+            .map_err(|error| Error::InvalidConfigBundle { error: error.to_string() })?;
+
+        if bundle.version > CONFIG_BUNDLE_VERSION {
+            return Err(
+                Error::InvalidConfigBundle {
+                    error: format!("Bundle version '{}' is newer than this app supports!", bundle.version)
+                }
+            );
+        }
+
+        Ok(bundle)
+    }
+
+    /// Checks the bundled configs deserialize and satisfy their template's constraints
+    /// and that none of the theme files would be written outside of the themes folder.
+    pub fn validate<T: CConfig>(&self, template_config_toml_string: &str) -> Result<(), Error> {
+        let invalid_bundle = |error: Error| Error::InvalidConfigBundle { error: error.to_string() };
+
+        parse_config::<T>(&self.config, template_config_toml_string).map_err(invalid_bundle)?;
+
+        // the global config is always read leniently so keys missing from it are fine, bad values are not
+        if let Some(global_config) = &self.global_config {
+            let (_, diagnostics) = parse_global_config(global_config).map_err(invalid_bundle)?;
+
+            let invalid_key_diagnostic = diagnostics.into_iter()
+                .find(|diagnostic| diagnostic.reason != ConfigDiagnosticReason::MissingKey);
+
+            if let Some(diagnostic) = invalid_key_diagnostic {
+                return Err(Error::InvalidConfigBundle { error: diagnostic.to_string() });
+            }
+        }
+
+        for theme_file in &self.theme_files {
+            validate_bundled_path(&theme_file.path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compares the bundle against the configs currently in use, `None` if that config doesn't exist yet.
+    pub fn diff(&self, current_config_toml_string: Option<&str>, current_global_config_toml_string: Option<&str>) -> Result<ConfigBundleDiff, Error> {
+        let config_changes = diff_config_strings(current_config_toml_string, &self.config)?;
+
+        let global_config_changes = match &self.global_config {
+            Some(global_config) => diff_config_strings(current_global_config_toml_string, global_config)?,
+            None => Vec::new(),
+        };
+
+        Ok(
+            ConfigBundleDiff {
+                config_changes,
+                global_config_changes,
+                theme_files: self.theme_files.iter().map(|theme_file| theme_file.path.clone()).collect(),
+            }
+        )
+    }
+
+    /// Writes the bundled theme files into the themes folder, overwriting files with the same path.
+    pub fn write_theme_files(&self, themes_dir_path: &Path) -> Result<(), Error> {
+        for theme_file in &self.theme_files {
+            validate_bundled_path(&theme_file.path)?;
+
+            let path = theme_file.path.split('/')
+                .fold(themes_dir_path.to_path_buf(), |path, part| path.join(part));
+
+            debug!("Writing bundled theme file to '{}'...", path.display());
+
+            if let Some(dir_path) = path.parent() {
+                fs::create_dir_all(dir_path)
+                    .map_err(|error| Error::FailedToImportConfigBundle { error: error.to_string() })?;
+            }
+
+            fs::write(&path, &theme_file.contents)
+                .map_err(|error| Error::FailedToImportConfigBundle { error: error.to_string() })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Bundles the app's config along with what's asked for in `options`.
+pub fn export_config_bundle(app_name: &str, options: &BundleExportOptions) -> Result<ConfigBundle, Error> {
+    export_config_bundle_with_format(app_name, options, &TomlFormat)
+}
+
+/// Same as [`export_config_bundle`] but the app's config is written in `format`, it's converted to TOML for the bundle.
+pub fn export_config_bundle_with_format(
+    app_name: &str,
+    options: &BundleExportOptions,
+    format: &dyn ConfigFormat
+) -> Result<ConfigBundle, Error> {
+    let export_error = |error: String| Error::FailedToExportConfigBundle { error };
+
+    let config_string = fs::read_to_string(get_config_file_path_with_format(app_name, format)?)
+        .map_err(|error| export_error(error.to_string()))?;
+
+    let config_toml_string = format.to_toml_string(&config_string)
+        .map_err(|error| export_error(error.to_string()))?;
+
+    let mut bundle = ConfigBundle::new(app_name, &config_toml_string);
+
+    if options.include_global_config {
+        match read_if_exists(&get_global_config_file_path()?).map_err(export_error)? {
+            Some(global_config_toml_string) => bundle = bundle.with_global_config(&global_config_toml_string),
+            None => debug!("No global config to bundle."),
+        }
+    }
+
+    if !options.theme_packs.is_empty() {
+        let themes_dir_path = get_user_cloudy_themes_folder_path()
+            .map_err(|error| export_error(error.to_string()))?;
+
+        for theme_pack in &options.theme_packs {
+            bundle.add_theme_pack(&themes_dir_path, theme_pack)?;
+        }
+    }
+
+    Ok(bundle)
+}
+
+/// Validates the bundle against the app's template and returns what importing it would change.
+pub fn preview_config_bundle<T: CConfig>(
+    bundle: &ConfigBundle,
+    app_name: &str,
+    template_config_toml_string: &str
+) -> Result<ConfigBundleDiff, Error> {
+    preview_config_bundle_with_format::<T>(bundle, app_name, template_config_toml_string, &TomlFormat)
+}
+
+/// Same as [`preview_config_bundle`] but the app's config is written in `format`.
+pub fn preview_config_bundle_with_format<T: CConfig>(
+    bundle: &ConfigBundle,
+    app_name: &str,
+    template_config_toml_string: &str,
+    format: &dyn ConfigFormat
+) -> Result<ConfigBundleDiff, Error> {
+    validate_bundle_for_app::<T>(bundle, app_name, template_config_toml_string)?;
+
+    let import_error = |error: String| Error::FailedToImportConfigBundle { error };
+
+    let current_config = match read_if_exists(&get_config_file_path_with_format(app_name, format)?).map_err(import_error)? {
+        Some(config_string) => Some(format.to_toml_string(&config_string)?.into_owned()),
+        None => None,
+    };
+
+    let current_global_config = match &bundle.global_config {
+        Some(_) => read_if_exists(&get_global_config_file_path()?).map_err(import_error)?,
+        None => None,
+    };
+
+    bundle.diff(current_config.as_deref(), current_global_config.as_deref())
+}
+
+/// Validates the bundle against the app's template then writes its configs and theme files in place.
+/// The configs being replaced are rotated into their `.bak.N` backups first so an import can be undone.
+pub fn import_config_bundle<T: CConfig>(
+    bundle: &ConfigBundle,
+    app_name: &str,
+    template_config_toml_string: &str
+) -> Result<(), Error> {
+    import_config_bundle_with_format::<T>(bundle, app_name, template_config_toml_string, &TomlFormat)
+}
+
+/// Same as [`import_config_bundle`] but the app's config is written in `format`, the bundled config is converted to it.
+pub fn import_config_bundle_with_format<T: CConfig>(
+    bundle: &ConfigBundle,
+    app_name: &str,
+    template_config_toml_string: &str,
+    format: &dyn ConfigFormat
+) -> Result<(), Error> {
+    validate_bundle_for_app::<T>(bundle, app_name, template_config_toml_string)?;
+
+    debug!("Importing config bundle for '{}'...", app_name);
+
+    let import_error = |error: Error| Error::FailedToImportConfigBundle { error: error.to_string() };
+
+    let config_path = get_config_file_path_with_format(app_name, format)?;
+
+    let previous_config_string = read_if_exists(&config_path)
+        .map_err(|error| Error::FailedToImportConfigBundle { error })?;
+
+    let config_string = format.to_config_string(&bundle.config, previous_config_string.as_deref())
+        .map_err(import_error)?;
+
+    write_config_file(&config_path, &config_string, DEFAULT_BACKUP_COUNT)
+        .map_err(import_error)?;
+
+    if let Some(global_config) = &bundle.global_config {
+        let global_config_path = get_global_config_file_path()?;
+
+        if let Some(dir_path) = global_config_path.parent() {
+            fs::create_dir_all(dir_path)
+                .map_err(|error| Error::FailedToImportConfigBundle { error: error.to_string() })?;
+        }
+
+        write_config_file(&global_config_path, global_config, DEFAULT_BACKUP_COUNT)
+            .map_err(import_error)?;
+    }
+
+    if !bundle.theme_files.is_empty() {
+        let themes_dir_path = get_user_cloudy_themes_folder_path()
+            .map_err(|error| Error::FailedToImportConfigBundle { error: error.to_string() })?;
+
+        bundle.write_theme_files(&themes_dir_path)?;
+    }
+
+    Ok(())
+}
+
+fn validate_bundle_for_app<T: CConfig>(bundle: &ConfigBundle, app_name: &str, template_config_toml_string: &str) -> Result<(), Error> {
+    if bundle.app_name != app_name {
+        return Err(
+            Error::InvalidConfigBundle {
+                error: format!("The bundle is for '{}', not '{}'!", bundle.app_name, app_name)
+            }
+        );
+    }
+
+    bundle.validate::<T>(template_config_toml_string)
+}
+
+/// Bundled paths are relative and `/` separated on every platform, they must never point outside of where they get written.
+fn validate_bundled_path(path: &str) -> Result<(), Error> {
+    let is_valid = path.split('/')
+        .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains(['\\', ':']));
+
+    match is_valid {
+        true => Ok(()),
+        false => Err(Error::InvalidConfigBundle { error: format!("'{path}' is not a valid bundled path!") }),
+    }
+}
+
+fn diff_config_strings(current_toml_string: Option<&str>, bundled_toml_string: &str) -> Result<Vec<ConfigKeyChange>, Error> {
+    let current_document: DocumentMut = current_toml_string.unwrap_or_default().parse()
+        .map_err(|error: toml_edit::TomlError| Error::FailedToReadConfig(error.to_string()))?;
+
+    let bundled_document: DocumentMut = bundled_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::InvalidConfigBundle { error: error.to_string() })?;
+
+    Ok(
+        diff_leaf_values(&current_document, &bundled_document).into_iter()
+            .map(|(key_path, before, after)| ConfigKeyChange { key_path, before, after })
+            .collect()
+    )
+}
+
+fn read_if_exists(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(toml_string) => Ok(Some(toml_string)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.to_string()),
+    }
+}
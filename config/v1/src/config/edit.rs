@@ -0,0 +1,434 @@
+use log::debug;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Key, Table, TableLike, Value};
+
+use crate::{error::Error, template::Template, toml_path::{get_item_by_path, get_item_by_path_mut, split_key_path}};
+
+/// Edits the user's config document (`document_to_edit`) to match the config in memory (`config_document`,
+/// usually from `toml::to_string(&config)`) by only touching what's different, so the comments and
+/// formatting in the user's config file are kept.
+///
+/// Keys missing from `document_to_edit` are skipped, see [`insert_missing_keys`] for those. Only inside of inline tables and the
+/// elements of arrays (of tables) are keys added to or removed from the document to match the config, as the template has no
+/// docstrings to give them. Elements added to or removed from arrays and arrays of tables (e.g: `[[plugins]]`) are added to
+/// or removed from the end of the array.
+pub fn edit_config_document(document_to_edit: &mut DocumentMut, config_document: &DocumentMut) {
+    walk_and_edit_toml_table(document_to_edit.as_table_mut(), config_document.as_table(), None, MissingKeys::Skip);
+}
+
+/// What to do with keys that are in only one of the tables while walking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MissingKeys {
+    /// Left to [`insert_missing_keys`] so they get the template's docstrings and keys the app doesn't know about are kept.
+    Skip,
+    /// Inserted into (or removed from) the table being edited, e.g: an element of an array of tables.
+    Sync,
+    /// Same as [`MissingKeys::Sync`] but the table being edited is an inline table, which can only hold values.
+    SyncInline,
+}
+
+/// Inserts the keys (and tables) of the template that are missing from the user's config document, taking their
+/// values from the config in memory (`config_document`). So a config file from an older version of the app
+/// grows tidily, every key is placed in template order and gets the template's docstring copied above it.
+///
+/// Keys that are missing from `config_document` too (e.g: an optional key set to `None`) are skipped.
+pub fn insert_missing_keys(
+    document_to_edit: &mut DocumentMut,
+    config_document: &DocumentMut,
+    template_config_toml_string: &str
+) -> Result<(), Error> {
+    insert_missing_keys_under(document_to_edit, config_document, template_config_toml_string, None)
+}
+
+/// Same as [`insert_missing_keys`] but only inserts the keys at or under `parent_key_path` if given.
+pub(crate) fn insert_missing_keys_under(
+    document_to_edit: &mut DocumentMut,
+    config_document: &DocumentMut,
+    template_config_toml_string: &str,
+    parent_key_path: Option<&str>
+) -> Result<(), Error> {
+    let mut template = Template::new(template_config_toml_string);
+    template.parse_keys()?;
+
+    let template_document: DocumentMut = template_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::TemplateConfigParseFailure { error: error.to_string() })?;
+
+    for key_path in &template.ordered_paths {
+        if let Some(parent_key_path) = parent_key_path && !is_key_path_under(key_path, parent_key_path) {
+            continue;
+        }
+
+        // array elements are never made up, only a whole array of tables that's missing is added
+        let key_path = match key_path.split_once('[') {
+            Some((array_path, _)) => array_path,
+            None => key_path.as_str(),
+        };
+
+        if get_item_by_path(document_to_edit.as_item(), key_path).is_some() {
+            continue;
+        }
+
+        let Some(item) = get_item_by_path(config_document.as_item(), key_path) else {
+            continue;
+        };
+
+        debug!("Config key '{}' is missing from the document being edited, inserting it...", key_path);
+
+        insert_missing_item(document_to_edit, &template_document, &template.ordered_paths, key_path, item.clone());
+    }
+
+    Ok(())
+}
+
+/// Whether `key_path` is `parent_key_path` itself or a key inside of it (e.g: `misc.name` is under `misc`).
+pub(crate) fn is_key_path_under(key_path: &str, parent_key_path: &str) -> bool {
+    match key_path.strip_prefix(parent_key_path) {
+        Some(rest) => rest.is_empty() || rest.starts_with('.') || rest.starts_with('['),
+        None => false,
+    }
+}
+
+fn insert_missing_item(
+    document_to_edit: &mut DocumentMut,
+    template_document: &DocumentMut,
+    template_ordered_paths: &[String],
+    key_path: &str,
+    mut item: Item
+) {
+    let (parent_path, key) = split_key_path(key_path);
+
+    let parent_table_like = match parent_path {
+        Some(parent_path) => get_or_insert_table(document_to_edit, template_document, parent_path),
+        None => Some(document_to_edit.as_table_mut() as &mut dyn TableLike),
+    };
+
+    let Some(parent_table_like) = parent_table_like else {
+        debug!("Can't insert config key '{}' as its parent is not a table!", key_path);
+        return;
+    };
+
+    let template_parent = match parent_path {
+        Some(parent_path) => get_item_by_path(template_document.as_item(), parent_path)
+            .and_then(|item| item.as_table_like()),
+        None => Some(template_document.as_table() as &dyn TableLike),
+    };
+
+    // the template's docstring lives in the decor of the key
+    let formatted_key = match template_parent.and_then(|template_parent| template_parent.key(key)) {
+        Some(template_key) => Key::new(key).with_leaf_decor(template_key.leaf_decor().clone()),
+        None => Key::new(key),
+    };
+
+    match (&mut item, template_parent.and_then(|template_parent| template_parent.get(key))) {
+        (Item::Value(value), Some(Item::Value(template_value))) => {
+            *value.decor_mut() = template_value.decor().clone();
+        },
+        (Item::ArrayOfTables(array_of_tables), _) => {
+            array_of_tables.iter_mut().for_each(clear_table_positions);
+        },
+        _ => {},
+    }
+
+    // keys already in the table that come after this key in the template
+    let sibling_key_paths: Vec<&str> = template_ordered_paths.iter()
+        .filter(|sibling_key_path| split_key_path(sibling_key_path).0 == parent_path)
+        .map(|sibling_key_path| split_key_path(sibling_key_path).1)
+        .collect();
+
+    let position = sibling_key_paths.iter().position(|sibling_key| *sibling_key == key);
+
+    let keys_to_move: Vec<String> = match position {
+        Some(position) => {
+            let following_keys = &sibling_key_paths[position + 1..];
+
+            let first_following_key = parent_table_like.iter()
+                .map(|(existing_key, _)| existing_key)
+                .position(|existing_key| following_keys.contains(&existing_key));
+
+            match first_following_key {
+                Some(first_following_key) => parent_table_like.iter()
+                    .skip(first_following_key)
+                    .map(|(existing_key, _)| existing_key.to_string())
+                    .collect(),
+                None => Vec::new(),
+            }
+        },
+        None => Vec::new(),
+    };
+
+    // The only way to insert in the middle of a table is taking out everything that
+    // comes after and putting it back in, which still keeps all of their formatting.
+    let moved_entries: Vec<(Key, Item)> = keys_to_move.iter()
+        .filter_map(|key_to_move| {
+            let formatted_key = parent_table_like.key(key_to_move)?.clone();
+            let item = parent_table_like.remove(key_to_move)?;
+
+            Some((formatted_key, item))
+        })
+        .collect();
+
+    insert_formatted(parent_table_like, &formatted_key, item);
+
+    for (formatted_key, item) in moved_entries {
+        insert_formatted(parent_table_like, &formatted_key, item);
+    }
+}
+
+/// Gets the table at the key path, creating it (with the template's comments) and any missing tables leading up to it.
+fn get_or_insert_table<'a>(
+    document_to_edit: &'a mut DocumentMut,
+    template_document: &DocumentMut,
+    key_path: &str
+) -> Option<&'a mut dyn TableLike> {
+    let mut current_path = String::new();
+
+    for part in key_path.split('.') {
+        let parent_path = (!current_path.is_empty()).then(|| current_path.clone());
+
+        if !current_path.is_empty() {
+            current_path.push('.');
+        }
+
+        current_path.push_str(part);
+
+        if get_item_by_path(document_to_edit.as_item(), &current_path).is_some() {
+            continue;
+        }
+
+        debug!("Table '{}' is missing from the document being edited, inserting it...", current_path);
+
+        let mut table = Table::new();
+
+        if let Some(Item::Table(template_table)) = get_item_by_path(template_document.as_item(), &current_path) {
+            *table.decor_mut() = template_table.decor().clone();
+            table.set_implicit(template_table.is_implicit());
+        }
+
+        let parent_table_like = match &parent_path {
+            Some(parent_path) => get_item_by_path_mut(document_to_edit.as_item_mut(), parent_path)?.as_table_like_mut()?,
+            None => document_to_edit.as_table_mut(),
+        };
+
+        parent_table_like.insert(part, Item::Table(table));
+    }
+
+    get_item_by_path_mut(document_to_edit.as_item_mut(), key_path)?.as_table_like_mut()
+}
+
+fn insert_formatted(table_like: &mut dyn TableLike, formatted_key: &Key, item: Item) {
+    if let toml_edit::Entry::Vacant(entry) = table_like.entry_format(formatted_key) {
+        entry.insert(item);
+    }
+}
+
+/// Whether the two values differ, ignoring their formatting. Arrays and inline tables are compared element by element.
+pub fn is_toml_value_different(value_a: &Value, value_b: &Value) -> bool {
+    match (value_a, value_b) {
+        (Value::String(formatted_a), Value::String(formatted_b)) => formatted_a.value() != formatted_b.value(),
+        (Value::Integer(formatted_a), Value::Integer(formatted_b)) => formatted_a.value() != formatted_b.value(),
+        (Value::Float(formatted_a), Value::Float(formatted_b)) => formatted_a.value() != formatted_b.value(),
+        (Value::Boolean(formatted_a), Value::Boolean(formatted_b)) => formatted_a.value() != formatted_b.value(),
+        (Value::Datetime(formatted_a), Value::Datetime(formatted_b)) => formatted_a.value() != formatted_b.value(),
+        (Value::Array(array), Value::Array(previous_array)) => {
+            array.len() != previous_array.len()
+                || array.iter()
+                    .zip(previous_array.iter())
+                    .any(|(value, previous_value)| is_toml_value_different(value, previous_value))
+        },
+        (Value::InlineTable(inline_table), Value::InlineTable(previous_inline_table)) => {
+            inline_table.len() != previous_inline_table.len()
+                || inline_table.iter()
+                    .any(|(key, value)| {
+                        match previous_inline_table.get(key) {
+                            Some(previous_value) => is_toml_value_different(value, previous_value),
+                            None => true,
+                        }
+                    })
+        },
+        _ => true,
+    }
+}
+
+fn walk_and_edit_toml_table(
+    table_to_edit: &mut dyn TableLike,
+    table_to_walk: &dyn TableLike,
+    key_path: Option<&str>,
+    missing_keys: MissingKeys
+) {
+    let mut is_keys_changed = false;
+
+    for (key, item) in table_to_walk.iter() {
+        let key_path = match key_path {
+            Some(path) => format!("{}.{}", path, key),
+            None => key.to_string(),
+        };
+
+        match (table_to_edit.get_mut(key), missing_keys) {
+            (Some(item_to_edit), _) => walk_and_edit_toml_item(item_to_edit, item, &key_path),
+            (None, MissingKeys::Skip) => debug!("Config key '{}' is missing from the document being edited, skipping it...", key_path),
+            (None, _) => {
+                debug!("Adding '{}' to the toml document...", key_path);
+
+                let mut item = item.clone();
+
+                match &mut item {
+                    Item::Table(table) => clear_table_positions(table),
+                    Item::ArrayOfTables(array_of_tables) => array_of_tables.iter_mut().for_each(clear_table_positions),
+                    _ => {},
+                }
+
+                if missing_keys == MissingKeys::SyncInline {
+                    item = match item.into_value() {
+                        Ok(value) => Item::Value(value),
+                        Err(_) => continue,
+                    };
+                }
+
+                insert_formatted(table_to_edit, &Key::new(key), item);
+                is_keys_changed = true;
+            },
+        }
+    }
+
+    if missing_keys == MissingKeys::Skip {
+        return;
+    }
+
+    let keys_to_remove: Vec<String> = table_to_edit.iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !table_to_walk.contains_key(key))
+        .collect();
+
+    for key in keys_to_remove {
+        debug!("Removing '{}' from the toml document...", key_path.map_or(key.clone(), |path| format!("{}.{}", path, key)));
+
+        table_to_edit.remove(&key);
+        is_keys_changed = true;
+    }
+
+    // spaces around the values of an inline table are kept with them, so the new last value is missing the one before '}'
+    if is_keys_changed && missing_keys == MissingKeys::SyncInline {
+        table_to_edit.fmt();
+    }
+}
+
+pub(crate) fn walk_and_edit_toml_item(item_to_edit: &mut Item, item_to_walk: &Item, key_path: &str) {
+    match item_to_walk {
+        Item::None => {},
+        Item::Value(value) => match item_to_edit.as_value_mut() {
+            Some(value_to_edit) => walk_and_edit_toml_value(value_to_edit, value, key_path),
+            None => replace_toml_item(item_to_edit, item_to_walk, key_path),
+        },
+        // standard tables are edited in place even if the user wrote them as inline tables
+        Item::Table(table) => {
+            let missing_keys = match (item_to_edit.is_inline_table(), key_path.contains('[')) {
+                (true, _) => MissingKeys::SyncInline,
+                (false, true) => MissingKeys::Sync,
+                (false, false) => MissingKeys::Skip,
+            };
+
+            match item_to_edit.as_table_like_mut() {
+                Some(table_to_edit) => walk_and_edit_toml_table(table_to_edit, table, Some(key_path), missing_keys),
+                None => replace_toml_item(item_to_edit, item_to_walk, key_path),
+            }
+        },
+        Item::ArrayOfTables(array_of_tables) => match item_to_edit {
+            Item::ArrayOfTables(array_of_tables_to_edit) => walk_and_edit_array_of_tables(
+                array_of_tables_to_edit, array_of_tables, key_path
+            ),
+            // the user wrote it as an array of inline tables (e.g: 'plugins = [{ name = "meow" }]')
+            Item::Value(value_to_edit @ Value::Array(_)) => walk_and_edit_toml_value(
+                value_to_edit, &Value::Array(array_of_tables.clone().into_array()), key_path
+            ),
+            _ => replace_toml_item(item_to_edit, item_to_walk, key_path),
+        },
+    }
+}
+
+fn walk_and_edit_array_of_tables(array_of_tables_to_edit: &mut ArrayOfTables, array_of_tables: &ArrayOfTables, key_path: &str) {
+    for (index, table) in array_of_tables.iter().enumerate() {
+        let element_key_path = format!("{}[{}]", key_path, index);
+
+        match array_of_tables_to_edit.get_mut(index) {
+            Some(table_to_edit) => walk_and_edit_toml_table(table_to_edit, table, Some(&element_key_path), MissingKeys::Sync),
+            None => {
+                debug!("Adding '{}' to the toml document...", element_key_path);
+
+                let mut table = table.clone();
+                clear_table_positions(&mut table);
+
+                array_of_tables_to_edit.push(table);
+            },
+        }
+    }
+
+    while array_of_tables_to_edit.len() > array_of_tables.len() {
+        let last_index = array_of_tables_to_edit.len() - 1;
+
+        debug!("Removing '{}[{}]' from the toml document...", key_path, last_index);
+        array_of_tables_to_edit.remove(last_index);
+    }
+}
+
+fn walk_and_edit_toml_value(value_to_edit: &mut Value, value: &Value, key_path: &str) {
+    if !is_toml_value_different(value, value_to_edit) {
+        return;
+    }
+
+    match (value_to_edit, value) {
+        (Value::Array(array_to_edit), Value::Array(array)) => {
+            for (index, element) in array.iter().enumerate() {
+                match array_to_edit.get_mut(index) {
+                    Some(element_to_edit) => walk_and_edit_toml_value(
+                        element_to_edit, element, &format!("{}[{}]", key_path, index)
+                    ),
+                    None => {
+                        debug!("Adding '{}[{}]' to the toml document...", key_path, index);
+                        array_to_edit.push(element.clone());
+                    },
+                }
+            }
+
+            while array_to_edit.len() > array.len() {
+                debug!("Removing '{}[{}]' from the toml document...", key_path, array_to_edit.len() - 1);
+                array_to_edit.remove(array_to_edit.len() - 1);
+            }
+        },
+        (Value::InlineTable(inline_table_to_edit), Value::InlineTable(inline_table)) => {
+            walk_and_edit_toml_table(inline_table_to_edit, inline_table, Some(key_path), MissingKeys::SyncInline);
+        },
+        (value_to_edit, value) => {
+            debug!("Editing toml key '{}' with value '{}'...", key_path, value);
+
+            // keep the user's whitespace and comments around the value (e.g: 'key = 1 # comment')
+            let decor = value_to_edit.decor().clone();
+
+            *value_to_edit = value.clone();
+            *value_to_edit.decor_mut() = decor;
+        },
+    }
+}
+
+fn replace_toml_item(item_to_edit: &mut Item, item: &Item, key_path: &str) {
+    debug!("Replacing toml key '{}' as its type changed...", key_path);
+
+    let mut item = item.clone();
+
+    if let Item::Table(table) = &mut item {
+        clear_table_positions(table);
+    }
+
+    *item_to_edit = item;
+}
+
+/// Tables remember where they were in the document they came from, which means nothing in another document.
+pub(crate) fn clear_table_positions(table: &mut Table) {
+    table.set_position(None);
+
+    for (_, item) in table.iter_mut() {
+        match item {
+            Item::Table(child_table) => clear_table_positions(child_table),
+            Item::ArrayOfTables(array_of_tables) => array_of_tables.iter_mut().for_each(clear_table_positions),
+            _ => {},
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+use crate::error::Error;
+
+/// The file format the user's config is written in.
+///
+/// The config pipeline (templates, key paths, migrations, saving, etc) works on TOML so a format only has to
+/// convert its files to TOML and back. [`TomlFormat`] is the default, the other formats are behind cargo features:
+///
+/// | Format          | Feature | Extension |
+/// |-----------------|---------|-----------|
+/// | [`TomlFormat`]  |         | `toml`    |
+/// | `RonFormat`     | `ron`   | `ron`     |
+/// | `Json5Format`   | `json5` | `json5`   |
+/// | `YamlFormat`    | `yaml`  | `yaml`    |
+///
+/// The template is always written in TOML, so its docstrings and constraints work whatever the format.
+/// Only TOML configs keep the user's comments and formatting on save, the other formats are rewritten
+/// from scratch, keeping just the comments at the top of the file. TOML has no null, so neither do the others.
+pub trait ConfigFormat: Send + Sync {
+    /// Name shown to the user (e.g: "YAML").
+    fn name(&self) -> &'static str;
+
+    /// Extension of the config file without the leading period (e.g: `yaml`).
+    fn extension(&self) -> &'static str;
+
+    /// Converts the contents of a config file in this format to TOML.
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error>;
+
+    /// Converts TOML to the contents of a config file in this format. `previous_config_string`
+    /// is what's currently in the file, so whatever the format can keep of it is kept.
+    fn to_config_string(&self, toml_string: &str, previous_config_string: Option<&str>) -> Result<String, Error>;
+
+    /// Name of the config file (e.g: `config.yaml`).
+    fn file_name(&self) -> String {
+        format!("config.{}", self.extension())
+    }
+}
+
+/// The default format, it's what the rest of the pipeline speaks so comments and formatting are kept on save.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlFormat;
+
+impl ConfigFormat for TomlFormat {
+    fn name(&self) -> &'static str {
+        "TOML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error> {
+        Ok(Cow::Borrowed(config_string))
+    }
+
+    fn to_config_string(&self, toml_string: &str, _previous_config_string: Option<&str>) -> Result<String, Error> {
+        Ok(toml_string.to_string())
+    }
+}
+
+/// [Rusty Object Notation](https://github.com/ron-rs/ron), tables are written as RON maps.
+#[cfg(feature = "ron")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RonFormat;
+
+#[cfg(feature = "ron")]
+impl ConfigFormat for RonFormat {
+    fn name(&self) -> &'static str {
+        "RON"
+    }
+
+    fn extension(&self) -> &'static str {
+        "ron"
+    }
+
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error> {
+        let table = ron::from_str::<toml::Table>(config_string)
+            .map_err(|error| format_error(self, error))?;
+
+        table_to_toml_string(self, &table)
+    }
+
+    fn to_config_string(&self, toml_string: &str, previous_config_string: Option<&str>) -> Result<String, Error> {
+        let table = toml_string_to_table(self, toml_string)?;
+
+        let config_string = ron::ser::to_string_pretty(&table, ron::ser::PrettyConfig::default())
+            .map_err(|error| format_error(self, error))?;
+
+        Ok(with_header_comments(previous_config_string, "//", config_string))
+    }
+}
+
+/// [JSON5](https://json5.org), JSON that allows comments and trailing commas.
+#[cfg(feature = "json5")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json5Format;
+
+#[cfg(feature = "json5")]
+impl ConfigFormat for Json5Format {
+    fn name(&self) -> &'static str {
+        "JSON5"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json5"
+    }
+
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error> {
+        let table = json5::from_str::<toml::Table>(config_string)
+            .map_err(|error| format_error(self, error))?;
+
+        table_to_toml_string(self, &table)
+    }
+
+    fn to_config_string(&self, toml_string: &str, previous_config_string: Option<&str>) -> Result<String, Error> {
+        let table = toml_string_to_table(self, toml_string)?;
+
+        // json5 can only write minified json, pretty json is just as valid json5 and far easier to edit
+        let config_string = serde_json::to_string_pretty(&table)
+            .map_err(|error| format_error(self, error))?;
+
+        Ok(with_header_comments(previous_config_string, "//", config_string + "\n"))
+    }
+}
+
+/// [YAML](https://yaml.org).
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlFormat;
+
+#[cfg(feature = "yaml")]
+impl ConfigFormat for YamlFormat {
+    fn name(&self) -> &'static str {
+        "YAML"
+    }
+
+    fn extension(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error> {
+        let table = serde_yaml_ng::from_str::<toml::Table>(config_string)
+            .map_err(|error| format_error(self, error))?;
+
+        table_to_toml_string(self, &table)
+    }
+
+    fn to_config_string(&self, toml_string: &str, previous_config_string: Option<&str>) -> Result<String, Error> {
+        let table = toml_string_to_table(self, toml_string)?;
+
+        let config_string = serde_yaml_ng::to_string(&table)
+            .map_err(|error| format_error(self, error))?;
+
+        Ok(with_header_comments(previous_config_string, "#", config_string))
+    }
+}
+
+#[cfg(any(feature = "ron", feature = "json5", feature = "yaml"))]
+fn format_error(format: &dyn ConfigFormat, error: impl ToString) -> Error {
+    Error::ConfigFormatFailure { format: format.name().to_string(), error: error.to_string() }
+}
+
+#[cfg(any(feature = "ron", feature = "json5", feature = "yaml"))]
+fn table_to_toml_string<'a>(format: &dyn ConfigFormat, table: &toml::Table) -> Result<Cow<'a, str>, Error> {
+    toml::to_string(table)
+        .map(Cow::Owned)
+        .map_err(|error| format_error(format, error))
+}
+
+#[cfg(any(feature = "ron", feature = "json5", feature = "yaml"))]
+fn toml_string_to_table(format: &dyn ConfigFormat, toml_string: &str) -> Result<toml::Table, Error> {
+    toml::from_str::<toml::Table>(toml_string)
+        .map_err(|error| format_error(format, error))
+}
+
+/// Puts the comment lines (and blank lines between them) at the top of the previous config back on top of `config_string`.
+#[cfg(any(feature = "ron", feature = "json5", feature = "yaml"))]
+fn with_header_comments(previous_config_string: Option<&str>, comment_prefix: &str, config_string: String) -> String {
+    let header_lines: Vec<&str> = previous_config_string.unwrap_or_default()
+        .lines()
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with(comment_prefix))
+        .collect();
+
+    let header = header_lines.join("\n");
+
+    match header.trim().is_empty() {
+        true => config_string,
+        false => format!("{}\n{}", header, config_string),
+    }
+}
@@ -0,0 +1,216 @@
+use std::{collections::VecDeque, hash::{DefaultHasher, Hasher}, time::{Duration, Instant}};
+
+use log::debug;
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::{config::{reset::diff_leaf_values, CConfig}, error::Error, toml_path::{insert_item_by_path, take_item_by_path}};
+
+/// How many changes [`ConfigHistory::default`] remembers.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Changes to the same keys recorded within this window of each other are merged into one,
+/// so dragging a slider across a dozen frames is undone in one go.
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(750);
+
+/// The value of a config key before and after a change. `None` if the key didn't exist.
+#[derive(Debug, Clone)]
+pub struct ConfigKeyChange {
+    pub key_path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// A bounded undo / redo history of changes made to a config in memory.
+///
+/// Call [`ConfigHistory::record`] whenever the config may have changed (e.g: every frame), every key
+/// that changed since the last call is captured as one undoable change.
+pub struct ConfigHistory {
+    /// How many changes are remembered, the oldest get forgotten first.
+    pub limit: usize,
+
+    undo_stack: VecDeque<Vec<ConfigKeyChange>>,
+    redo_stack: Vec<Vec<ConfigKeyChange>>,
+
+    snapshot: Option<(u64, DocumentMut)>,
+    last_recorded_at: Option<Instant>,
+}
+
+impl Default for ConfigHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl ConfigHistory {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            snapshot: None,
+            last_recorded_at: None,
+        }
+    }
+
+    /// Captures the keys that changed since the last call as one change. Returns `true` if anything changed.
+    ///
+    /// The first call only takes a snapshot of the config to compare against.
+    pub fn record<T: CConfig>(&mut self, config: &T) -> Result<bool, Error> {
+        let config_hash = hash_config(config);
+
+        let previous_document = match &self.snapshot {
+            Some((snapshot_hash, _)) if *snapshot_hash == config_hash => return Ok(false),
+            Some((_, snapshot_document)) => Some(snapshot_document),
+            None => None,
+        };
+
+        let document = config_to_document(config)?;
+
+        let changes: Vec<ConfigKeyChange> = match previous_document {
+            Some(previous_document) => diff_leaf_values(previous_document, &document).into_iter()
+                .map(|(key_path, before, after)| ConfigKeyChange { key_path, before, after })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.snapshot = Some((config_hash, document));
+
+        if changes.is_empty() {
+            return Ok(false);
+        }
+
+        debug!("Recording change to config key(s) {:?}...", changes.iter().map(|change| &change.key_path).collect::<Vec<_>>());
+
+        let now = Instant::now();
+
+        let is_within_window = self.last_recorded_at
+            .is_some_and(|last_recorded_at| now.duration_since(last_recorded_at) <= COALESCE_WINDOW);
+
+        self.last_recorded_at = Some(now);
+        self.redo_stack.clear();
+
+        if is_within_window && let Some(last_changes) = self.undo_stack.back_mut() && is_same_keys(last_changes, &changes) {
+            for (last_change, change) in last_changes.iter_mut().zip(changes) {
+                last_change.after = change.after;
+            }
+
+            return Ok(true);
+        }
+
+        self.undo_stack.push_back(changes);
+
+        while self.undo_stack.len() > self.limit {
+            self.undo_stack.pop_front();
+        }
+
+        Ok(true)
+    }
+
+    /// Reverts the config to before the last change. Returns `false` if there's nothing to undo.
+    pub fn undo<T: CConfig>(&mut self, config: &mut T) -> Result<bool, Error> {
+        // changes made since the last record are what the user expects to undo first
+        self.record(config)?;
+
+        let Some(changes) = self.undo_stack.pop_back() else {
+            return Ok(false);
+        };
+
+        debug!("Undoing change to {} config key(s)...", changes.len());
+
+        self.apply(config, &changes, |change| &change.before)?;
+        self.redo_stack.push(changes);
+
+        Ok(true)
+    }
+
+    /// Re-applies the last undone change. Returns `false` if there's nothing to redo.
+    pub fn redo<T: CConfig>(&mut self, config: &mut T) -> Result<bool, Error> {
+        self.record(config)?;
+
+        let Some(changes) = self.redo_stack.pop() else {
+            return Ok(false);
+        };
+
+        debug!("Redoing change to {} config key(s)...", changes.len());
+
+        self.apply(config, &changes, |change| &change.after)?;
+        self.undo_stack.push_back(changes);
+
+        Ok(true)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forgets every change, the next [`ConfigHistory::record`] takes a fresh snapshot.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.snapshot = None;
+        self.last_recorded_at = None;
+    }
+
+    /// Starts the history over from `config`, forgetting every change. Use this when the config is replaced by
+    /// something that isn't the user's edit to undo (e.g: reloaded from disk) so the next [`ConfigHistory::record`]
+    /// doesn't capture it as one, as undoing it would revert the changes made on disk.
+    pub fn resnapshot<T: CConfig>(&mut self, config: &T) -> Result<(), Error> {
+        self.clear();
+        self.snapshot = Some((hash_config(config), config_to_document(config)?));
+
+        Ok(())
+    }
+
+    fn apply<T: CConfig>(
+        &mut self,
+        config: &mut T,
+        changes: &[ConfigKeyChange],
+        value_of: impl Fn(&ConfigKeyChange) -> &Option<Value>
+    ) -> Result<(), Error> {
+        let mut document = config_to_document(config)?;
+
+        for change in changes {
+            match value_of(change) {
+                Some(value) => insert_item_by_path(document.as_item_mut(), &change.key_path, None, Item::Value(value.clone()))
+                    .map_err(Error::FailedToReadConfig)?,
+                None => {
+                    take_item_by_path(document.as_item_mut(), &change.key_path);
+                },
+            }
+        }
+
+        *config = toml::from_str::<T>(&document.to_string())
+            .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+        // so the change we just applied isn't recorded as a new one
+        self.snapshot = Some((hash_config(config), config_to_document(config)?));
+        self.last_recorded_at = None;
+
+        Ok(())
+    }
+}
+
+fn is_same_keys(changes_a: &[ConfigKeyChange], changes_b: &[ConfigKeyChange]) -> bool {
+    changes_a.len() == changes_b.len()
+        && changes_a.iter()
+            .zip(changes_b)
+            .all(|(change_a, change_b)| change_a.key_path == change_b.key_path)
+}
+
+pub(crate) fn config_to_document<T: CConfig>(config: &T) -> Result<DocumentMut, Error> {
+    toml::to_string(config)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?
+        .parse::<DocumentMut>()
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))
+}
+
+pub(crate) fn hash_config<T: CConfig>(config: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    config.hash(&mut hasher);
+
+    hasher.finish()
+}
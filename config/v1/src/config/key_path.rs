@@ -0,0 +1,196 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap, HashSet}, fmt::Display, marker::PhantomData, ops::Deref, path::PathBuf};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::{config::{history::config_to_document, CConfig}, error::Error, toml_path::{get_item_by_path, insert_item_by_path, take_item_by_path}};
+
+/// Typed key paths of a type found in a config, derive it on config structs with `#[derive(ConfigKeys)]`.
+///
+/// For a config struct [`ConfigKeys::KeyPaths`] has a method per field (e.g: `Config::key_paths().misc().theme()`),
+/// for a value (e.g: `bool`, `String` or an enum) it's the [`ConfigKeyPath`] to it. Key paths are built from the
+/// names serde serializes the fields with, so `#[serde(rename)]` and `#[serde(rename_all)]` are taken into account.
+pub trait ConfigKeys {
+    type KeyPaths;
+
+    /// The key paths of this type found at `key_path` in the config (empty at the root).
+    fn key_paths_at(key_path: String) -> Self::KeyPaths;
+}
+
+/// Period separated path to a config key holding a `V` (e.g: `misc.theme`), get one from `#[derive(ConfigKeys)]`.
+///
+/// It derefs to `&str` so it can be used anywhere a key path string is expected (e.g: `TemplateKeys` lookups).
+pub struct ConfigKeyPath<V> {
+    key_path: String,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<V> ConfigKeyPath<V> {
+    pub fn new(key_path: impl Into<String>) -> Self {
+        Self { key_path: key_path.into(), _value: PhantomData }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.key_path
+    }
+}
+
+impl<V: Serialize + DeserializeOwned> ConfigKeyPath<V> {
+    /// Reads the key's value from the config, the way it would be serialized to the config file.
+    pub fn get<T: CConfig>(&self, config: &T) -> Result<V, Error> {
+        let document = config_to_document(config)?;
+
+        match get_item_by_path(document.as_item(), &self.key_path) {
+            Some(item) => item_to_typed_value(Some(item)),
+            // a `None` isn't serialized at all
+            None => item_to_typed_value(None)
+                .map_err(|_| Error::UnknownConfigKey { key_path: self.key_path.clone() }),
+        }
+    }
+
+    /// Sets the key's value in the config.
+    pub fn set<T: CConfig>(&self, config: &mut T, value: V) -> Result<(), Error> {
+        let mut document = config_to_document(config)?;
+
+        match typed_value_to_item(&value)? {
+            Some(item) => insert_item_by_path(document.as_item_mut(), &self.key_path, None, item)
+                .map_err(Error::FailedToReadConfig)?,
+            None => {
+                take_item_by_path(document.as_item_mut(), &self.key_path);
+            },
+        }
+
+        *config = toml::from_str::<T>(&document.to_string())
+            .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+        Ok(())
+    }
+
+    /// The value as a toml value, e.g: for a [`ConfigOverride`](crate::config::layered::ConfigOverride).
+    pub fn to_toml_value(&self, value: &V) -> Result<Value, Error> {
+        match typed_value_to_item(value)? {
+            Some(Item::Value(value)) => Ok(value),
+            _ => Err(Error::FailedToReadConfig(format!("The value of '{}' is not a toml value!", self.key_path))),
+        }
+    }
+}
+
+impl<V> Clone for ConfigKeyPath<V> {
+    fn clone(&self) -> Self {
+        Self::new(self.key_path.clone())
+    }
+}
+
+impl<V> std::fmt::Debug for ConfigKeyPath<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ConfigKeyPath").field(&self.key_path).finish()
+    }
+}
+
+impl<V> PartialEq for ConfigKeyPath<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key_path == other.key_path
+    }
+}
+
+impl<V> Deref for ConfigKeyPath<V> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.key_path
+    }
+}
+
+impl<V> AsRef<str> for ConfigKeyPath<V> {
+    fn as_ref(&self) -> &str {
+        &self.key_path
+    }
+}
+
+impl<V> Display for ConfigKeyPath<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key_path)
+    }
+}
+
+impl<V> From<ConfigKeyPath<V>> for String {
+    fn from(key_path: ConfigKeyPath<V>) -> Self {
+        key_path.key_path
+    }
+}
+
+macro_rules! impl_value_config_keys {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ConfigKeys for $ty {
+                type KeyPaths = ConfigKeyPath<Self>;
+
+                fn key_paths_at(key_path: String) -> Self::KeyPaths {
+                    ConfigKeyPath::new(key_path)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_generic_value_config_keys {
+    ($($ty:ident < $($generic:ident),+ >),* $(,)?) => {
+        $(
+            impl<$($generic),+> ConfigKeys for $ty<$($generic),+> {
+                type KeyPaths = ConfigKeyPath<Self>;
+
+                fn key_paths_at(key_path: String) -> Self::KeyPaths {
+                    ConfigKeyPath::new(key_path)
+                }
+            }
+        )*
+    };
+}
+
+impl_value_config_keys!(
+    bool, char, String, PathBuf,
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64,
+);
+
+// collections (and optional values) are set as a whole, their items don't have key paths
+impl_generic_value_config_keys!(
+    Option<T>, Vec<T>, HashSet<T>, BTreeSet<T>, HashMap<K, V>, BTreeMap<K, V>,
+);
+
+impl<T, const N: usize> ConfigKeys for [T; N] {
+    type KeyPaths = ConfigKeyPath<Self>;
+
+    fn key_paths_at(key_path: String) -> Self::KeyPaths {
+        ConfigKeyPath::new(key_path)
+    }
+}
+
+/// toml can't serialize a lone value, so it goes through a document with a single key.
+#[derive(Serialize, Deserialize)]
+struct KeyValue<V> {
+    value: V,
+}
+
+fn item_to_typed_value<V: DeserializeOwned>(item: Option<&Item>) -> Result<V, Error> {
+    let mut document = DocumentMut::new();
+
+    if let Some(item) = item {
+        document.insert("value", item.clone());
+    }
+
+    toml::from_str::<KeyValue<V>>(&document.to_string())
+        .map(|key_value| key_value.value)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))
+}
+
+/// `None` if the value serializes to nothing (e.g: a `None`).
+fn typed_value_to_item<V: Serialize>(value: &V) -> Result<Option<Item>, Error> {
+    let mut document = toml::to_string(&KeyValue { value })
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?
+        .parse::<DocumentMut>()
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    Ok(document.remove("value"))
+}
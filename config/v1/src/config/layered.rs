@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use log::{debug, warn};
+use serde::{de::DeserializeOwned, Serialize};
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::{config::{check_template_constraints, edit::is_toml_value_different, key_path::ConfigKeyPath, CConfig}, error::Error, toml_path::{get_item_by_path, get_item_by_path_mut, get_leaf_key_paths, insert_item_by_path, split_key_path, take_item_by_path}};
+
+/// Prefix of the environment variables that override config keys, followed by the app's name.
+pub const ENV_OVERRIDE_PREFIX: &str = "CTK";
+
+/// Where the value of a config key came from. Later layers win over earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigLayer {
+    Template,
+    UserFile,
+    /// e.g: `CTK_<APP>_MISC__ENABLE_CUSTOM_FOLDER=true`
+    Environment,
+    /// e.g: `--set misc.enable_custom_folder=true`
+    CommandLine,
+}
+
+/// A value that overrides a config key for the current run only, it never gets saved to the user's config.
+#[derive(Debug, Clone)]
+pub struct ConfigOverride {
+    pub key_path: String,
+    pub value: Value,
+    pub layer: ConfigLayer,
+}
+
+impl ConfigOverride {
+    /// Overrides the key at `key_path` (see [`ConfigKeys`](crate::ConfigKeys)) with `value`.
+    pub fn new<V: Serialize + DeserializeOwned>(key_path: &ConfigKeyPath<V>, value: &V, layer: ConfigLayer) -> Result<Self, Error> {
+        Ok(
+            Self {
+                key_path: key_path.to_string(),
+                value: key_path.to_toml_value(value)?,
+                layer,
+            }
+        )
+    }
+
+    /// Parses a `key.path=value` override. The value is read as toml (e.g: `true`, `15`,
+    /// `"meow"`, `[1, 2]`) and falls back to a plain string if it isn't valid toml.
+    pub fn parse(argument: &str, layer: ConfigLayer) -> Result<Self, Error> {
+        let (key_path, value) = argument.split_once('=')
+            .map(|(key_path, value)| (key_path.trim(), value.trim()))
+            .filter(|(key_path, _)| !key_path.is_empty())
+            .ok_or_else(|| Error::InvalidConfigOverride { argument: argument.to_string() })?;
+
+        Ok(
+            Self {
+                key_path: key_path.to_string(),
+                value: parse_override_value(value),
+                layer,
+            }
+        )
+    }
+}
+
+/// The config resolved from all of its layers along with where each key's value came from.
+pub struct ResolvedConfig<T: CConfig> {
+    pub config: T,
+    /// The layer each key path (of a value) was taken from.
+    pub sources: HashMap<String, ConfigLayer>,
+    pub overrides: Vec<ConfigOverride>,
+}
+
+impl<T: CConfig> ResolvedConfig<T> {
+    pub fn source_of(&self, key_path: &str) -> Option<ConfigLayer> {
+        self.sources.get(key_path).copied()
+    }
+}
+
+/// Collects the overrides set with environment variables for `app_name`.
+///
+/// `CTK_<APP>_MISC__ENABLE_CUSTOM_FOLDER=true` overrides `misc.enable_custom_folder`, the app's
+/// name is upper cased with anything that isn't a letter or number replaced with an underscore.
+pub fn env_overrides(app_name: &str) -> Vec<ConfigOverride> {
+    env_overrides_from_vars(app_name, std::env::vars())
+}
+
+/// Same as [`env_overrides`] but reads from `vars` instead of the process environment.
+pub fn env_overrides_from_vars(app_name: &str, vars: impl IntoIterator<Item = (String, String)>) -> Vec<ConfigOverride> {
+    let app_name: String = app_name.chars()
+        .map(|char| if char.is_ascii_alphanumeric() { char.to_ascii_uppercase() } else { '_' })
+        .collect();
+
+    let prefix = format!("{}_{}_", ENV_OVERRIDE_PREFIX, app_name);
+
+    let mut overrides: Vec<ConfigOverride> = vars.into_iter()
+        .filter_map(|(name, value)| {
+            let key_path = name.strip_prefix(&prefix)?
+                .split("__")
+                .map(|part| part.to_lowercase())
+                .collect::<Vec<String>>()
+                .join(".");
+
+            Some(
+                ConfigOverride {
+                    key_path,
+                    value: parse_override_value(&value),
+                    layer: ConfigLayer::Environment,
+                }
+            )
+        })
+        .collect();
+
+    // the environment has no order so we sort to at least be deterministic
+    overrides.sort_by(|a, b| a.key_path.cmp(&b.key_path));
+
+    overrides
+}
+
+/// Parses every `key.path=value` passed with `--set` (see `cirrus_clap_cli::ConfigOverrideArgs`).
+pub fn cli_overrides(arguments: &[String]) -> Result<Vec<ConfigOverride>, Error> {
+    arguments.iter()
+        .map(|argument| ConfigOverride::parse(argument, ConfigLayer::CommandLine))
+        .collect()
+}
+
+/// Resolves the config from its layers: the template's defaults, then the user's config file
+/// then the `overrides` in order (pass the environment's before the command line's).
+pub fn resolve_config<T: CConfig>(
+    template_config_toml_string: &str,
+    user_config_toml_string: Option<&str>,
+    overrides: Vec<ConfigOverride>
+) -> Result<ResolvedConfig<T>, Error> {
+    let mut document: DocumentMut = template_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::TemplateConfigParseFailure { error: error.to_string() })?;
+
+    let mut sources: HashMap<String, ConfigLayer> = get_leaf_key_paths(document.as_item())
+        .into_iter()
+        .map(|key_path| (key_path, ConfigLayer::Template))
+        .collect();
+
+    if let Some(user_config_toml_string) = user_config_toml_string {
+        let user_document: DocumentMut = user_config_toml_string.parse()
+            .map_err(|error: toml_edit::TomlError| Error::FailedToReadConfig(error.to_string()))?;
+
+        for key_path in get_leaf_key_paths(user_document.as_item()) {
+            if let Some(item) = get_item_by_path(user_document.as_item(), &key_path) {
+                insert_item_by_path(document.as_item_mut(), &key_path, None, item.clone())
+                    .map_err(Error::FailedToReadConfig)?;
+            }
+
+            sources.insert(key_path, ConfigLayer::UserFile);
+        }
+    }
+
+    apply_overrides(&mut document, &overrides)?;
+
+    for config_override in &overrides {
+        sources.insert(config_override.key_path.clone(), config_override.layer);
+    }
+
+    let config = toml::from_str::<T>(&document.to_string())
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    check_template_constraints(&document, template_config_toml_string)?;
+
+    Ok(ResolvedConfig { config, sources, overrides })
+}
+
+/// Sets every override's value in the document, later overrides win.
+pub fn apply_overrides(document: &mut DocumentMut, overrides: &[ConfigOverride]) -> Result<(), Error> {
+    for config_override in overrides {
+        debug!("Overriding config key '{}' ({:?})...", config_override.key_path, config_override.layer);
+
+        if get_item_by_path(document.as_item(), &config_override.key_path).is_none() {
+            warn!(
+                "The config key '{}' being overridden does not exist, is it a typo?",
+                config_override.key_path
+            );
+        }
+
+        insert_item_by_path(
+            document.as_item_mut(),
+            &config_override.key_path,
+            None,
+            Item::Value(config_override.value.clone())
+        ).map_err(|_| Error::InvalidConfigOverride { argument: config_override.key_path.clone() })?;
+    }
+
+    Ok(())
+}
+
+/// Puts back `disk_document`'s values for keys in `updated_document` that still hold their override's value,
+/// so transient overrides never make it into the user's config. Keys that have since been changed (e.g: by
+/// the user in the app) no longer hold the override's value and are kept like any other change.
+///
+/// Keys that were only added by an override (not on disk) are removed.
+pub fn mask_overrides(updated_document: &mut DocumentMut, disk_document: &DocumentMut, overrides: &[ConfigOverride]) {
+    for config_override in overrides {
+        let key_path = &config_override.key_path;
+
+        let is_still_overridden = get_item_by_path(updated_document.as_item(), key_path)
+            .and_then(Item::as_value)
+            .is_some_and(|value| !is_toml_value_different(value, &config_override.value));
+
+        if !is_still_overridden {
+            continue;
+        }
+
+        debug!("Not saving config key '{}' as it's overridden for this run...", key_path);
+
+        match get_item_by_path(disk_document.as_item(), key_path) {
+            Some(disk_item) => {
+                if let Some(item) = get_item_by_path_mut(updated_document.as_item_mut(), key_path) {
+                    *item = disk_item.clone();
+                }
+            },
+            None => {
+                take_item_by_path(updated_document.as_item_mut(), key_path);
+
+                // along with any tables that were only made for the override
+                let mut parent_path = split_key_path(key_path).0;
+
+                while let Some(table_path) = parent_path {
+                    let is_empty_made_up_table = get_item_by_path(disk_document.as_item(), table_path).is_none()
+                        && get_item_by_path(updated_document.as_item(), table_path)
+                            .and_then(Item::as_table_like)
+                            .is_some_and(|table_like| table_like.is_empty());
+
+                    if !is_empty_made_up_table {
+                        break;
+                    }
+
+                    take_item_by_path(updated_document.as_item_mut(), table_path);
+                    parent_path = split_key_path(table_path).0;
+                }
+            },
+        }
+    }
+}
+
+fn parse_override_value(value: &str) -> Value {
+    value.parse::<Value>()
+        .unwrap_or_else(|_| Value::from(value))
+}
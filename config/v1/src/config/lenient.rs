@@ -0,0 +1,284 @@
+use std::{collections::HashSet, fmt::Display};
+
+use cirrus_error::error::CError;
+use log::{debug, warn};
+use toml_edit::{Document, DocumentMut, Item, Table, Value};
+
+use crate::{config::CConfig, error::Error, template::Template, toml_path::{get_item_by_path, get_item_by_path_mut, insert_item_by_path, is_table_like_at_path, split_key_path, take_item_by_path}};
+
+/// A problem found with a single key of the user's config while loading it leniently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDiagnostic {
+    /// Period separated path to the troubled key. Empty if the problem is with the whole file.
+    pub key_path: String,
+    pub reason: ConfigDiagnosticReason,
+    /// The user's value for this key as it was written in their config file.
+    pub offending_value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigDiagnosticReason {
+    /// The key exists in the template but not in the user's config.
+    MissingKey,
+    /// The user's value is of a different type than the template's value.
+    InvalidType { expected: String, found: String },
+    /// The value is the right type but could not be deserialized (e.g: out of range, unknown choice).
+    InvalidValue { error: String },
+    /// The value breaks a constraint declared in the template (`@range` or `@choices`).
+    ConstraintViolation { error: String },
+    /// The config file is not valid toml, every key falls back to the template.
+    ParseFailure { error: String },
+}
+
+impl CError for ConfigDiagnostic {}
+
+impl Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key_path = &self.key_path;
+
+        match &self.reason {
+            ConfigDiagnosticReason::MissingKey => write!(
+                f, "The config key '{key_path}' is missing, the default value will be used."
+            ),
+            ConfigDiagnosticReason::InvalidType { expected, found } => write!(
+                f, "The config key '{key_path}' should be a {expected} but it's a {found}, the default value will be used."
+            ),
+            ConfigDiagnosticReason::InvalidValue { .. } => write!(
+                f, "The config key '{key_path}' has an invalid value, the default value will be used."
+            ),
+            ConfigDiagnosticReason::ConstraintViolation { error } => write!(
+                f, "The config key '{key_path}' is invalid ({error}), the default value will be used."
+            ),
+            ConfigDiagnosticReason::ParseFailure { .. } => write!(
+                f, "The config file could not be read as toml, the default config will be used."
+            ),
+        }
+    }
+}
+
+/// Deserializes the user's config key by key, falling back to the template's
+/// value (`TemplateKey.defined_toml_value`) for every key that is missing or invalid
+/// instead of failing like [`toml::from_str`] would.
+///
+/// Returns the config along with a diagnostic for every key that had to fall back.
+pub fn parse_config_lenient<T: CConfig>(
+    config_toml_string: &str,
+    template_config_toml_string: &str
+) -> Result<(T, Vec<ConfigDiagnostic>), Error> {
+    let mut template = Template::new(template_config_toml_string);
+    template.parse_keys()?;
+
+    let template_document: DocumentMut = template_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::TemplateConfigParseFailure { error: error.to_string() })?;
+
+    let mut diagnostics = Vec::new();
+
+    let mut merged_document: DocumentMut = match config_toml_string.parse() {
+        Ok(document) => document,
+        Err(error) => {
+            warn!("Failed to parse the user's config, falling back to the template! Error: {}", error);
+
+            diagnostics.push(
+                ConfigDiagnostic {
+                    key_path: String::new(),
+                    reason: ConfigDiagnosticReason::ParseFailure { error: error.to_string() },
+                    offending_value: None,
+                }
+            );
+
+            template_document.clone()
+        },
+    };
+
+    for key_path in &template.ordered_paths {
+        let template_value = &template.keys[key_path].defined_toml_value;
+
+        let diagnostic = match get_item_by_path(merged_document.as_item(), key_path) {
+            // keys inside arrays of tables (e.g: 'plugins[1].name')
+            None if key_path.contains('[') => {
+                let (array_path, _) = key_path.split_once('[').unwrap_or_default();
+
+                if get_item_by_path(merged_document.as_item(), array_path).is_some() {
+                    let (parent_path, _) = split_key_path(key_path);
+
+                    // the user having less tables in the array than the template is perfectly fine
+                    if !parent_path.is_some_and(|parent_path| is_table_like_at_path(merged_document.as_item(), parent_path)) {
+                        continue;
+                    }
+
+                    ConfigDiagnostic {
+                        key_path: key_path.clone(),
+                        reason: ConfigDiagnosticReason::MissingKey,
+                        offending_value: None,
+                    }
+                } else {
+                    // the whole array is missing so it's taken from the template as a whole
+                    let diagnostic = ConfigDiagnostic {
+                        key_path: array_path.to_string(),
+                        reason: ConfigDiagnosticReason::MissingKey,
+                        offending_value: None,
+                    };
+
+                    debug!("{}", diagnostic);
+                    diagnostics.push(diagnostic);
+
+                    replace_with_template_item(&mut merged_document, &template_document, array_path)?;
+                    continue;
+                }
+            },
+            None => ConfigDiagnostic {
+                key_path: key_path.clone(),
+                reason: ConfigDiagnosticReason::MissingKey,
+                offending_value: None,
+            },
+            Some(Item::Value(value)) => {
+                let is_same_type = value.type_name() == template_value.type_name()
+                    // serde is happy to deserialize integers into floats
+                    || (value.is_integer() && template_value.is_float());
+
+                if is_same_type {
+                    continue;
+                }
+
+                ConfigDiagnostic {
+                    key_path: key_path.clone(),
+                    reason: ConfigDiagnosticReason::InvalidType {
+                        expected: template_value.type_name().to_string(),
+                        found: value.type_name().to_string(),
+                    },
+                    offending_value: Some(value_to_string(value)),
+                }
+            },
+            Some(item) => ConfigDiagnostic {
+                key_path: key_path.clone(),
+                reason: ConfigDiagnosticReason::InvalidType {
+                    expected: template_value.type_name().to_string(),
+                    found: item.type_name().to_string(),
+                },
+                offending_value: None,
+            },
+        };
+
+        debug!("{}", diagnostic);
+        diagnostics.push(diagnostic);
+
+        replace_with_template_item(&mut merged_document, &template_document, key_path)?;
+    }
+
+    for violation in template.check_constraints(&merged_document) {
+        let offending_value = get_item_by_path(merged_document.as_item(), &violation.key_path)
+            .and_then(|item| item.as_value())
+            .map(value_to_string);
+
+        let diagnostic = ConfigDiagnostic {
+            key_path: violation.key_path.clone(),
+            reason: ConfigDiagnosticReason::ConstraintViolation { error: violation.error },
+            offending_value,
+        };
+
+        debug!("{}", diagnostic);
+        diagnostics.push(diagnostic);
+
+        replace_with_template_item(&mut merged_document, &template_document, &violation.key_path)?;
+    }
+
+    // Types match the template now but values can still fail to deserialize into 'T' (e.g: a number
+    // too large for a u8 or a string that isn't one of an enum's variants) so we deserialize, find the
+    // key the error points at, fall back to the template for that key and try again until we succeed.
+    let mut attempted_key_paths: HashSet<String> = HashSet::new();
+
+    loop {
+        let merged_toml_string = merged_document.to_string();
+
+        let error = match toml::from_str::<T>(&merged_toml_string) {
+            Ok(config) => return Ok((config, diagnostics)),
+            Err(error) => error,
+        };
+
+        let key_path = error.span()
+            .and_then(|span| find_key_path_at(&merged_toml_string, span.start))
+            .filter(|key_path| attempted_key_paths.insert(key_path.clone()))
+            .ok_or_else(|| Error::FailedToReadConfig(error.to_string()))?;
+
+        let offending_value = get_item_by_path(merged_document.as_item(), &key_path)
+            .and_then(|item| item.as_value())
+            .map(value_to_string);
+
+        let diagnostic = ConfigDiagnostic {
+            key_path: key_path.clone(),
+            reason: ConfigDiagnosticReason::InvalidValue { error: error.message().to_string() },
+            offending_value,
+        };
+
+        debug!("{}", diagnostic);
+        diagnostics.push(diagnostic);
+
+        replace_with_template_item(&mut merged_document, &template_document, &key_path)?;
+    }
+}
+
+/// The value as it was written by the user, without the comments or whitespace around it.
+fn value_to_string(value: &Value) -> String {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+
+    value.to_string()
+}
+
+fn replace_with_template_item(document: &mut DocumentMut, template_document: &DocumentMut, key_path: &str) -> Result<(), Error> {
+    let template_item = get_item_by_path(template_document.as_item(), key_path).cloned();
+
+    match (get_item_by_path_mut(document.as_item_mut(), key_path), template_item) {
+        (Some(item), Some(template_item)) => *item = template_item,
+        (None, Some(template_item)) => {
+            insert_item_by_path(document.as_item_mut(), key_path, None, template_item)
+                .map_err(Error::FailedToReadConfig)?;
+        },
+        // not in the template, so the best we can do is get rid of it
+        (Some(_), None) => {
+            take_item_by_path(document.as_item_mut(), key_path);
+        },
+        (None, None) => {},
+    }
+
+    Ok(())
+}
+
+/// Finds the path of the deepest key whose value contains the byte offset `position`.
+fn find_key_path_at(toml_string: &str, position: usize) -> Option<String> {
+    let document: Document<&str> = Document::parse(toml_string).ok()?;
+
+    find_key_path_in_table(document.as_table(), None, position)
+}
+
+fn find_key_path_in_table(table: &Table, path: Option<&str>, position: usize) -> Option<String> {
+    for (key, item) in table.iter() {
+        let key_path = match path {
+            Some(path) => format!("{}.{}", path, key),
+            None => key.to_string(),
+        };
+
+        match item {
+            Item::Table(child_table) => {
+                if let Some(key_path) = find_key_path_in_table(child_table, Some(&key_path), position) {
+                    return Some(key_path);
+                }
+            },
+            Item::ArrayOfTables(array_of_tables) => {
+                for (index, child_table) in array_of_tables.iter().enumerate() {
+                    let child_key_path = format!("{}[{}]", key_path, index);
+
+                    if let Some(key_path) = find_key_path_in_table(child_table, Some(&child_key_path), position) {
+                        return Some(key_path);
+                    }
+                }
+            },
+            Item::Value(_) if item.span().is_some_and(|span| span.contains(&position)) => {
+                return Some(key_path);
+            },
+            _ => {},
+        }
+    }
+
+    None
+}
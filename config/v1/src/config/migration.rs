@@ -0,0 +1,215 @@
+use log::{debug, warn};
+use toml_edit::{DocumentMut, Value};
+
+use crate::{error::Error, toml_path::{get_item_by_path, get_item_by_path_mut, insert_item_by_path, take_item_by_path}};
+
+/// The root key in every config file that states what version of the template it follows.
+pub const CONFIG_VERSION_KEY: &str = "version";
+
+/// Configs from before the `version` key was introduced are assumed to be on version 1.
+pub const DEFAULT_CONFIG_VERSION: i64 = 1;
+
+/// A single upgrade operation that is performed on the user's config document.
+///
+/// Key paths are period separated and start from the root of
+/// the document, exactly like the ones `config_key_path!()` gives you.
+pub enum MigrationStep {
+    /// Renames / moves a key to a new key path, keeping its value and comments.
+    RenameKey { from: String, to: String },
+    /// Moves a whole table (including all of its keys) to a new key path.
+    MoveTable { from: String, to: String },
+    /// Converts the value of a key into a different type (e.g: `"7"` into `7`).
+    ///
+    /// The migration fails if `convert` returns `None`.
+    ChangeType { path: String, convert: fn(&Value) -> Option<Value> },
+    /// Removes a key or table from the config.
+    DropKey { path: String },
+}
+
+impl MigrationStep {
+    pub fn rename_key(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::RenameKey { from: from.into(), to: to.into() }
+    }
+
+    pub fn move_table(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::MoveTable { from: from.into(), to: to.into() }
+    }
+
+    pub fn change_type(path: impl Into<String>, convert: fn(&Value) -> Option<Value>) -> Self {
+        Self::ChangeType { path: path.into(), convert }
+    }
+
+    pub fn drop_key(path: impl Into<String>) -> Self {
+        Self::DropKey { path: path.into() }
+    }
+
+    fn apply(&self, document: &mut DocumentMut, version: i64) -> Result<(), Error> {
+        match self {
+            MigrationStep::RenameKey { from, to } | MigrationStep::MoveTable { from, to } => {
+                debug!("Moving config key '{}' to '{}'...", from, to);
+
+                let (key, item) = match take_item_by_path(document.as_item_mut(), from) {
+                    Some(entry) => entry,
+                    None => {
+                        debug!("Config key '{}' does not exist, nothing to move.", from);
+                        return Ok(());
+                    },
+                };
+
+                if let MigrationStep::MoveTable { .. } = self && !item.is_table_like() {
+                    return Err(
+                        Error::ConfigMigrationFailure {
+                            version,
+                            error: format!("Expected '{from}' to be a table but it was a {}!", item.type_name())
+                        }
+                    );
+                }
+
+                if get_item_by_path(document.as_item(), to).is_some() {
+                    warn!("The config key '{}' already exists, it will be overwritten!", to);
+                }
+
+                insert_item_by_path(document.as_item_mut(), to, Some(&key), item)
+                    .map_err(|error| Error::ConfigMigrationFailure { version, error })
+            },
+            MigrationStep::ChangeType { path, convert } => {
+                let value = match get_item_by_path_mut(document.as_item_mut(), path).and_then(|item| item.as_value_mut()) {
+                    Some(value) => value,
+                    None => {
+                        debug!("Config key '{}' does not exist, nothing to convert.", path);
+                        return Ok(());
+                    },
+                };
+
+                let mut converted_value = convert(value)
+                    .ok_or_else(
+                        || Error::ConfigMigrationFailure {
+                            version,
+                            error: format!("Failed to convert the value '{}' of '{path}'!", value.to_string().trim())
+                        }
+                    )?;
+
+                // carry over comments after the value (e.g: "key = 1 # comment")
+                *converted_value.decor_mut() = value.decor().clone();
+
+                *value = converted_value;
+
+                Ok(())
+            },
+            MigrationStep::DropKey { path } => {
+                debug!("Dropping config key '{}'...", path);
+
+                if take_item_by_path(document.as_item_mut(), path).is_none() {
+                    debug!("Config key '{}' does not exist, nothing to drop.", path);
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+/// The steps required to upgrade a config to `version` from the version before it.
+pub struct Migration {
+    pub version: i64,
+    pub steps: Vec<MigrationStep>,
+}
+
+/// Registry of every migration an app's config has gone through.
+///
+/// The `version` key in your `config.template.toml` should always
+/// be equal to the version of the latest migration you've registered.
+///
+/// ```ignore
+/// let migrations = Migrations::new()
+///     .add(2, vec![
+///         MigrationStep::rename_key("misc.custom_folder", "misc.enable_custom_folder"),
+///         MigrationStep::drop_key("misc.legacy_renderer"),
+///     ])
+///     .add(3, vec![
+///         MigrationStep::move_table("key_binds", "misc.key_binds"),
+///     ]);
+/// ```
+#[derive(Default)]
+pub struct Migrations {
+    migrations: Vec<Migration>,
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the steps needed to upgrade a config from `version - 1` to `version`.
+    pub fn add(mut self, version: i64, steps: Vec<MigrationStep>) -> Self {
+        self.migrations.push(Migration { version, steps });
+        self.migrations.sort_by_key(|migration| migration.version);
+
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    pub fn latest_version(&self) -> Option<i64> {
+        self.migrations.last().map(|migration| migration.version)
+    }
+
+    /// Runs every migration newer than the document's version in order, editing the document
+    /// in place so comments and formatting are preserved. The `version` key is bumped as it goes.
+    ///
+    /// Returns the version the document was on before migrating, or `None` if nothing had to be done.
+    pub fn migrate(&self, document: &mut DocumentMut) -> Result<Option<i64>, Error> {
+        let document_version = get_config_version(document)?;
+
+        if let Some(latest_version) = self.latest_version() && document_version > latest_version {
+            warn!(
+                "The config is on version '{}' which is newer than the latest known version '{}'! \
+                    It will not be migrated, was it created by a newer version of this app?",
+                document_version, latest_version
+            );
+        }
+
+        let mut migrated = false;
+
+        for migration in &self.migrations {
+            if migration.version <= document_version {
+                continue;
+            }
+
+            debug!("Migrating config to version '{}'...", migration.version);
+
+            for step in &migration.steps {
+                step.apply(document, migration.version)?;
+            }
+
+            set_config_version(document, migration.version);
+            migrated = true;
+        }
+
+        Ok(migrated.then_some(document_version))
+    }
+}
+
+pub fn get_config_version(document: &DocumentMut) -> Result<i64, Error> {
+    match document.get(CONFIG_VERSION_KEY) {
+        Some(item) => item.as_integer()
+            .ok_or_else(|| Error::ConfigVersionInvalid { version: item.to_string().trim().to_string() }),
+        None => Ok(DEFAULT_CONFIG_VERSION),
+    }
+}
+
+fn set_config_version(document: &mut DocumentMut, version: i64) {
+    match document.get_mut(CONFIG_VERSION_KEY).and_then(|item| item.as_value_mut()) {
+        Some(value) => {
+            let decor = value.decor().clone();
+
+            *value = Value::from(version);
+            *value.decor_mut() = decor;
+        },
+        None => {
+            document.insert(CONFIG_VERSION_KEY, toml_edit::value(version));
+        },
+    }
+}
@@ -0,0 +1,281 @@
+use std::{fs, hash::Hash, path::{Path, PathBuf}};
+
+use log::{debug, warn};
+use serde::{de::DeserializeOwned, Serialize};
+use toml_edit::DocumentMut;
+use cirrus_path::{get_user_config_cloudy_folder_path};
+
+use crate::{error::Error, template::Template};
+
+pub mod migration;
+pub mod lenient;
+pub mod watcher;
+pub mod persist;
+pub mod layered;
+pub mod edit;
+pub mod reset;
+pub mod history;
+pub mod profile;
+pub mod bundle;
+pub mod format;
+pub mod key_path;
+pub mod subscription;
+
+use migration::Migrations;
+use lenient::{parse_config_lenient, ConfigDiagnostic};
+use persist::write_config_file;
+use format::{ConfigFormat, TomlFormat};
+use layered::{resolve_config, ConfigOverride, ResolvedConfig};
+
+pub trait CConfig: DeserializeOwned + Serialize + Hash + Default {}
+
+pub fn get_and_create_config_file<T: CConfig>(app_name: &str, template_config_toml_string: &str) -> Result<(T, PathBuf), Error> {
+    get_and_create_config_file_with_migrations(app_name, template_config_toml_string, &Migrations::new())
+}
+
+/// Same as [`get_and_create_config_file`] but upgrades the user's existing config
+/// with `migrations` before deserializing it. See [`Migrations`] for more info.
+pub fn get_and_create_config_file_with_migrations<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations
+) -> Result<(T, PathBuf), Error> {
+    get_and_create_config_file_with_format(app_name, template_config_toml_string, migrations, &TomlFormat)
+}
+
+/// Same as [`get_and_create_config_file_with_migrations`] but the user's config is written in `format`
+/// (e.g: `config.yaml`) instead of TOML. The template stays TOML, see [`ConfigFormat`] for more info.
+pub fn get_and_create_config_file_with_format<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations,
+    format: &dyn ConfigFormat
+) -> Result<(T, PathBuf), Error> {
+    let config_path = get_config_file_path_with_format(app_name, format)?;
+
+    if config_path.exists() {
+        migrate_config_file_with_format(&config_path, migrations, format)?;
+
+        debug!("Reading and deserializing config file...");
+
+        return match fs::read_to_string(&config_path) {
+            Ok(value) => Ok(
+                (
+                    parse_config::<T>(&format.to_toml_string(&value)?, template_config_toml_string)?,
+                    config_path
+                )
+            ),
+            Err(error) => Err(Error::FailedToReadConfig(error.to_string())),
+        };
+    }
+
+    Ok((create_config_file(&config_path, template_config_toml_string, format)?, config_path))
+}
+
+/// Deserializes the user's config, failing if it's invalid or if one of its values
+/// breaks a constraint declared in the template (see [`KeyConstraints`](crate::template::KeyConstraints)).
+pub fn parse_config<T: CConfig>(config_toml_string: &str, template_config_toml_string: &str) -> Result<T, Error> {
+    let config = toml::from_str::<T>(config_toml_string)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    let document = config_toml_string.parse::<DocumentMut>()
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    check_template_constraints(&document, template_config_toml_string)?;
+
+    Ok(config)
+}
+
+pub(crate) fn check_template_constraints(document: &DocumentMut, template_config_toml_string: &str) -> Result<(), Error> {
+    let mut template = Template::new(template_config_toml_string);
+    template.parse_keys()?;
+
+    match template.check_constraints(document).into_iter().next() {
+        Some(violation) => Err(
+            Error::ConfigConstraintViolation { key_path: violation.key_path, error: violation.error }
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Same as [`get_and_create_config_file_with_migrations`] but invalid or missing keys in the user's
+/// config fall back to the template's values instead of failing, so one bad value can't stop
+/// the app from starting. Every key that had to fall back is returned as a [`ConfigDiagnostic`].
+pub fn get_and_create_config_file_lenient<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations
+) -> Result<(T, PathBuf, Vec<ConfigDiagnostic>), Error> {
+    get_and_create_config_file_lenient_with_format(app_name, template_config_toml_string, migrations, &TomlFormat)
+}
+
+/// Same as [`get_and_create_config_file_lenient`] but the user's config is written in `format`.
+pub fn get_and_create_config_file_lenient_with_format<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations,
+    format: &dyn ConfigFormat
+) -> Result<(T, PathBuf, Vec<ConfigDiagnostic>), Error> {
+    let config_path = get_config_file_path_with_format(app_name, format)?;
+
+    if config_path.exists() {
+        // A config that fails to migrate is still worth loading leniently as
+        // the keys that don't line up with the template will just fall back.
+        if let Err(error) = migrate_config_file_with_format(&config_path, migrations, format) {
+            warn!("Failed to migrate the config, loading it as is! Error: {}", error);
+        }
+
+        debug!("Reading and leniently deserializing config file...");
+
+        let config_string = fs::read_to_string(&config_path)
+            .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+        let (config, diagnostics) = parse_config_lenient::<T>(
+            &format.to_toml_string(&config_string)?, template_config_toml_string
+        )?;
+
+        return Ok((config, config_path, diagnostics));
+    }
+
+    Ok(
+        (
+            create_config_file(&config_path, template_config_toml_string, format)?,
+            config_path,
+            Vec::new()
+        )
+    )
+}
+
+/// Same as [`get_and_create_config_file_with_migrations`] but the config is resolved in layers: the template's
+/// defaults, the user's config file, then the `overrides` (see [`layered::env_overrides`] and [`layered::cli_overrides`]).
+/// Keys missing from the user's config fall back to the template and the overrides are never written to disk.
+pub fn get_and_create_config_file_layered<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations,
+    overrides: Vec<ConfigOverride>
+) -> Result<(ResolvedConfig<T>, PathBuf), Error> {
+    get_and_create_config_file_layered_with_format(app_name, template_config_toml_string, migrations, overrides, &TomlFormat)
+}
+
+/// Same as [`get_and_create_config_file_layered`] but the user's config is written in `format`.
+pub fn get_and_create_config_file_layered_with_format<T: CConfig>(
+    app_name: &str,
+    template_config_toml_string: &str,
+    migrations: &Migrations,
+    overrides: Vec<ConfigOverride>,
+    format: &dyn ConfigFormat
+) -> Result<(ResolvedConfig<T>, PathBuf), Error> {
+    let config_path = get_config_file_path_with_format(app_name, format)?;
+
+    if config_path.exists() {
+        migrate_config_file_with_format(&config_path, migrations, format)?;
+    } else {
+        create_config_file::<T>(&config_path, template_config_toml_string, format)?;
+    }
+
+    debug!("Reading and resolving config file with {} override(s)...", overrides.len());
+
+    let config_string = fs::read_to_string(&config_path)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    let resolved_config = resolve_config::<T>(
+        template_config_toml_string, Some(&format.to_toml_string(&config_string)?), overrides
+    )?;
+
+    Ok((resolved_config, config_path))
+}
+
+/// Returns the path to the app's config file of `format` (e.g: `config.yaml`), creating the app's config directory if it's missing.
+pub(crate) fn get_config_file_path_with_format(app_name: &str, format: &dyn ConfigFormat) -> Result<PathBuf, Error> {
+    let config_dir_path = get_user_config_cloudy_folder_path()
+        .map_err(|error| Error::UserConfigPathNotFound {error: error.to_string()})?
+        .join(app_name);
+
+    if !config_dir_path.exists() {
+        debug!("Config directory missing ({}), creating dir for '{}'...", config_dir_path.display(), app_name);
+
+        if let Err(error) = fs::create_dir_all(&config_dir_path) {
+            return Err(Error::FailedToCreateConfigDirectory(error.to_string()));
+        }
+
+        debug!("Config directory created!");
+    }
+
+    Ok(config_dir_path.join(format.file_name()))
+}
+
+fn create_config_file<T: CConfig>(
+    config_path: &Path,
+    template_config_toml_string: &str,
+    format: &dyn ConfigFormat
+) -> Result<T, Error> {
+    debug!(
+        "Reading template config and creating config file at '{}'...",
+        &config_path.display()
+    );
+
+    let config_string = format.to_config_string(template_config_toml_string, None)?;
+
+    // there's nothing to back up yet
+    write_config_file(config_path, &config_string, 0)?;
+
+    Ok(
+        toml::from_str(template_config_toml_string)
+            .expect("Failed to deserialize template toml file!")
+        // I'm panicking here as if this fails to deserialize it's our fault!
+        // Apps should have a test with 'assert_config_template!()' to
+        // ensure this doesn't happen from our end.
+    )
+}
+
+/// Runs `migrations` on the config file at `config_path`. If the config gets upgraded, a
+/// backup of the original file is written next to it (e.g: `config.toml.v1.bak`) before
+/// the upgraded config is saved over it.
+///
+/// Returns `true` if the config was migrated.
+pub fn migrate_config_file(config_path: &Path, migrations: &Migrations) -> Result<bool, Error> {
+    migrate_config_file_with_format(config_path, migrations, &TomlFormat)
+}
+
+/// Same as [`migrate_config_file`] but for a config file written in `format`.
+pub fn migrate_config_file_with_format(
+    config_path: &Path,
+    migrations: &Migrations,
+    format: &dyn ConfigFormat
+) -> Result<bool, Error> {
+    if migrations.is_empty() {
+        return Ok(false);
+    }
+
+    let config_string = fs::read_to_string(config_path)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    let mut document = format.to_toml_string(&config_string)?.parse::<DocumentMut>()
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    let previous_version = match migrations.migrate(&mut document)? {
+        Some(previous_version) => previous_version,
+        None => return Ok(false),
+    };
+
+    let mut backup_file_name = config_path.file_name()
+        .unwrap_or_default()
+        .to_os_string();
+
+    backup_file_name.push(format!(".v{}.bak", previous_version));
+
+    let backup_path = config_path.with_file_name(backup_file_name);
+
+    debug!("Config migrated, backing up the original config to '{}'...", backup_path.display());
+
+    fs::copy(config_path, &backup_path)
+        .map_err(|error| Error::FailedToBackupConfig { error: error.to_string() })?;
+
+    // the versioned backup above is all we need, no point rotating it into the '.bak.N' backups too
+    let migrated_config_string = format.to_config_string(&document.to_string(), Some(&config_string))?;
+
+    write_config_file(config_path, &migrated_config_string, 0)?;
+
+    Ok(true)
+}
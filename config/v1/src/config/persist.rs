@@ -0,0 +1,138 @@
+use std::{ffi::OsString, fs::{self, File}, io::Write, path::{Path, PathBuf}, time::SystemTime};
+
+use log::{debug, warn};
+
+use crate::error::Error;
+
+/// How many `config.toml.bak.N` backups are kept around by default.
+pub const DEFAULT_BACKUP_COUNT: usize = 3;
+
+/// A rolling backup of the config file, `index` 1 being the most recent one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigBackup {
+    pub index: usize,
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+/// The one and only way the config file should be written to disk.
+///
+/// The current config gets rotated into the `config.toml.bak.N` backups (keeping at most `backup_count`
+/// of them), then `contents` is written to a temporary file, fsynced and atomically renamed over the
+/// config. That way a crash or a full disk mid-write can never leave the user with a truncated config.
+pub fn write_config_file(config_path: &Path, contents: &str, backup_count: usize) -> Result<(), Error> {
+    if backup_count > 0 && config_path.exists() {
+        rotate_backups(config_path, backup_count)?;
+    }
+
+    let temp_path = path_with_suffix(config_path, ".tmp");
+
+    debug!("Writing config to '{}' before moving it into place...", temp_path.display());
+
+    let result = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, config_path));
+
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp_path);
+
+        return Err(Error::FailedToWriteToConfig(error.to_string()));
+    }
+
+    // The rename itself is only durable once the directory is synced. Not
+    // every platform lets us open a directory for this so it's best effort.
+    if let Some(dir_path) = config_path.parent() && let Ok(dir) = File::open(dir_path) {
+        let _ = dir.sync_all();
+    }
+
+    Ok(())
+}
+
+/// Lists the backups of the config file, most recent first.
+pub fn list_config_backups(config_path: &Path) -> Result<Vec<ConfigBackup>, Error> {
+    let (dir_path, prefix) = match (config_path.parent(), config_path.file_name()) {
+        (Some(dir_path), Some(file_name)) => (dir_path, format!("{}.bak.", file_name.to_string_lossy())),
+        _ => return Ok(Vec::new()),
+    };
+
+    let entries = match fs::read_dir(dir_path) {
+        Ok(entries) => entries,
+        Err(_) if !dir_path.exists() => return Ok(Vec::new()),
+        Err(error) => return Err(Error::FailedToReadConfig(error.to_string())),
+    };
+
+    let mut backups: Vec<ConfigBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let index = entry.file_name()
+                .to_str()?
+                .strip_prefix(&prefix)?
+                .parse::<usize>()
+                .ok()?;
+
+            Some(
+                ConfigBackup {
+                    index,
+                    path: entry.path(),
+                    modified: entry.metadata().and_then(|metadata| metadata.modified()).ok(),
+                }
+            )
+        })
+        .collect();
+
+    backups.sort_by_key(|backup| backup.index);
+
+    Ok(backups)
+}
+
+/// Writes the backup at `index` back over the config file. The config that gets replaced
+/// is rotated into the backups like any other write, so a restore can also be undone.
+pub fn restore_config_backup(config_path: &Path, index: usize, backup_count: usize) -> Result<(), Error> {
+    let backup_path = backup_path(config_path, index);
+
+    debug!("Restoring config from backup '{}'...", backup_path.display());
+
+    let contents = fs::read_to_string(&backup_path)
+        .map_err(|error| Error::FailedToRestoreConfigBackup { index, error: error.to_string() })?;
+
+    write_config_file(config_path, &contents, backup_count)
+}
+
+fn rotate_backups(config_path: &Path, backup_count: usize) -> Result<(), Error> {
+    debug!("Rotating config backups (keeping {})...", backup_count);
+
+    // Backups past the limit (e.g: the count was lowered) are cleaned up too.
+    for backup in list_config_backups(config_path)? {
+        if backup.index >= backup_count && let Err(error) = fs::remove_file(&backup.path) {
+            warn!("Failed to remove old config backup '{}'! Error: {}", backup.path.display(), error);
+        }
+    }
+
+    for index in (1..backup_count).rev() {
+        let from = backup_path(config_path, index);
+
+        if from.exists() {
+            fs::rename(&from, backup_path(config_path, index + 1))
+                .map_err(|error| Error::FailedToBackupConfig { error: error.to_string() })?;
+        }
+    }
+
+    fs::copy(config_path, backup_path(config_path, 1))
+        .map_err(|error| Error::FailedToBackupConfig { error: error.to_string() })?;
+
+    Ok(())
+}
+
+fn backup_path(config_path: &Path, index: usize) -> PathBuf {
+    path_with_suffix(config_path, &format!(".bak.{}", index))
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name: OsString = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+
+    path.with_file_name(file_name)
+}
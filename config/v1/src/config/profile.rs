@@ -0,0 +1,230 @@
+use std::{fs, io::ErrorKind, path::{Path, PathBuf}};
+
+use log::debug;
+use toml_edit::{DocumentMut, Item};
+
+use crate::{config::{edit::{clear_table_positions, is_toml_value_different, walk_and_edit_toml_item}, persist::write_config_file, reset::item_to_value}, error::Error, toml_path::{get_item_by_path, get_item_by_path_mut, get_leaf_key_paths, insert_item_by_path}};
+
+/// Name of the directory the profiles live in, next to the app's `config.toml`.
+pub const PROFILES_DIR_NAME: &str = "profiles";
+
+/// File in the profiles directory holding the name of the active profile.
+const ACTIVE_PROFILE_FILE_NAME: &str = "active";
+
+/// Named presets of the config (e.g: "presentation", "low-power") kept in a `profiles/` directory next to `config.toml`:
+///
+/// ```text
+/// ~/.config/cloudy/{app}/
+///     config.toml
+///     profiles/
+///         active              <- name of the active profile
+///         presentation.toml
+///         low-power.toml
+/// ```
+///
+/// A profile only holds the keys it changes, they overlay the base config (see [`overlay_profile`]).
+pub struct ConfigProfiles {
+    profiles_dir_path: PathBuf,
+}
+
+impl ConfigProfiles {
+    /// The profiles of the config file at `config_path`.
+    pub fn new(config_path: &Path) -> Self {
+        let profiles_dir_path = config_path.parent()
+            .unwrap_or(Path::new(""))
+            .join(PROFILES_DIR_NAME);
+
+        Self { profiles_dir_path }
+    }
+
+    pub fn profile_path(&self, name: &str) -> PathBuf {
+        self.profiles_dir_path.join(format!("{name}.toml"))
+    }
+
+    /// Names of every profile, sorted alphabetically.
+    pub fn list(&self) -> Result<Vec<String>, Error> {
+        let read_dir = match fs::read_dir(&self.profiles_dir_path) {
+            Ok(read_dir) => read_dir,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(Error::FailedToAccessProfiles { error: error.to_string() }),
+        };
+
+        let mut names: Vec<String> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+
+                match path.extension().is_some_and(|extension| extension == "toml") {
+                    true => Some(path.file_stem()?.to_string_lossy().to_string()),
+                    false => None,
+                }
+            })
+            .collect();
+
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// Creates an empty profile, which is the same as the base config until keys are changed in it.
+    pub fn create(&self, name: &str) -> Result<PathBuf, Error> {
+        self.write_new_profile(name, "")
+    }
+
+    /// Creates the profile `new_name` as a copy of the profile `name`.
+    pub fn duplicate(&self, name: &str, new_name: &str) -> Result<PathBuf, Error> {
+        let profile_toml_string = self.read(name)?;
+
+        self.write_new_profile(new_name, &profile_toml_string)
+    }
+
+    /// Deletes the profile, switching back to the base config if it was the active one.
+    pub fn delete(&self, name: &str) -> Result<(), Error> {
+        validate_profile_name(name)?;
+
+        debug!("Deleting config profile '{}'...", name);
+
+        fs::remove_file(self.profile_path(name))
+            .map_err(|error| match error.kind() {
+                ErrorKind::NotFound => Error::ProfileNotFound { name: name.to_string() },
+                _ => Error::ProfileFailure { name: name.to_string(), error: error.to_string() },
+            })?;
+
+        if self.active()?.as_deref() == Some(name) {
+            self.switch(None)?;
+        }
+
+        Ok(())
+    }
+
+    /// Makes `name` the active profile, `None` switches back to the base config.
+    pub fn switch(&self, name: Option<&str>) -> Result<(), Error> {
+        let active_profile_path = self.profiles_dir_path.join(ACTIVE_PROFILE_FILE_NAME);
+
+        let Some(name) = name else {
+            debug!("Switching to the base config...");
+
+            return match fs::remove_file(&active_profile_path) {
+                Err(error) if error.kind() != ErrorKind::NotFound => Err(Error::FailedToAccessProfiles { error: error.to_string() }),
+                _ => Ok(()),
+            };
+        };
+
+        validate_profile_name(name)?;
+
+        if !self.profile_path(name).exists() {
+            return Err(Error::ProfileNotFound { name: name.to_string() });
+        }
+
+        debug!("Switching to config profile '{}'...", name);
+
+        write_config_file(&active_profile_path, name, 0)
+            .map_err(|error| Error::ProfileFailure { name: name.to_string(), error: error.to_string() })
+    }
+
+    /// The name of the active profile, `None` if the base config is being used.
+    pub fn active(&self) -> Result<Option<String>, Error> {
+        match fs::read_to_string(self.profiles_dir_path.join(ACTIVE_PROFILE_FILE_NAME)) {
+            Ok(name) => Ok(Some(name.trim().to_string()).filter(|name| !name.is_empty())),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(Error::FailedToAccessProfiles { error: error.to_string() }),
+        }
+    }
+
+    pub fn read(&self, name: &str) -> Result<String, Error> {
+        validate_profile_name(name)?;
+
+        fs::read_to_string(self.profile_path(name))
+            .map_err(|error| match error.kind() {
+                ErrorKind::NotFound => Error::ProfileNotFound { name: name.to_string() },
+                _ => Error::ProfileFailure { name: name.to_string(), error: error.to_string() },
+            })
+    }
+
+    fn write_new_profile(&self, name: &str, profile_toml_string: &str) -> Result<PathBuf, Error> {
+        validate_profile_name(name)?;
+
+        let profile_path = self.profile_path(name);
+
+        if profile_path.exists() {
+            return Err(Error::ProfileAlreadyExists { name: name.to_string() });
+        }
+
+        debug!("Creating config profile '{}'...", name);
+
+        fs::create_dir_all(&self.profiles_dir_path)
+            .map_err(|error| Error::ProfileFailure { name: name.to_string(), error: error.to_string() })?;
+
+        write_config_file(&profile_path, profile_toml_string, 0)
+            .map_err(|error| Error::ProfileFailure { name: name.to_string(), error: error.to_string() })?;
+
+        Ok(profile_path)
+    }
+}
+
+/// Profile names end up as file names so they're kept to letters, numbers, `-` and `_`.
+pub fn validate_profile_name(name: &str) -> Result<(), Error> {
+    let is_valid = !name.is_empty()
+        && name.chars().all(|char| char.is_alphanumeric() || char == '-' || char == '_');
+
+    match is_valid {
+        true => Ok(()),
+        false => Err(Error::InvalidProfileName { name: name.to_string() }),
+    }
+}
+
+/// Overlays every key set in the profile on top of the base config, arrays of tables are replaced as a whole.
+pub fn overlay_profile(base_config_toml_string: &str, profile_toml_string: &str) -> Result<String, Error> {
+    let mut document: DocumentMut = base_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::FailedToReadConfig(error.to_string()))?;
+
+    let profile_document: DocumentMut = profile_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::FailedToReadConfig(error.to_string()))?;
+
+    for key_path in get_leaf_key_paths(profile_document.as_item()) {
+        if let Some(item) = get_item_by_path(profile_document.as_item(), &key_path) {
+            insert_item_by_path(document.as_item_mut(), &key_path, None, item.clone())
+                .map_err(Error::FailedToReadConfig)?;
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+/// Edits the profile to match the config in memory (`config_document`). Keys already in the profile are edited in
+/// place (keeping their comments) and keys whose value now differs from the base config are added to the profile.
+pub fn edit_profile_document(profile_document: &mut DocumentMut, base_document: &DocumentMut, config_document: &DocumentMut) {
+    for key_path in get_leaf_key_paths(config_document.as_item()) {
+        let Some(item) = get_item_by_path(config_document.as_item(), &key_path) else {
+            continue;
+        };
+
+        if let Some(item_to_edit) = get_item_by_path_mut(profile_document.as_item_mut(), &key_path) {
+            walk_and_edit_toml_item(item_to_edit, item, &key_path);
+            continue;
+        }
+
+        let base_value = get_item_by_path(base_document.as_item(), &key_path).and_then(item_to_value);
+
+        let is_different_from_base = match (base_value, item_to_value(item)) {
+            (Some(base_value), Some(value)) => is_toml_value_different(&base_value, &value),
+            _ => true,
+        };
+
+        if !is_different_from_base {
+            continue;
+        }
+
+        debug!("Adding config key '{}' to the profile...", key_path);
+
+        let mut item = item.clone();
+
+        if let Item::ArrayOfTables(array_of_tables) = &mut item {
+            array_of_tables.iter_mut().for_each(clear_table_positions);
+        }
+
+        if let Err(error) = insert_item_by_path(profile_document.as_item_mut(), &key_path, None, item) {
+            debug!("Failed to add config key '{}' to the profile! Error: {}", key_path, error);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use log::debug;
+use toml_edit::{DocumentMut, Item, Value};
+
+use crate::{config::edit::{edit_config_document, insert_missing_keys_under, is_toml_value_different, walk_and_edit_toml_item}, error::Error, toml_path::{get_item_by_path, get_item_by_path_mut, get_leaf_key_paths}};
+
+/// A config key whose value differs from the template's, see [`diff_against_template`].
+#[derive(Debug, Clone)]
+pub struct ConfigKeyDiff {
+    pub key_path: String,
+    /// `None` if the key doesn't exist in the template.
+    pub default_value: Option<Value>,
+    /// `None` if the key is missing from the config.
+    pub current_value: Option<Value>,
+}
+
+/// Resets the key at `key_path` (or every key of the table at `key_path`) to the template's value, pass `None`
+/// to reset the whole config. The user's config document is edited in place so their comments are kept and
+/// keys missing from it are inserted back (see [`insert_missing_keys`](crate::config::edit::insert_missing_keys)).
+///
+/// Keys that aren't in the template are left alone.
+pub fn reset_config_keys(
+    document_to_edit: &mut DocumentMut,
+    template_config_toml_string: &str,
+    key_path: Option<&str>
+) -> Result<(), Error> {
+    let template_document: DocumentMut = template_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::TemplateConfigParseFailure { error: error.to_string() })?;
+
+    match key_path {
+        Some(key_path) => {
+            let template_item = get_item_by_path(template_document.as_item(), key_path)
+                .ok_or_else(|| Error::UnknownConfigKey { key_path: key_path.to_string() })?;
+
+            debug!("Resetting config key '{}' to the template's value...", key_path);
+
+            if let Some(item_to_edit) = get_item_by_path_mut(document_to_edit.as_item_mut(), key_path) {
+                walk_and_edit_toml_item(item_to_edit, template_item, key_path);
+            }
+        },
+        None => {
+            debug!("Resetting the whole config to the template's values...");
+            edit_config_document(document_to_edit, &template_document);
+        },
+    }
+
+    insert_missing_keys_under(document_to_edit, &template_document, template_config_toml_string, key_path)
+}
+
+/// Lists every key of the config whose value differs from the template's default, in template order
+/// followed by the keys that aren't in the template. Arrays of tables are compared as a whole.
+pub fn diff_against_template(config_document: &DocumentMut, template_config_toml_string: &str) -> Result<Vec<ConfigKeyDiff>, Error> {
+    let template_document: DocumentMut = template_config_toml_string.parse()
+        .map_err(|error: toml_edit::TomlError| Error::TemplateConfigParseFailure { error: error.to_string() })?;
+
+    let diffs = diff_leaf_values(&template_document, config_document).into_iter()
+        .map(|(key_path, default_value, current_value)| ConfigKeyDiff { key_path, default_value, current_value })
+        .collect();
+
+    Ok(diffs)
+}
+
+/// Every leaf key path whose value differs between the two documents along with both values, in
+/// `document_a`'s order followed by the keys only in `document_b`. Arrays of tables are compared as a whole.
+pub(crate) fn diff_leaf_values(document_a: &DocumentMut, document_b: &DocumentMut) -> Vec<(String, Option<Value>, Option<Value>)> {
+    let mut key_paths = get_leaf_key_paths(document_a.as_item());
+
+    for key_path in get_leaf_key_paths(document_b.as_item()) {
+        if !key_paths.contains(&key_path) {
+            key_paths.push(key_path);
+        }
+    }
+
+    key_paths.into_iter()
+        .filter_map(|key_path| {
+            let value_a = get_item_by_path(document_a.as_item(), &key_path).and_then(item_to_value);
+            let value_b = get_item_by_path(document_b.as_item(), &key_path).and_then(item_to_value);
+
+            let is_different = match (&value_a, &value_b) {
+                (Some(value_a), Some(value_b)) => is_toml_value_different(value_a, value_b),
+                _ => true,
+            };
+
+            is_different.then_some((key_path, value_a, value_b))
+        })
+        .collect()
+}
+
+pub(crate) fn item_to_value(item: &Item) -> Option<Value> {
+    match item {
+        Item::Value(value) => Some(value.clone()),
+        Item::ArrayOfTables(array_of_tables) => Some(Value::Array(array_of_tables.clone().into_array())),
+        _ => None,
+    }
+}
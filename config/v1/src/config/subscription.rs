@@ -0,0 +1,131 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use log::debug;
+use toml_edit::DocumentMut;
+
+use crate::{config::{edit::is_key_path_under, history::{config_to_document, hash_config, ConfigKeyChange}, reset::diff_leaf_values, CConfig}, error::Error};
+
+/// Identifies a subscription so it can be removed with [`ConfigSubscriptions::unsubscribe`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+enum Subscriber {
+    Callback(Box<dyn FnMut(&ConfigKeyChange) + Send>),
+    Channel(Sender<ConfigKeyChange>),
+}
+
+struct Subscription {
+    id: SubscriptionId,
+    key_path: String,
+    subscriber: Subscriber,
+}
+
+/// Callbacks and channels subscribed to changes of config keys.
+///
+/// Call [`ConfigSubscriptions::check`] whenever the config may have changed (e.g: every frame), every key
+/// that changed since the last call is passed to the subscribers of that key or of a table it's in.
+#[derive(Default)]
+pub struct ConfigSubscriptions {
+    subscriptions: Vec<Subscription>,
+    next_id: u64,
+
+    snapshot: Option<(u64, DocumentMut)>,
+}
+
+impl ConfigSubscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` with every change to the key at `key_path` or to any key inside of it if it's
+    /// a table (e.g: `misc`). An empty key path subscribes to every key of the config.
+    pub fn subscribe(
+        &mut self,
+        key_path: impl AsRef<str>,
+        callback: impl FnMut(&ConfigKeyChange) + Send + 'static
+    ) -> SubscriptionId {
+        self.add(key_path.as_ref(), Subscriber::Callback(Box::new(callback)))
+    }
+
+    /// Same as [`ConfigSubscriptions::subscribe`] but the changes are sent to the returned channel. The
+    /// subscription is removed once the receiver is dropped.
+    pub fn subscribe_channel(&mut self, key_path: impl AsRef<str>) -> (SubscriptionId, Receiver<ConfigKeyChange>) {
+        let (sender, receiver) = channel();
+
+        (self.add(key_path.as_ref(), Subscriber::Channel(sender)), receiver)
+    }
+
+    /// Returns `false` if there was no such subscription.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let subscription_count = self.subscriptions.len();
+
+        self.subscriptions.retain(|subscription| subscription.id != id);
+
+        self.subscriptions.len() != subscription_count
+    }
+
+    /// Notifies the subscribers of every key that changed since the last call, returning the changes.
+    ///
+    /// The first call only takes a snapshot of the config to compare against.
+    pub fn check<T: CConfig>(&mut self, config: &T) -> Result<Vec<ConfigKeyChange>, Error> {
+        let config_hash = hash_config(config);
+
+        let previous_document = match &self.snapshot {
+            Some((snapshot_hash, _)) if *snapshot_hash == config_hash => return Ok(Vec::new()),
+            Some((_, snapshot_document)) => Some(snapshot_document),
+            None => None,
+        };
+
+        let document = config_to_document(config)?;
+
+        let changes: Vec<ConfigKeyChange> = match previous_document {
+            Some(previous_document) => diff_leaf_values(previous_document, &document).into_iter()
+                .map(|(key_path, before, after)| ConfigKeyChange { key_path, before, after })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.snapshot = Some((config_hash, document));
+
+        self.notify(&changes);
+
+        Ok(changes)
+    }
+
+    /// Passes the changes to their subscribers.
+    pub fn notify(&mut self, changes: &[ConfigKeyChange]) {
+        if changes.is_empty() || self.subscriptions.is_empty() {
+            return;
+        }
+
+        debug!("Notifying subscribers of change to config key(s) {:?}...", changes.iter().map(|change| &change.key_path).collect::<Vec<_>>());
+
+        self.subscriptions.retain_mut(|subscription| {
+            let changes = changes.iter()
+                .filter(|change| subscription.key_path.is_empty() || is_key_path_under(&change.key_path, &subscription.key_path));
+
+            for change in changes {
+                match &mut subscription.subscriber {
+                    Subscriber::Callback(callback) => callback(change),
+                    Subscriber::Channel(sender) => {
+                        if sender.send(change.clone()).is_err() {
+                            // nobody is listening anymore
+                            return false;
+                        }
+                    },
+                }
+            }
+
+            true
+        });
+    }
+
+    fn add(&mut self, key_path: &str, subscriber: Subscriber) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+
+        self.subscriptions.push(Subscription { id, key_path: key_path.to_string(), subscriber });
+
+        id
+    }
+}
@@ -0,0 +1,92 @@
+use std::{ffi::OsString, path::Path, sync::mpsc::{self, Receiver}, time::Duration};
+
+use log::{debug, warn};
+use notify::{Event, EventKind, PollWatcher, RecursiveMode, Watcher};
+
+use crate::error::Error;
+
+/// How often the polling fallback checks the config file for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches a config file on disk for changes made outside of the app (e.g: the user editing it by hand).
+///
+/// Uses the platform's native file watcher (inotify on Linux) and falls back to polling the
+/// file every [`POLL_INTERVAL`] if that isn't available or can't watch the config's folder.
+pub struct ConfigWatcher {
+    // the watcher stops watching once it's dropped
+    _watcher: Box<dyn Watcher + Send>,
+    receiver: Receiver<notify::Result<Event>>,
+    config_file_name: OsString,
+}
+
+impl ConfigWatcher {
+    pub fn new(config_path: &Path) -> Result<Self, Error> {
+        let (config_file_name, config_dir_path) = match (config_path.file_name(), config_path.parent()) {
+            (Some(file_name), Some(dir_path)) => (file_name.to_os_string(), dir_path),
+            _ => return Err(
+                Error::FailedToWatchConfig {
+                    error: format!("'{}' is not a path to a file!", config_path.display())
+                }
+            ),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+
+        debug!("Watching '{}' for config changes...", config_dir_path.display());
+
+        // We watch the directory instead of the file itself as editors usually save by writing
+        // to a temporary file and renaming it over the original, which would end a watch on the file.
+        let native_watcher = notify::recommended_watcher(sender.clone())
+            .and_then(|mut watcher| {
+                watcher.watch(config_dir_path, RecursiveMode::NonRecursive)?;
+                Ok(watcher)
+            });
+
+        let watcher: Box<dyn Watcher + Send> = match native_watcher {
+            Ok(watcher) => Box::new(watcher),
+            // e.g: the inotify watch limit was hit or the config is on a network filesystem
+            Err(error) => {
+                warn!("Failed to watch the config with the native file watcher, falling back to polling! Error: {}", error);
+
+                let mut poll_watcher = PollWatcher::new(
+                    sender, notify::Config::default().with_poll_interval(POLL_INTERVAL)
+                ).map_err(|error| Error::FailedToWatchConfig { error: error.to_string() })?;
+
+                poll_watcher.watch(config_dir_path, RecursiveMode::NonRecursive)
+                    .map_err(|error| Error::FailedToWatchConfig { error: error.to_string() })?;
+
+                Box::new(poll_watcher)
+            },
+        };
+
+        Ok(
+            Self {
+                _watcher: watcher,
+                receiver,
+                config_file_name,
+            }
+        )
+    }
+
+    /// Returns `true` if the config file was created or modified since the last
+    /// time this was called. Never blocks, so it's fine to call every frame.
+    pub fn poll_changed(&self) -> bool {
+        let mut changed = false;
+
+        for result in self.receiver.try_iter() {
+            match result {
+                Ok(event) => {
+                    let is_config_file = event.paths.iter()
+                        .any(|path| path.file_name() == Some(self.config_file_name.as_os_str()));
+
+                    if is_config_file && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Any) {
+                        changed = true;
+                    }
+                },
+                Err(error) => warn!("Error while watching the config file! Error: {}", error),
+            }
+        }
+
+        changed
+    }
+}
@@ -12,6 +12,28 @@ pub enum Error {
 
     UserConfigPathNotFound { error: String },
     TemplateConfigParseFailure { error: String },
+
+    ConfigVersionInvalid { version: String },
+    ConfigMigrationFailure { version: i64, error: String },
+    FailedToBackupConfig { error: String },
+    FailedToWatchConfig { error: String },
+    FailedToRestoreConfigBackup { index: usize, error: String },
+    InvalidConfigOverride { argument: String },
+    ConfigConstraintViolation { key_path: String, error: String },
+    UnknownConfigKey { key_path: String },
+
+    InvalidProfileName { name: String },
+    ProfileNotFound { name: String },
+    ProfileAlreadyExists { name: String },
+    ProfileFailure { name: String, error: String },
+    FailedToAccessProfiles { error: String },
+
+    InvalidConfigBundle { error: String },
+    FailedToExportConfigBundle { error: String },
+    FailedToImportConfigBundle { error: String },
+
+    ConfigFormatFailure { format: String, error: String },
+    FailedToWriteConfigDocs { error: String },
 }
 
 impl CError for Error {}
@@ -37,7 +59,61 @@ impl Display for Error {
             ),
             Error::TemplateConfigParseFailure { .. } => write!(
                 f, "Failed to parse template config! Report immediately, this should never be the case!"
-            )
+            ),
+            Error::ConfigVersionInvalid { version } => write!(
+                f, "The 'version' key in the config must be an integer! What we got: '{version}'."
+            ),
+            Error::ConfigMigrationFailure { version, .. } => write!(
+                f, "Failed to migrate the config to version '{version}'!"
+            ),
+            Error::FailedToBackupConfig { .. } => write!(
+                f, "Failed to backup config toml file!"
+            ),
+            Error::FailedToWatchConfig { .. } => write!(
+                f, "Failed to watch config toml file for changes!"
+            ),
+            Error::FailedToRestoreConfigBackup { index, .. } => write!(
+                f, "Failed to restore config backup number '{index}'!"
+            ),
+            Error::InvalidConfigOverride { argument } => write!(
+                f, "Invalid config override '{argument}', it should look like 'key.path=value'!"
+            ),
+            Error::ConfigConstraintViolation { key_path, error } => write!(
+                f, "Invalid value for the config key '{key_path}': {error}!"
+            ),
+            Error::UnknownConfigKey { key_path } => write!(
+                f, "The config key '{key_path}' does not exist in the template!"
+            ),
+            Error::InvalidProfileName { name } => write!(
+                f, "'{name}' is not a valid profile name, only letters, numbers, '-' and '_' are allowed!"
+            ),
+            Error::ProfileNotFound { name } => write!(
+                f, "The config profile '{name}' does not exist!"
+            ),
+            Error::ProfileAlreadyExists { name } => write!(
+                f, "A config profile named '{name}' already exists!"
+            ),
+            Error::ProfileFailure { name, .. } => write!(
+                f, "Failed to read or write the config profile '{name}'!"
+            ),
+            Error::FailedToAccessProfiles { .. } => write!(
+                f, "Failed to access the config profiles directory!"
+            ),
+            Error::InvalidConfigBundle { error } => write!(
+                f, "Invalid config bundle: {error}"
+            ),
+            Error::FailedToExportConfigBundle { .. } => write!(
+                f, "Failed to export config bundle!"
+            ),
+            Error::FailedToImportConfigBundle { .. } => write!(
+                f, "Failed to import config bundle!"
+            ),
+            Error::ConfigFormatFailure { format, error } => write!(
+                f, "Failed to convert the config to or from {format}: {error}"
+            ),
+            Error::FailedToWriteConfigDocs { .. } => write!(
+                f, "Failed to write the config reference docs!"
+            ),
         }
     }
 }
\ No newline at end of file
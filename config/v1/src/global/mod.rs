@@ -0,0 +1,154 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use cirrus_path::get_user_config_cloudy_folder_path;
+
+use crate::{config::{lenient::{parse_config_lenient, ConfigDiagnostic}, CConfig}, error::Error};
+
+/// The template of the global config shared across every cloudy-org app (`~/.config/cloudy/config.toml`).
+pub const GLOBAL_TEMPLATE_CONFIG_TOML: &str = include_str!("template.toml");
+
+/// The global config shared across every cloudy-org app, read it with [`get_global_config`].
+///
+/// String keys left empty in the config are `None`, meaning the user hasn't set them.
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize)]
+pub struct GlobalConfig {
+    pub version: i8,
+    /// Code name of the theme to use (e.g: "dark", "light", "auto" or an installed theme).
+    #[serde(with = "empty_string_as_none")]
+    pub theme: Option<String>,
+    /// Hex colour used as the accent instead of the desktop's accent colour.
+    #[serde(with = "empty_string_as_none")]
+    pub accent_colour: Option<String>,
+    /// Locale code apps are displayed in (e.g: "en-GB").
+    #[serde(with = "empty_string_as_none")]
+    pub locale: Option<String>,
+    pub reduced_motion: bool,
+    pub check_for_updates: bool,
+    /// Command of the editor config files are opened with.
+    #[serde(with = "empty_string_as_none")]
+    pub editor: Option<String>,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            theme: None,
+            accent_colour: None,
+            locale: None,
+            reduced_motion: false,
+            check_for_updates: true,
+            editor: None,
+        }
+    }
+}
+
+impl CConfig for GlobalConfig {}
+
+/// Values of the global config an app's config overrides, keys that aren't set inherit the global value.
+///
+/// Embed it in your app's config to let users override the global config for that app only:
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, Hash, Default)]
+/// pub struct Config {
+///     pub version: i8,
+///     #[serde(default)]
+///     pub global: GlobalConfigOverrides,
+/// }
+///
+/// let global_config = get_global_config()?.with_overrides(&config.global);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalConfigOverrides {
+    #[serde(with = "empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(with = "empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub accent_colour: Option<String>,
+    #[serde(with = "empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reduced_motion: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_for_updates: Option<bool>,
+    #[serde(with = "empty_string_as_none", skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+}
+
+impl GlobalConfig {
+    /// Returns the global config with the app's `overrides` applied on top of it.
+    pub fn with_overrides(&self, overrides: &GlobalConfigOverrides) -> Self {
+        let overrides = overrides.clone();
+
+        Self {
+            version: self.version,
+            theme: overrides.theme.or_else(|| self.theme.clone()),
+            accent_colour: overrides.accent_colour.or_else(|| self.accent_colour.clone()),
+            locale: overrides.locale.or_else(|| self.locale.clone()),
+            reduced_motion: overrides.reduced_motion.unwrap_or(self.reduced_motion),
+            check_for_updates: overrides.check_for_updates.unwrap_or(self.check_for_updates),
+            editor: overrides.editor.or_else(|| self.editor.clone()),
+        }
+    }
+}
+
+/// **Linux:** `~/.config/cloudy/config.toml`
+///
+/// **Windows:** `C:\Users\{user}\AppData\Local\Cloudy\config.toml`
+pub fn get_global_config_file_path() -> Result<PathBuf, Error> {
+    Ok(
+        get_user_config_cloudy_folder_path()
+            .map_err(|error| Error::UserConfigPathNotFound { error: error.to_string() })?
+            .join("config.toml")
+    )
+}
+
+/// Reads the user's global config. The template's defaults are returned if the user has no
+/// global config and any invalid keys fall back to the template (see [`parse_global_config`]).
+pub fn get_global_config() -> Result<GlobalConfig, Error> {
+    get_global_config_from_path(&get_global_config_file_path()?)
+}
+
+/// Same as [`get_global_config`] but reads the global config at `config_path`.
+pub fn get_global_config_from_path(config_path: &Path) -> Result<GlobalConfig, Error> {
+    if !config_path.exists() {
+        debug!("No global config found at '{}', using the defaults...", config_path.display());
+        return Ok(GlobalConfig::default());
+    }
+
+    debug!("Reading global config at '{}'...", config_path.display());
+
+    let config_toml_string = fs::read_to_string(config_path)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    let (global_config, diagnostics) = parse_global_config(&config_toml_string)?;
+
+    for diagnostic in diagnostics {
+        warn!("Global config: {}", diagnostic);
+    }
+
+    Ok(global_config)
+}
+
+/// Leniently deserializes the global config, one bad key in it shouldn't stop every app from starting.
+pub fn parse_global_config(config_toml_string: &str) -> Result<(GlobalConfig, Vec<ConfigDiagnostic>), Error> {
+    parse_config_lenient::<GlobalConfig>(config_toml_string, GLOBAL_TEMPLATE_CONFIG_TOML)
+}
+
+// an empty string is how the template leaves a key unset as toml has no null
+mod empty_string_as_none {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value.as_deref().unwrap_or_default())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+
+        Ok(value.filter(|value| !value.is_empty()))
+    }
+}
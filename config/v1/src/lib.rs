@@ -1,7 +1,15 @@
 pub mod error;
 pub mod config;
 pub mod template;
+pub mod global;
 
+mod toml_path;
+
+pub use config::key_path::ConfigKeys;
+pub use cirrus_config_derive::ConfigKeys;
+
+/// Prefer `#[derive(ConfigKeys)]` on the config struct, its key paths follow serde's renames.
+///
 /// # ⚠️ WARNING
 /// The root of the path and `self.` is consumed.
 /// For example `config_key_path!(self.config.field_a.field_b)` will result in `field_a.field_b`.
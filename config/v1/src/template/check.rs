@@ -0,0 +1,193 @@
+use std::{fmt::Display, fs, path::{Path, PathBuf}};
+
+use cirrus_error::error::CError;
+use toml_edit::DocumentMut;
+
+use crate::{config::CConfig, error::Error, template::Template, toml_path::{get_item_by_path, get_leaf_key_paths}};
+
+/// Something wrong with the template found by [`check_template`] or [`check_config_key_paths`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateProblem {
+    /// The template doesn't deserialize into the config struct, `get_and_create_config_file` would panic on it.
+    DeserializeFailure { error: String },
+    /// A field of the config struct has no key in the template.
+    MissingKey { key_path: String },
+    /// The template's key for a field of the config struct has no docstring explaining it.
+    MissingDocstring { key_path: String },
+    /// A `config_key_path!()` in the code points to a key that isn't in the template.
+    UnknownKeyPath { key_path: String, file_path: PathBuf, line_number: usize },
+}
+
+impl CError for TemplateProblem {}
+
+impl Display for TemplateProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateProblem::DeserializeFailure { error } => write!(
+                f, "The template does not deserialize into the config struct: {error}"
+            ),
+            TemplateProblem::MissingKey { key_path } => write!(
+                f, "The config struct has the key '{key_path}' but the template does not!"
+            ),
+            TemplateProblem::MissingDocstring { key_path } => write!(
+                f, "The template key '{key_path}' has no docstring!"
+            ),
+            TemplateProblem::UnknownKeyPath { key_path, file_path, line_number } => write!(
+                f, "'config_key_path!()' at {}:{line_number} points to '{key_path}' which is not in the template!",
+                file_path.display()
+            ),
+        }
+    }
+}
+
+/// Checks the template deserializes into `T` and that every field of `T` has a key with a docstring in the
+/// template. The fields are found by serializing `T::default()`, so fields that serialize to nothing (e.g: `None`)
+/// are skipped. Use [`assert_config_template!`](crate::assert_config_template) to run this as a test.
+pub fn check_template<T: CConfig>(template_config_toml_string: &str) -> Vec<TemplateProblem> {
+    if let Err(error) = toml::from_str::<T>(template_config_toml_string) {
+        return vec![TemplateProblem::DeserializeFailure { error: error.to_string() }];
+    }
+
+    let mut template = Template::new(template_config_toml_string);
+
+    if let Err(error) = template.parse_keys() {
+        return vec![TemplateProblem::DeserializeFailure { error: error.to_string() }];
+    }
+
+    // the template already parsed fine above and, like when saving, the config serializing is taken for granted
+    let template_document = parse_document(template_config_toml_string)
+        .expect("Failed to parse template toml file!");
+
+    let config_document = toml::to_string(&T::default()).ok()
+        .and_then(|config_toml_string| parse_document(&config_toml_string).ok())
+        .expect("Failed to serialize the default config!");
+
+    let mut problems = Vec::new();
+
+    for key_path in get_leaf_key_paths(config_document.as_item()) {
+        if get_item_by_path(template_document.as_item(), &key_path).is_none() {
+            problems.push(TemplateProblem::MissingKey { key_path });
+            continue;
+        }
+
+        // the version is there for migrations, not for users
+        if key_path == "version" {
+            continue;
+        }
+
+        // arrays of tables have their docstrings on the keys of each table
+        let has_docstring = match template.keys.get(&key_path) {
+            Some(template_key) => template_key.docstring.description.long.is_some(),
+            None => true,
+        };
+
+        if !has_docstring {
+            problems.push(TemplateProblem::MissingDocstring { key_path });
+        }
+    }
+
+    problems
+}
+
+/// Checks every `config_key_path!()` in the `.rs` files under `source_dir_path` points to a key (or table)
+/// in the template. Meant for a test or `build.rs`, e.g: `check_config_key_paths(template, Path::new("src"))`.
+pub fn check_config_key_paths(template_config_toml_string: &str, source_dir_path: &Path) -> Result<Vec<TemplateProblem>, Error> {
+    let template_document = parse_document(template_config_toml_string)?;
+
+    let mut problems = Vec::new();
+
+    for file_path in find_rust_files(source_dir_path)? {
+        let source = fs::read_to_string(&file_path)
+            .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+        for (line_number, key_path) in find_config_key_paths(&source) {
+            if get_item_by_path(template_document.as_item(), &key_path).is_none() {
+                problems.push(TemplateProblem::UnknownKeyPath { key_path, file_path: file_path.clone(), line_number });
+            }
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Fails the test listing every problem [`check_template`] finds with the template, for example:
+///
+/// ```ignore
+/// #[test]
+/// fn test_config_template() {
+///     cirrus_config::assert_config_template!(Config, include_str!("../assets/config.template.toml"));
+/// }
+/// ```
+#[macro_export] macro_rules! assert_config_template {
+    ($config:ty, $template:expr) => {{
+        let problems = $crate::template::check_template::<$config>($template);
+
+        if !problems.is_empty() {
+            let problems: Vec<String> = problems.iter()
+                .map(|problem| format!("  - {}", problem))
+                .collect();
+
+            panic!("The config template does not match '{}':\n{}", stringify!($config), problems.join("\n"));
+        }
+    }};
+}
+
+fn parse_document(toml_string: &str) -> Result<DocumentMut, Error> {
+    toml_string.parse::<DocumentMut>()
+        .map_err(|error| Error::TemplateConfigParseFailure { error: error.to_string() })
+}
+
+fn find_rust_files(dir_path: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut file_paths = Vec::new();
+
+    let read_dir = fs::read_dir(dir_path)
+        .map_err(|error| Error::FailedToReadConfig(error.to_string()))?;
+
+    for entry in read_dir.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+
+        if path.is_dir() {
+            file_paths.extend(find_rust_files(&path)?);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            file_paths.push(path);
+        }
+    }
+
+    file_paths.sort();
+
+    Ok(file_paths)
+}
+
+/// Finds every `config_key_path!(...)` outside of comments and returns the key path it results in, the
+/// same way the macro does it: `self.` is dropped along with the root (e.g: `self.config.misc.theme` -> `misc.theme`).
+fn find_config_key_paths(source: &str) -> Vec<(usize, String)> {
+    const MACRO_CALL: &str = "config_key_path!(";
+
+    let mut key_paths = Vec::new();
+
+    for (start, _) in source.match_indices(MACRO_CALL) {
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+
+        if source[line_start..start].trim_start().starts_with("//") {
+            continue;
+        }
+
+        let arguments = &source[start + MACRO_CALL.len()..];
+
+        let Some(end) = arguments.find(')') else {
+            continue;
+        };
+
+        // the call may be split across lines
+        let argument: String = arguments[..end].split_whitespace().collect();
+        let argument = argument.replace("self.", "");
+
+        if let Some((_, key_path)) = argument.split_once('.') {
+            let line_number = source[..start].matches('\n').count() + 1;
+
+            key_paths.push((line_number, key_path.to_string()));
+        }
+    }
+
+    key_paths
+}
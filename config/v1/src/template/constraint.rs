@@ -0,0 +1,151 @@
+use std::ops::RangeInclusive;
+
+use log::debug;
+use toml_edit::Value;
+
+/// Inclusive range a number has to be within (`# @range 1..=100`). Either end can be left out (`# @range 0..`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyRange {
+    Int(RangeInclusive<i64>),
+    Float(RangeInclusive<f64>),
+}
+
+/// Constraints on a key's value declared in the template with annotations, for example:
+///
+/// ```toml
+/// # @range 1..=100
+/// max_cats = 15
+///
+/// # @choices ["dark", "light"]
+/// theme = "dark"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyConstraints {
+    pub range: Option<KeyRange>,
+    pub choices: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintViolation {
+    pub key_path: String,
+    pub error: String,
+}
+
+impl KeyConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.range.is_none() && self.choices.is_none()
+    }
+
+    /// Checks the value against every constraint, returning why it doesn't satisfy them.
+    pub fn check(&self, value: &Value) -> Result<(), String> {
+        if let Some(range) = &self.range {
+            let is_in_range = match (range, value) {
+                (KeyRange::Int(range), Value::Integer(integer)) => range.contains(integer.value()),
+                (KeyRange::Int(range), Value::Float(float)) => {
+                    (*range.start() as f64..=*range.end() as f64).contains(float.value())
+                },
+                (KeyRange::Float(range), Value::Integer(integer)) => range.contains(&(*integer.value() as f64)),
+                (KeyRange::Float(range), Value::Float(float)) => range.contains(float.value()),
+                // wrong types are caught when deserializing
+                _ => true,
+            };
+
+            if !is_in_range {
+                return Err(format!("{} is not within the range {}", value_to_string(value), range_to_string(range)));
+            }
+        }
+
+        if let Some(choices) = &self.choices && !choices.iter().any(|choice| is_same_value(choice, value)) {
+            let choices: Vec<String> = choices.iter().map(value_to_string).collect();
+
+            return Err(format!("{} is not one of {}", value_to_string(value), choices.join(", ")));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses `1..=100`, `1..100` (integers only), `0.5..=2.0`, `0..` and `..=10`.
+pub(crate) fn parse_range(argument: &str) -> Option<KeyRange> {
+    let (start, end, is_end_inclusive) = match argument.split_once("..=") {
+        Some((start, end)) => (start.trim(), end.trim(), true),
+        None => {
+            let (start, end) = argument.split_once("..")?;
+            (start.trim(), end.trim(), false)
+        },
+    };
+
+    let is_float = start.contains('.') || end.contains('.');
+
+    let range = match is_float {
+        true => {
+            if !is_end_inclusive && !end.is_empty() {
+                debug!("Exclusive float ranges are not supported ('{}'), use '..=' instead.", argument);
+                return None;
+            }
+
+            KeyRange::Float(
+                parse_bound(start, f64::NEG_INFINITY)?..=parse_bound(end, f64::INFINITY)?
+            )
+        },
+        false => {
+            let mut end_bound = parse_bound(end, i64::MAX)?;
+
+            if !is_end_inclusive && !end.is_empty() {
+                end_bound = end_bound.checked_sub(1)?;
+            }
+
+            KeyRange::Int(parse_bound(start, i64::MIN)?..=end_bound)
+        },
+    };
+
+    Some(range)
+}
+
+/// Parses a toml array (e.g: `["dark", "light"]`).
+pub(crate) fn parse_choices(argument: &str) -> Option<Vec<Value>> {
+    match argument.parse::<Value>() {
+        Ok(Value::Array(array)) => Some(
+            array.iter()
+                .map(|value| {
+                    let mut value = value.clone();
+                    value.decor_mut().clear();
+
+                    value
+                })
+                .collect()
+        ),
+        _ => None,
+    }
+}
+
+fn parse_bound<N: std::str::FromStr>(bound: &str, default: N) -> Option<N> {
+    match bound.is_empty() {
+        true => Some(default),
+        false => bound.replace('_', "").parse().ok(),
+    }
+}
+
+fn range_to_string(range: &KeyRange) -> String {
+    match range {
+        KeyRange::Int(range) => format!("{}..={}", range.start(), range.end()),
+        KeyRange::Float(range) => format!("{}..={}", range.start(), range.end()),
+    }
+}
+
+fn is_same_value(value_a: &Value, value_b: &Value) -> bool {
+    match (value_a, value_b) {
+        (Value::String(formatted_a), Value::String(formatted_b)) => formatted_a.value() == formatted_b.value(),
+        (Value::Integer(formatted_a), Value::Integer(formatted_b)) => formatted_a.value() == formatted_b.value(),
+        (Value::Float(formatted_a), Value::Float(formatted_b)) => formatted_a.value() == formatted_b.value(),
+        (Value::Boolean(formatted_a), Value::Boolean(formatted_b)) => formatted_a.value() == formatted_b.value(),
+        _ => value_to_string(value_a) == value_to_string(value_b),
+    }
+}
+
+pub(crate) fn value_to_string(value: &Value) -> String {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+
+    value.to_string()
+}
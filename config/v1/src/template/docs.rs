@@ -0,0 +1,379 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use log::debug;
+use serde_json::{json, Map, Value as JsonValue};
+use toml_edit::Value;
+
+use crate::{error::Error, template::{constraint::value_to_string, KeyRange, Template, TemplateKey}};
+
+/// Names of the files written by [`write_config_docs`].
+pub const MARKDOWN_DOCS_FILE_NAME: &str = "config.md";
+pub const HTML_DOCS_FILE_NAME: &str = "config.html";
+pub const JSON_SCHEMA_FILE_NAME: &str = "config.schema.json";
+
+impl Template<'_> {
+    /// Renders every key of the template (in the order they're written in) as a Markdown reference page.
+    ///
+    /// Call [`Template::parse_keys`] first.
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut markdown = format!("# {title}\n");
+        let mut current_table_path = None;
+
+        for (key_path, template_key) in self.iter_keys() {
+            let table_path = table_path_of(key_path);
+
+            if table_path.is_some() && table_path != current_table_path {
+                markdown.push_str(&format!("\n## `[{}]`\n", table_path.unwrap_or_default()));
+            }
+
+            current_table_path = table_path;
+
+            let docstring = &template_key.docstring;
+
+            markdown.push_str(&format!("\n### `{key_path}`\n\n"));
+
+            if let Some(deprecation) = &docstring.deprecated {
+                markdown.push_str("> **Deprecated!**");
+
+                if let Some(note) = &deprecation.note {
+                    markdown.push_str(&format!(" {note}"));
+                }
+
+                markdown.push_str("\n\n");
+            }
+
+            if let Some(long) = &docstring.description.long {
+                for paragraph in paragraphs(long) {
+                    markdown.push_str(&format!("{paragraph}\n\n"));
+                }
+            }
+
+            markdown.push_str("| | |\n|---|---|\n");
+
+            for (name, value) in key_details(template_key) {
+                markdown.push_str(&format!("| {name} | {} |\n", value.replace('|', "\\|")));
+            }
+        }
+
+        markdown
+    }
+
+    /// Same as [`Template::to_markdown`] but as a standalone HTML page.
+    pub fn to_html(&self, title: &str) -> String {
+        let title = escape_html(title);
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+
+        let mut current_table_path = None;
+
+        for (key_path, template_key) in self.iter_keys() {
+            let table_path = table_path_of(key_path);
+
+            if table_path.is_some() && table_path != current_table_path {
+                html.push_str(&format!("<h2><code>[{}]</code></h2>\n", escape_html(table_path.unwrap_or_default())));
+            }
+
+            current_table_path = table_path;
+
+            let docstring = &template_key.docstring;
+            let key_path = escape_html(key_path);
+
+            html.push_str(&format!("<section id=\"{key_path}\">\n<h3><code>{key_path}</code></h3>\n"));
+
+            if let Some(deprecation) = &docstring.deprecated {
+                let note = deprecation.note.as_deref().map(escape_html).unwrap_or_default();
+
+                html.push_str(&format!("<p><strong>Deprecated!</strong> {note}</p>\n"));
+            }
+
+            if let Some(long) = &docstring.description.long {
+                for paragraph in paragraphs(long) {
+                    html.push_str(&format!("<p>{}</p>\n", escape_html(paragraph)));
+                }
+            }
+
+            html.push_str("<dl>\n");
+
+            for (name, value) in key_details(template_key) {
+                // the details are written with markdown code spans
+                let value = escape_html(&value).split('`')
+                    .enumerate()
+                    .map(|(index, part)| match index % 2 {
+                        1 => format!("<code>{part}</code>"),
+                        _ => part.to_string(),
+                    })
+                    .collect::<String>();
+
+                html.push_str(&format!("<dt>{name}</dt><dd>{value}</dd>\n"));
+            }
+
+            html.push_str("</dl>\n</section>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+
+        html
+    }
+
+    /// Renders the template as a [JSON Schema](https://json-schema.org) so editors (e.g: taplo) can
+    /// complete and validate the config. Descriptions, defaults, examples and constraints are included.
+    pub fn to_json_schema(&self, title: &str) -> String {
+        let mut schema = json!({
+            "$schema": "https://json-schema.org/draft-07/schema#",
+            "title": title,
+            "type": "object",
+            "properties": {},
+        });
+
+        for (key_path, template_key) in self.iter_keys() {
+            insert_key_schema(&mut schema, key_path, key_schema(template_key));
+        }
+
+        serde_json::to_string_pretty(&schema)
+            .expect("Failed to serialize json schema!") // a json value always serializes
+    }
+
+    fn iter_keys(&self) -> impl Iterator<Item = (&String, &TemplateKey)> {
+        self.ordered_paths.iter()
+            .filter_map(|key_path| Some((key_path, self.keys.get(key_path)?)))
+    }
+}
+
+/// Writes the Markdown, HTML and JSON Schema reference of the template into `output_dir_path`,
+/// made to be called from a `build.rs` or a release script. Returns the paths of the files written.
+///
+/// ```ignore
+/// write_config_docs(include_str!("config.template.toml"), Path::new("docs"), "Cat Spawner config")?;
+/// ```
+pub fn write_config_docs(template_config_toml_string: &str, output_dir_path: &Path, title: &str) -> Result<Vec<PathBuf>, Error> {
+    let mut template = Template::new(template_config_toml_string);
+    template.parse_keys()?;
+
+    fs::create_dir_all(output_dir_path)
+        .map_err(|error| Error::FailedToWriteConfigDocs { error: error.to_string() })?;
+
+    let docs = [
+        (MARKDOWN_DOCS_FILE_NAME, template.to_markdown(title)),
+        (HTML_DOCS_FILE_NAME, template.to_html(title)),
+        (JSON_SCHEMA_FILE_NAME, template.to_json_schema(title)),
+    ];
+
+    docs.into_iter()
+        .map(|(file_name, contents)| {
+            let path = output_dir_path.join(file_name);
+
+            debug!("Writing config docs to '{}'...", path.display());
+
+            fs::write(&path, contents)
+                .map_err(|error| Error::FailedToWriteConfigDocs { error: error.to_string() })?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Type, default, allowed values, examples and version of the key, values wrapped in markdown code spans.
+fn key_details(template_key: &TemplateKey) -> Vec<(&'static str, String)> {
+    let value = &template_key.defined_toml_value;
+    let docstring = &template_key.docstring;
+    let constraints = &template_key.constraints;
+
+    let mut details = vec![
+        ("Type", format!("`{}`", value.type_name())),
+        ("Default", format!("`{}`", value_to_string(value))),
+    ];
+
+    if let Some(range) = &constraints.range {
+        details.push(("Range", format!("`{}`", range_to_doc_string(range))));
+    }
+
+    if let Some(choices) = &constraints.choices {
+        let choices: Vec<String> = choices.iter()
+            .map(|choice| format!("`{}`", value_to_string(choice)))
+            .collect();
+
+        details.push(("Allowed values", choices.join(", ")));
+    }
+
+    if !docstring.examples.is_empty() {
+        let examples: Vec<String> = docstring.examples.iter()
+            .map(|example| format!("`{example}`"))
+            .collect();
+
+        details.push(("Examples", examples.join(", ")));
+    }
+
+    if let Some(since) = &docstring.since {
+        details.push(("Since", since.clone()));
+    }
+
+    details
+}
+
+fn key_schema(template_key: &TemplateKey) -> JsonValue {
+    let value = &template_key.defined_toml_value;
+    let docstring = &template_key.docstring;
+    let constraints = &template_key.constraints;
+
+    let mut schema = Map::new();
+
+    schema.insert("type".into(), json!(json_schema_type(value)));
+
+    if let Some(short) = &docstring.description.short {
+        schema.insert("title".into(), json!(short));
+    }
+
+    if let Some(long) = &docstring.description.long {
+        schema.insert("description".into(), json!(long));
+    }
+
+    schema.insert("default".into(), toml_value_to_json(value));
+
+    if let Value::Array(array) = value && let Some(first_value) = array.iter().next() {
+        schema.insert("items".into(), json!({ "type": json_schema_type(first_value) }));
+    }
+
+    if !docstring.examples.is_empty() {
+        let examples: Vec<JsonValue> = docstring.examples.iter()
+            .map(|example| match example.parse::<Value>() {
+                Ok(value) => toml_value_to_json(&value),
+                Err(_) => json!(example),
+            })
+            .collect();
+
+        schema.insert("examples".into(), json!(examples));
+    }
+
+    if let Some(choices) = &constraints.choices {
+        schema.insert("enum".into(), choices.iter().map(toml_value_to_json).collect());
+    }
+
+    match &constraints.range {
+        Some(KeyRange::Int(range)) => {
+            if *range.start() != i64::MIN {
+                schema.insert("minimum".into(), json!(range.start()));
+            }
+
+            if *range.end() != i64::MAX {
+                schema.insert("maximum".into(), json!(range.end()));
+            }
+        },
+        Some(KeyRange::Float(range)) => {
+            if range.start().is_finite() {
+                schema.insert("minimum".into(), json!(range.start()));
+            }
+
+            if range.end().is_finite() {
+                schema.insert("maximum".into(), json!(range.end()));
+            }
+        },
+        None => {},
+    }
+
+    if docstring.deprecated.is_some() {
+        schema.insert("deprecated".into(), json!(true));
+    }
+
+    JsonValue::Object(schema)
+}
+
+/// Inserts the key's schema into the object schema, creating the schemas of the tables
+/// leading up to it. Arrays of tables (e.g: `plugins[0].name`) get one schema for their items.
+fn insert_key_schema(schema: &mut JsonValue, key_path: &str, key_schema: JsonValue) {
+    let parts: Vec<&str> = key_path.split('.').collect();
+    let Some((key, table_parts)) = parts.split_last() else {
+        return;
+    };
+
+    let mut object_schema = schema;
+
+    for part in table_parts {
+        let (name, is_array_of_tables) = match part.split_once('[') {
+            Some((name, _)) => (name, true),
+            None => (*part, false),
+        };
+
+        let properties = &mut object_schema["properties"];
+
+        if properties.get(name).is_none() {
+            properties[name] = match is_array_of_tables {
+                true => json!({ "type": "array", "items": { "type": "object", "properties": {} } }),
+                false => json!({ "type": "object", "properties": {} }),
+            };
+        }
+
+        object_schema = match is_array_of_tables {
+            true => &mut properties[name]["items"],
+            false => &mut properties[name],
+        };
+    }
+
+    // only the first table of an array of tables is documented
+    if object_schema["properties"].get(*key).is_none() {
+        object_schema["properties"][*key] = key_schema;
+    }
+}
+
+fn json_schema_type(value: &Value) -> &'static str {
+    match value {
+        Value::String(_) | Value::Datetime(_) => "string",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "number",
+        Value::Boolean(_) => "boolean",
+        Value::Array(_) => "array",
+        Value::InlineTable(_) => "object",
+    }
+}
+
+fn toml_value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::String(string) => json!(string.value()),
+        Value::Integer(integer) => json!(integer.value()),
+        Value::Float(float) => json!(float.value()),
+        Value::Boolean(boolean) => json!(boolean.value()),
+        Value::Datetime(datetime) => json!(datetime.value().to_string()),
+        Value::Array(array) => array.iter().map(toml_value_to_json).collect(),
+        Value::InlineTable(inline_table) => inline_table.iter()
+            .map(|(key, value)| (key.to_string(), toml_value_to_json(value)))
+            .collect::<Map<_, _>>()
+            .into(),
+    }
+}
+
+/// Like a rust range, leaving out the ends that are unbounded (e.g: `0..`).
+fn range_to_doc_string(range: &KeyRange) -> String {
+    match range {
+        KeyRange::Int(range) => match (*range.start(), *range.end()) {
+            (i64::MIN, i64::MAX) => "..".to_string(),
+            (i64::MIN, end) => format!("..={end}"),
+            (start, i64::MAX) => format!("{start}.."),
+            (start, end) => format!("{start}..={end}"),
+        },
+        KeyRange::Float(range) => match (range.start().is_finite(), range.end().is_finite()) {
+            (false, false) => "..".to_string(),
+            (false, true) => format!("..={}", range.end()),
+            (true, false) => format!("{}..", range.start()),
+            (true, true) => format!("{}..={}", range.start(), range.end()),
+        },
+    }
+}
+
+/// Path of the table the key is in, `None` for keys at the root.
+fn table_path_of(key_path: &str) -> Option<&str> {
+    key_path.rsplit_once('.').map(|(table_path, _)| table_path)
+}
+
+fn paragraphs(description: &str) -> impl Iterator<Item = &str> {
+    description.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,15 +1,46 @@
-#[derive(Debug)]
+use log::debug;
+
+use crate::template::constraint::{parse_choices, parse_range, KeyConstraints};
+
+/// The comment above a key in the template, for example:
+///
+/// ```toml
+/// # @brief Maximum amount of cats.
+/// # Adjust this value to limit the maximum amount of cats that are allowed to spawn in.
+/// #
+/// # @example 30
+/// # @since 0.2.0
+/// max_cats = 15
+/// ```
+#[derive(Debug, Clone, Default)]
 pub struct KeyDocstring {
-    pub description: KeyDocstringDescription
+    pub description: KeyDocstringDescription,
+    /// Example values for the key (`# @example <value>`), can be given more than once.
+    pub examples: Vec<String>,
+    /// Set if the key is deprecated (`# @deprecated [note]`).
+    pub deprecated: Option<KeyDeprecation>,
+    /// The app version the key was introduced in (`# @since <version>`).
+    pub since: Option<String>,
+    /// The values the key accepts (`# @choices ["dark", "light"]`).
+    pub allowed_values: Option<Vec<String>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct KeyDocstringDescription {
+    /// One line summary, either given with `# @brief <text>` or the first paragraph of the description.
     pub short: Option<String>,
+    /// The whole description, without any `@` annotations.
     pub long: Option<String>,
 }
 
-pub fn parse_key_docstring(toml_string: &str, key_line_number: usize) -> KeyDocstring {
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeyDeprecation {
+    /// What to do instead, e.g: "Use 'misc.enable_custom_folder' instead."
+    pub note: Option<String>,
+}
+
+/// Parses the docstring above the key along with the constraints declared in it (`@range` and `@choices`).
+pub fn parse_key_docstring(toml_string: &str, key_line_number: usize) -> (KeyDocstring, KeyConstraints) {
     let lines: Vec<&str> = toml_string.lines().collect();
 
     let mut docstring_lines = Vec::new();
@@ -37,30 +68,74 @@ pub fn parse_key_docstring(toml_string: &str, key_line_number: usize) -> KeyDocs
 
     docstring_lines.reverse();
 
-    KeyDocstring {
-        description: match docstring_lines.is_empty() {
-            true => KeyDocstringDescription {
-                short: None,
-                long: None,
+    let mut docstring = KeyDocstring::default();
+    let mut constraints = KeyConstraints::default();
+    let mut brief = None;
+
+    // annotations aren't part of the description
+    docstring_lines.retain(|line| {
+        let Some(annotation) = line.strip_prefix('@') else {
+            return true;
+        };
+
+        let (tag, argument) = match annotation.split_once(char::is_whitespace) {
+            Some((tag, argument)) => (tag, Some(argument.trim()).filter(|argument| !argument.is_empty())),
+            None => (annotation, None),
+        };
+
+        match (tag, argument) {
+            ("brief", Some(argument)) => brief = Some(argument.to_string()),
+            ("example", Some(argument)) => docstring.examples.push(argument.to_string()),
+            ("deprecated", note) => docstring.deprecated = Some(
+                KeyDeprecation { note: note.map(str::to_string) }
+            ),
+            ("since", Some(argument)) => docstring.since = Some(argument.to_string()),
+            ("choices", Some(argument)) => match parse_choices(argument) {
+                Some(choices) => {
+                    docstring.allowed_values = Some(
+                        choices.iter()
+                            .map(|value| match value.as_str() {
+                                Some(string) => string.to_string(),
+                                None => value.to_string(),
+                            })
+                            .collect()
+                    );
+
+                    constraints.choices = Some(choices);
+                },
+                None => debug!("Ignoring '@choices' annotation as '{}' is not a toml array.", argument),
             },
-            false => {
-                let constructed_docstring: String = docstring_lines.iter()
-                    .map(|line| {
-                        match *line {
-                            // don't append space if new line
-                            "\n\n" => line.to_string(),
-                            other_line => format!("{} ", other_line)
-                        }
-                    })
-                    .collect::<String>()
-                    .trim_end()
-                    .to_owned();
-
-                KeyDocstringDescription {
-                    short: None, // TODO: implement short (brief) description.
-                    long: Some(constructed_docstring),
-                }
+            ("range", Some(argument)) => match parse_range(argument) {
+                Some(range) => constraints.range = Some(range),
+                None => debug!("Ignoring '@range' annotation as '{}' is not a valid range.", argument),
             },
-        },
-    }
-}
\ No newline at end of file
+            _ => debug!("Ignoring unknown or incomplete docstring annotation '@{}'.", annotation),
+        }
+
+        false
+    });
+
+    let constructed_docstring: String = docstring_lines.iter()
+        .map(|line| {
+            match *line {
+                // don't append space if new line
+                "\n\n" => line.to_string(),
+                other_line => format!("{} ", other_line)
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_owned();
+
+    let long = Some(constructed_docstring).filter(|docstring| !docstring.is_empty());
+
+    let short = brief.or_else(
+        || long.as_ref()
+            .and_then(|long| long.split("\n\n").next())
+            .map(|first_paragraph| first_paragraph.trim().to_string())
+    );
+
+    docstring.description = KeyDocstringDescription { short, long };
+
+    (docstring, constraints)
+}
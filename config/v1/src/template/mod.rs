@@ -1,16 +1,26 @@
 use std::collections::BTreeMap;
 
-use toml_edit::{Document, Item, Table, TomlError, Value};
+use toml_edit::{Document, DocumentMut, Item, Table, TomlError, Value};
 
-use crate::{error::Error, template::docstring::{KeyDocstring, parse_key_docstring}};
+use crate::{error::Error, template::docstring::parse_key_docstring, toml_path::get_item_by_path};
 
 mod docstring; // not public as the internals of this crate may change
+mod constraint;
+mod docs;
+mod check;
+
+pub use docstring::{KeyDeprecation, KeyDocstring, KeyDocstringDescription};
+pub use constraint::{ConstraintViolation, KeyConstraints, KeyRange};
+pub use check::{check_config_key_paths, check_template, TemplateProblem};
+pub use docs::{write_config_docs, HTML_DOCS_FILE_NAME, JSON_SCHEMA_FILE_NAME, MARKDOWN_DOCS_FILE_NAME};
 
 #[derive(Debug)]
 pub struct TemplateKey {
     pub key: String,
     pub docstring: KeyDocstring,
     pub defined_toml_value: Value,
+    /// Declared with `@range` and `@choices` annotations in the key's docstring.
+    pub constraints: KeyConstraints,
 }
 
 pub type TemplateKeys = BTreeMap<String, TemplateKey>;
@@ -60,6 +70,25 @@ impl<'a> Template<'a> {
         Ok(())
     }
 
+    /// Checks every value in the config against the constraints declared in the template
+    /// (see [`KeyConstraints`]). Keys that are missing or not a plain value are skipped.
+    pub fn check_constraints(&self, config_document: &DocumentMut) -> Vec<ConstraintViolation> {
+        self.ordered_paths.iter()
+            .filter_map(|key_path| {
+                let constraints = &self.keys.get(key_path)?.constraints;
+
+                if constraints.is_empty() {
+                    return None;
+                }
+
+                let value = get_item_by_path(config_document.as_item(), key_path)?.as_value()?;
+
+                constraints.check(value).err()
+                    .map(|error| ConstraintViolation { key_path: key_path.clone(), error })
+            })
+            .collect()
+    }
+
     fn walk_and_parse_toml_table(
         &self,
         toml_string: &'a str,
@@ -79,8 +108,8 @@ impl<'a> Template<'a> {
             match item {
                 Item::None => return,
                 Item::Value(value) => {
-                    let span_start = item.span().map(|s| s.start).expect(
-                        &format!(
+                    let span_start = item.span().map(|s| s.start).unwrap_or_else(
+                        || panic!(
                             "Honestly if this blows up, ooooh noooo my badddd :) - Failed to find item for '{}' \
                                 in toml document that's needed to get the first column the toml key is mentioned!",
                             path
@@ -91,7 +120,7 @@ impl<'a> Template<'a> {
                         .filter(|&char| char == '\n')
                         .count() + 1;
 
-                    let docstring = parse_key_docstring(toml_string, line_number);
+                    let (docstring, constraints) = parse_key_docstring(toml_string, line_number);
 
                     let key_path = path.to_string();
 
@@ -102,7 +131,8 @@ impl<'a> Template<'a> {
                         TemplateKey {
                             docstring,
                             key: key.to_string(),
-                            defined_toml_value: value.to_owned()
+                            defined_toml_value: value.to_owned(),
+                            constraints,
                         }
                     );
                 },
@@ -114,12 +144,11 @@ impl<'a> Template<'a> {
                     template_keys
                 ),
                 Item::ArrayOfTables(array_of_tables) => {
-                    // TODO: this needs testing!
-
-                    for child_table in array_of_tables {
+                    // each table gets its own index so their keys don't collide (e.g: 'plugins[1].name')
+                    for (index, child_table) in array_of_tables.iter().enumerate() {
                         self.walk_and_parse_toml_table(
                             toml_string,
-                            Some(path),
+                            Some(&format!("{}[{}]", path, index)),
                             child_table,
                             ordered_key_paths,
                             template_keys
@@ -0,0 +1,181 @@
+use toml_edit::{Entry, Item, Key, Table, TableLike, Value};
+
+/// Splits `misc.key_binds.spawn` into `(Some("misc.key_binds"), "spawn")`.
+pub(crate) fn split_key_path(key_path: &str) -> (Option<&str>, &str) {
+    match key_path.rsplit_once('.') {
+        Some((parent_path, key)) => (Some(parent_path), key),
+        None => (None, key_path),
+    }
+}
+
+/// Splits `plugins[2]` into `("plugins", Some(2))`.
+pub(crate) fn split_key_index(part: &str) -> (&str, Option<usize>) {
+    match part.strip_suffix(']').and_then(|part| part.split_once('[')) {
+        Some((key, index)) => match index.parse() {
+            Ok(index) => (key, Some(index)),
+            Err(_) => (part, None),
+        },
+        None => (part, None),
+    }
+}
+
+/// Key paths can index into arrays of tables (and arrays of inline tables) like so: `plugins[2].name`.
+///
+/// Only the tables along the way can be indexed, the last part of the key path has to be a plain key.
+pub(crate) fn get_item_by_path<'a>(root: &'a Item, key_path: &str) -> Option<&'a Item> {
+    let (parent_path, key) = split_key_path(key_path);
+
+    let table_like = match parent_path {
+        Some(parent_path) => get_table_like_by_path(root, parent_path)?,
+        None => root.as_table_like()?,
+    };
+
+    match split_key_index(key) {
+        (key, None) => table_like.get(key),
+        (_, Some(_)) => None,
+    }
+}
+
+pub(crate) fn get_item_by_path_mut<'a>(root: &'a mut Item, key_path: &str) -> Option<&'a mut Item> {
+    let (parent_path, key) = split_key_path(key_path);
+
+    let table_like = get_table_like_by_path_mut(root, parent_path)?;
+
+    match split_key_index(key) {
+        (key, None) => table_like.get_mut(key),
+        (_, Some(_)) => None,
+    }
+}
+
+pub(crate) fn is_table_like_at_path(root: &Item, key_path: &str) -> bool {
+    get_table_like_by_path(root, key_path).is_some()
+}
+
+fn get_table_like_by_path<'a>(root: &'a Item, key_path: &str) -> Option<&'a dyn TableLike> {
+    let mut table_like = root.as_table_like()?;
+
+    for part in key_path.split('.') {
+        let (key, index) = split_key_index(part);
+
+        table_like = as_table_like_at(table_like.get(key)?, index)?;
+    }
+
+    Some(table_like)
+}
+
+fn get_table_like_by_path_mut<'a>(root: &'a mut Item, key_path: Option<&str>) -> Option<&'a mut dyn TableLike> {
+    let mut table_like = root.as_table_like_mut()?;
+
+    let Some(key_path) = key_path else {
+        return Some(table_like);
+    };
+
+    for part in key_path.split('.') {
+        let (key, index) = split_key_index(part);
+
+        table_like = as_table_like_at_mut(table_like.get_mut(key)?, index)?;
+    }
+
+    Some(table_like)
+}
+
+/// The item itself if `index` is `None`, otherwise the table at `index` of the array of tables (or array of inline tables).
+fn as_table_like_at(item: &Item, index: Option<usize>) -> Option<&dyn TableLike> {
+    match (item, index) {
+        (item, None) => item.as_table_like(),
+        (Item::ArrayOfTables(array_of_tables), Some(index)) => Some(array_of_tables.get(index)?),
+        (Item::Value(Value::Array(array)), Some(index)) => Some(array.get(index)?.as_inline_table()?),
+        _ => None,
+    }
+}
+
+fn as_table_like_at_mut(item: &mut Item, index: Option<usize>) -> Option<&mut dyn TableLike> {
+    match (item, index) {
+        (item, None) => item.as_table_like_mut(),
+        (Item::ArrayOfTables(array_of_tables), Some(index)) => Some(array_of_tables.get_mut(index)?),
+        (Item::Value(Value::Array(array)), Some(index)) => Some(array.get_mut(index)?.as_inline_table_mut()?),
+        _ => None,
+    }
+}
+
+/// Removes the item at the key path, returning it along with its key (which holds the key's comments).
+pub(crate) fn take_item_by_path(root: &mut Item, key_path: &str) -> Option<(Key, Item)> {
+    let (parent_path, key) = split_key_path(key_path);
+
+    let table_like = get_table_like_by_path_mut(root, parent_path)?;
+
+    let formatted_key = table_like.key(key)?.clone();
+    let item = table_like.remove(key)?;
+
+    Some((formatted_key, item))
+}
+
+/// Inserts the item at the key path, creating any tables leading up to it that don't exist yet.
+///
+/// `formatted_key` is used to carry over the comments above a key, pass `None` for a plain key.
+pub(crate) fn insert_item_by_path(root: &mut Item, key_path: &str, formatted_key: Option<&Key>, item: Item) -> Result<(), String> {
+    let (parent_path, key) = split_key_path(key_path);
+
+    if split_key_index(key).1.is_some() {
+        return Err(format!("Cannot insert '{key_path}' as it doesn't end with a key!"));
+    }
+
+    let mut table_like: &mut dyn TableLike = root.as_table_like_mut()
+        .ok_or_else(|| format!("Cannot insert '{key_path}' as the root is not a table!"))?;
+
+    if let Some(parent_path) = parent_path {
+        for part in parent_path.split('.') {
+            let item = match split_key_index(part) {
+                (key, None) => table_like.entry(key).or_insert_with(|| Item::Table(Table::new())),
+                // array elements are never made up, they have to exist already
+                (key, Some(_)) => table_like.get_mut(key)
+                    .ok_or_else(|| format!("Cannot insert '{key_path}' as '{key}' does not exist!"))?,
+            };
+
+            table_like = as_table_like_at_mut(item, split_key_index(part).1)
+                .ok_or_else(|| format!("Cannot insert '{key_path}' as '{part}' is not a table!"))?;
+        }
+    }
+
+    let new_key = match formatted_key {
+        Some(formatted_key) => Key::new(key).with_leaf_decor(formatted_key.leaf_decor().clone()),
+        None => Key::new(key),
+    };
+
+    match table_like.entry_format(&new_key) {
+        Entry::Occupied(mut entry) => {
+            entry.insert(item);
+        },
+        Entry::Vacant(entry) => {
+            entry.insert(item);
+        },
+    }
+
+    Ok(())
+}
+
+/// Every key path leading to a value (or array of tables), in document order. Tables are walked into, not returned.
+pub(crate) fn get_leaf_key_paths(root: &Item) -> Vec<String> {
+    let mut key_paths = Vec::new();
+
+    if let Some(table_like) = root.as_table_like() {
+        collect_leaf_key_paths(table_like, None, &mut key_paths);
+    }
+
+    key_paths
+}
+
+fn collect_leaf_key_paths(table_like: &dyn TableLike, parent_path: Option<&str>, key_paths: &mut Vec<String>) {
+    for (key, item) in table_like.iter() {
+        let key_path = match parent_path {
+            Some(parent_path) => format!("{}.{}", parent_path, key),
+            None => key.to_string(),
+        };
+
+        match item {
+            Item::Table(table) => collect_leaf_key_paths(table, Some(&key_path), key_paths),
+            Item::None => {},
+            _ => key_paths.push(key_path),
+        }
+    }
+}
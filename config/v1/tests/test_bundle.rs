@@ -0,0 +1,104 @@
+use std::fs;
+
+use cirrus_config_v1::{config::{bundle::{BundledFile, ConfigBundle}, CConfig}, error::Error};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE: &str = r#"version = 1
+
+# @range 1..=50
+max_cats = 15
+
+[misc]
+name = "Goldy"
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: u8,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Misc {
+    name: String,
+}
+
+impl CConfig for Config {}
+
+#[test]
+fn test_bundle_round_trip() -> Result<(), Error> {
+    let config = "version = 1\n\n# fewer cats\nmax_cats = 3\n\n[misc]\nname = \"Whiskers\"\n";
+
+    let bundle = ConfigBundle::new("meow", config)
+        .with_global_config("version = 1\ntheme = \"light\"\n");
+
+    let bundle_toml_string = bundle.to_toml_string()?;
+    let imported_bundle = ConfigBundle::from_toml_string(&bundle_toml_string)?;
+
+    assert_eq!(imported_bundle, bundle);
+    // the user's comments come along
+    assert!(imported_bundle.config.contains("# fewer cats"));
+
+    imported_bundle.validate::<Config>(TEMPLATE)?;
+
+    let diff = imported_bundle.diff(Some(TEMPLATE), None)?;
+
+    let key_paths: Vec<&str> = diff.config_changes.iter().map(|change| change.key_path.as_str()).collect();
+    assert_eq!(key_paths, vec!["max_cats", "misc.name"]);
+
+    let global_key_paths: Vec<&str> = diff.global_config_changes.iter().map(|change| change.key_path.as_str()).collect();
+    assert_eq!(global_key_paths, vec!["version", "theme"]);
+
+    assert!(matches!(
+        ConfigBundle::from_toml_string(&bundle_toml_string.replace("version = 1\napp_name", "version = 99\napp_name")),
+        Err(Error::InvalidConfigBundle { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_bundles() {
+    let bundle = ConfigBundle::new("meow", "version = 1\nmax_cats = 200\n\n[misc]\nname = \"Goldy\"\n");
+    assert!(matches!(bundle.validate::<Config>(TEMPLATE), Err(Error::InvalidConfigBundle { .. })));
+
+    let mut bundle = ConfigBundle::new("meow", TEMPLATE);
+    bundle.theme_files.push(BundledFile { path: "pack/../../../.bashrc".into(), contents: String::new() });
+
+    assert!(matches!(bundle.validate::<Config>(TEMPLATE), Err(Error::InvalidConfigBundle { .. })));
+
+    let bundle = ConfigBundle::new("meow", TEMPLATE).with_global_config("reduced_motion = \"yes\"\n");
+    assert!(matches!(bundle.validate::<Config>(TEMPLATE), Err(Error::InvalidConfigBundle { .. })));
+}
+
+#[test]
+fn test_bundle_theme_packs() -> Result<(), Error> {
+    let test_dir_path = std::env::temp_dir().join("cirrus_config_test_bundle");
+    let _ = fs::remove_dir_all(&test_dir_path);
+
+    let themes_dir_path = test_dir_path.join("themes");
+    fs::create_dir_all(themes_dir_path.join("my-pack").join("rose")).unwrap();
+    fs::write(themes_dir_path.join("my-pack").join("rose").join("theme.toml"), "version = 1\n").unwrap();
+    fs::write(themes_dir_path.join("my-pack").join("preview.png"), [0x89, 0x50, 0x4e, 0x47, 0xff, 0xfe]).unwrap();
+
+    let mut bundle = ConfigBundle::new("meow", TEMPLATE);
+    bundle.add_theme_pack(&themes_dir_path, "my-pack")?;
+
+    // the image isn't text so it's skipped
+    assert_eq!(bundle.theme_files, vec![BundledFile { path: "my-pack/rose/theme.toml".into(), contents: "version = 1\n".into() }]);
+
+    let imported_themes_dir_path = test_dir_path.join("imported_themes");
+
+    ConfigBundle::from_toml_string(&bundle.to_toml_string()?)?
+        .write_theme_files(&imported_themes_dir_path)?;
+
+    assert_eq!(
+        fs::read_to_string(imported_themes_dir_path.join("my-pack").join("rose").join("theme.toml")).unwrap(),
+        "version = 1\n"
+    );
+
+    fs::remove_dir_all(&test_dir_path).unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::{borrow::Cow, fs};
+
+use cirrus_config_v1::{config::{bundle::{export_config_bundle_with_format, import_config_bundle_with_format, preview_config_bundle_with_format, BundleExportOptions}, format::ConfigFormat, get_and_create_config_file_lenient_with_format, migration::Migrations, CConfig}, error::Error};
+use cirrus_path::CLOUDY_HOME_ENV_VAR;
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE: &str = r#"version = 1
+
+# @range 1..=50
+max_cats = 15
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: u8,
+}
+
+impl CConfig for Config {}
+
+/// TOML with a header line the TOML parser would choke on, so anything that skips the conversion fails.
+struct HeaderFormat;
+
+const HEADER: &str = "%header\n";
+
+impl ConfigFormat for HeaderFormat {
+    fn name(&self) -> &'static str {
+        "Header"
+    }
+
+    fn extension(&self) -> &'static str {
+        "header"
+    }
+
+    fn to_toml_string<'a>(&self, config_string: &'a str) -> Result<Cow<'a, str>, Error> {
+        config_string.strip_prefix(HEADER)
+            .map(Cow::Borrowed)
+            .ok_or_else(|| Error::ConfigFormatFailure { format: self.name().to_string(), error: "Missing header!".to_string() })
+    }
+
+    fn to_config_string(&self, toml_string: &str, _previous_config_string: Option<&str>) -> Result<String, Error> {
+        Ok(format!("{HEADER}{toml_string}"))
+    }
+}
+
+// the only test in this file as it changes the environment of the whole test binary
+#[test]
+fn test_bundle_with_format() -> Result<(), Error> {
+    let cloudy_home_path = std::env::temp_dir().join("cirrus_config_test_bundle_format");
+    let _ = fs::remove_dir_all(&cloudy_home_path);
+
+    unsafe { std::env::set_var(CLOUDY_HOME_ENV_VAR, &cloudy_home_path); }
+
+    let (_, config_path, _) = get_and_create_config_file_lenient_with_format::<Config>(
+        "meow", TEMPLATE, &Migrations::new(), &HeaderFormat
+    )?;
+
+    assert_eq!(config_path, cloudy_home_path.join("config").join("meow").join("config.header"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), format!("{HEADER}{TEMPLATE}"));
+
+    let mut bundle = export_config_bundle_with_format("meow", &BundleExportOptions::default(), &HeaderFormat)?;
+
+    // bundled as toml
+    assert_eq!(bundle.config, TEMPLATE);
+
+    bundle.config = bundle.config.replace("max_cats = 15", "max_cats = 3");
+
+    let diff = preview_config_bundle_with_format::<Config>(&bundle, "meow", TEMPLATE, &HeaderFormat)?;
+
+    let key_paths: Vec<&str> = diff.config_changes.iter().map(|change| change.key_path.as_str()).collect();
+    assert_eq!(key_paths, vec!["max_cats"]);
+
+    import_config_bundle_with_format::<Config>(&bundle, "meow", TEMPLATE, &HeaderFormat)?;
+
+    let (config, _, diagnostics) = get_and_create_config_file_lenient_with_format::<Config>(
+        "meow", TEMPLATE, &Migrations::new(), &HeaderFormat
+    )?;
+
+    assert_eq!(config.max_cats, 3);
+    assert!(diagnostics.is_empty());
+    assert!(!config_path.with_file_name("config.toml").exists());
+
+    unsafe { std::env::remove_var(CLOUDY_HOME_ENV_VAR); }
+    fs::remove_dir_all(&cloudy_home_path).unwrap();
+
+    Ok(())
+}
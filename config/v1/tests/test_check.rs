@@ -0,0 +1,99 @@
+use std::fs;
+
+use cirrus_config_v1::{config::CConfig, error::Error, template::{check_config_key_paths, check_template, TemplateProblem}};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE_CONFIG: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = 15
+
+[misc]
+theme = "dark"
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: i64,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Misc {
+    theme: String,
+    #[serde(default)]
+    allow_naps: bool,
+}
+
+impl CConfig for Config {}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct BrokenConfig {
+    version: i8,
+    max_cats: String,
+}
+
+impl CConfig for BrokenConfig {}
+
+#[test]
+fn test_check_template() {
+    assert_eq!(
+        check_template::<Config>(TEMPLATE_CONFIG),
+        vec![
+            TemplateProblem::MissingDocstring { key_path: "misc.theme".to_string() },
+            TemplateProblem::MissingKey { key_path: "misc.allow_naps".to_string() },
+        ]
+    );
+
+    assert!(
+        matches!(
+            check_template::<BrokenConfig>(TEMPLATE_CONFIG).as_slice(),
+            [TemplateProblem::DeserializeFailure { .. }]
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "The config template does not match 'Config'")]
+fn test_assert_config_template() {
+    cirrus_config_v1::assert_config_template!(Config, TEMPLATE_CONFIG);
+}
+
+#[test]
+fn test_check_config_key_paths() -> Result<(), Error> {
+    let source_dir_path = std::env::temp_dir().join("cirrus_config_test_check_config_key_paths");
+    fs::create_dir_all(source_dir_path.join("widgets")).unwrap();
+
+    fs::write(
+        source_dir_path.join("main.rs"),
+        "// config_key_path!(config.commented_out)\nlet path = config_key_path!(self.config.misc.theme);\n"
+    ).unwrap();
+
+    fs::write(
+        source_dir_path.join("widgets/settings.rs"),
+        "let a = config_key_path!(config.max_cats);\nlet b = config_key_path!(\n config.misc.naps);\nlet c = config_key_path!(config.misc.nap);\n"
+    ).unwrap();
+
+    let problems = check_config_key_paths(TEMPLATE_CONFIG, &source_dir_path);
+
+    let _ = fs::remove_dir_all(&source_dir_path);
+
+    assert_eq!(
+        problems?,
+        vec![
+            TemplateProblem::UnknownKeyPath {
+                key_path: "misc.naps".to_string(),
+                file_path: source_dir_path.join("widgets/settings.rs"),
+                line_number: 2,
+            },
+            TemplateProblem::UnknownKeyPath {
+                key_path: "misc.nap".to_string(),
+                file_path: source_dir_path.join("widgets/settings.rs"),
+                line_number: 4,
+            },
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use cirrus_config_v1::{config::{get_and_create_config_file, CConfig}, error::Error};
+use cirrus_path::{AppDirKind, AppDirs, CLOUDY_HOME_ENV_VAR};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE_CONFIG: &str = r#"version = 1
+
+# Maximum amount of cats.
+max_cats = 15
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: i64,
+}
+
+impl CConfig for Config {}
+
+// the only test in this file as it changes the environment of the whole test binary
+#[test]
+fn test_cloudy_home_redirects_app_folders() -> Result<(), Error> {
+    let cloudy_home_path = std::env::temp_dir().join("cirrus_config_test_cloudy_home");
+    let _ = fs::remove_dir_all(&cloudy_home_path);
+
+    unsafe { std::env::set_var(CLOUDY_HOME_ENV_VAR, &cloudy_home_path); }
+
+    let (config, config_path) = get_and_create_config_file::<Config>("meow", TEMPLATE_CONFIG)?;
+
+    assert_eq!(config.max_cats, 15);
+    assert_eq!(config_path, cloudy_home_path.join("config").join("meow").join("config.toml"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), TEMPLATE_CONFIG);
+
+    let app_dirs = AppDirs::new("meow");
+
+    assert_eq!(app_dirs.path(AppDirKind::Config).unwrap(), cloudy_home_path.join("config").join("meow"));
+    assert_eq!(app_dirs.state_dir().unwrap(), cloudy_home_path.join("state").join("meow"));
+    assert_eq!(app_dirs.log_dir().unwrap(), cloudy_home_path.join("state").join("meow").join("logs"));
+    assert!(cloudy_home_path.join("state").join("meow").join("logs").is_dir());
+
+    let runtime_dir_path = app_dirs.runtime_dir().unwrap();
+    assert_eq!(runtime_dir_path, cloudy_home_path.join("runtime").join("meow"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        assert_eq!(fs::metadata(&runtime_dir_path).unwrap().permissions().mode() & 0o777, 0o700);
+    }
+
+    unsafe { std::env::remove_var(CLOUDY_HOME_ENV_VAR); }
+    fs::remove_dir_all(&cloudy_home_path).unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use cirrus_config_v1::{error::Error, template::{write_config_docs, Template, JSON_SCHEMA_FILE_NAME}};
+
+const TEMPLATE_CONFIG: &str = r#"version = 1
+
+# @brief Maximum amount of cats.
+# Adjust this value to limit the maximum amount of 🐈 cats that are allowed to spawn in.
+#
+# Don't go <too> high.
+#
+# @range 1..=100
+# @example 30
+# @since 0.2.0
+max_cats = 15
+
+[misc]
+# The theme the cats are rendered with.
+# @choices ["dark", "light"]
+theme = "dark"
+
+# @deprecated Use 'misc.theme' instead.
+legacy_renderer = false
+
+[[plugins]]
+# Name of the plugin.
+name = "purr"
+"#;
+
+fn template() -> Result<Template<'static>, Error> {
+    let mut template = Template::new(TEMPLATE_CONFIG);
+    template.parse_keys()?;
+
+    Ok(template)
+}
+
+#[test]
+fn test_markdown_docs() -> Result<(), Error> {
+    let markdown = template()?.to_markdown("Cats config");
+
+    assert!(markdown.starts_with("# Cats config\n"));
+    assert!(markdown.contains(
+        "### `max_cats`\n\nAdjust this value to limit the maximum amount of 🐈 cats that are allowed to spawn in.\n\nDon't go <too> high.\n\n"
+    ));
+    assert!(markdown.contains("| Type | `integer` |\n| Default | `15` |\n| Range | `1..=100` |\n| Examples | `30` |\n| Since | 0.2.0 |\n"));
+    assert!(markdown.contains("## `[misc]`\n\n### `misc.theme`"));
+    assert!(markdown.contains("| Allowed values | `\"dark\"`, `\"light\"` |"));
+    assert!(markdown.contains("> **Deprecated!** Use 'misc.theme' instead."));
+
+    // keys are kept in the template's order
+    assert!(markdown.find("`max_cats`") < markdown.find("`misc.theme`"));
+
+    Ok(())
+}
+
+#[test]
+fn test_html_docs() -> Result<(), Error> {
+    let html = template()?.to_html("Cats <config>");
+
+    assert!(html.contains("<h1>Cats &lt;config&gt;</h1>"));
+    assert!(html.contains("<p>Don't go &lt;too&gt; high.</p>"));
+    assert!(html.contains("<dt>Default</dt><dd><code>15</code></dd>"));
+    assert!(html.contains("<section id=\"misc.legacy_renderer\">"));
+
+    Ok(())
+}
+
+#[test]
+fn test_json_schema() -> Result<(), Error> {
+    let output_dir_path = std::env::temp_dir().join("cirrus_config_test_json_schema");
+
+    let paths = write_config_docs(TEMPLATE_CONFIG, &output_dir_path, "Cats config")?;
+    assert_eq!(paths.len(), 3);
+
+    let schema: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(output_dir_path.join(JSON_SCHEMA_FILE_NAME)).unwrap()
+    ).unwrap();
+
+    let _ = std::fs::remove_dir_all(&output_dir_path);
+
+    let max_cats = &schema["properties"]["max_cats"];
+
+    assert_eq!(max_cats["type"], "integer");
+    assert_eq!(max_cats["title"], "Maximum amount of cats.");
+    assert_eq!(max_cats["default"], 15);
+    assert_eq!(max_cats["minimum"], 1);
+    assert_eq!(max_cats["maximum"], 100);
+    assert_eq!(max_cats["examples"], serde_json::json!([30]));
+
+    let misc = &schema["properties"]["misc"];
+
+    assert_eq!(misc["type"], "object");
+    assert_eq!(misc["properties"]["theme"]["enum"], serde_json::json!(["dark", "light"]));
+    assert_eq!(misc["properties"]["legacy_renderer"]["deprecated"], true);
+
+    let plugins = &schema["properties"]["plugins"];
+
+    assert_eq!(plugins["type"], "array");
+    assert_eq!(plugins["items"]["properties"]["name"]["type"], "string");
+
+    Ok(())
+}
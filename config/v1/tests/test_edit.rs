@@ -0,0 +1,284 @@
+use cirrus_config_v1::{config::edit::{edit_config_document, insert_missing_keys}, error::Error};
+use toml_edit::DocumentMut;
+
+const DISK_CONFIG: &str = r#"# How many cats.
+max_cats = 15 # not too many
+numbers = [1, 2, 3]
+point = { x = 1, y = 2 }
+
+[misc]
+# The cat's name.
+name = "Goldy"
+
+# The first plugin.
+[[plugins]]
+name = "purr"
+enabled = true
+
+[[plugins]]
+name = "hiss"
+enabled = true
+
+[[plugins]]
+name = "meow"
+enabled = false
+"#;
+
+fn edit(config_toml_string: &str) -> String {
+    let mut document: DocumentMut = DISK_CONFIG.parse().unwrap();
+    let config_document: DocumentMut = config_toml_string.parse().unwrap();
+
+    edit_config_document(&mut document, &config_document);
+
+    document.to_string()
+}
+
+#[test]
+fn test_unchanged_config_is_untouched() {
+    let config = r#"max_cats = 15
+numbers = [1, 2, 3]
+
+[point]
+x = 1
+y = 2
+
+[misc]
+name = "Goldy"
+
+[[plugins]]
+name = "purr"
+enabled = true
+
+[[plugins]]
+name = "hiss"
+enabled = true
+
+[[plugins]]
+name = "meow"
+enabled = false
+"#;
+
+    assert_eq!(edit(config), DISK_CONFIG);
+}
+
+#[test]
+fn test_edit_values_tables_and_arrays() {
+    let config = r#"max_cats = 3
+numbers = [1, 5]
+
+[point]
+x = 1
+y = 7
+
+[misc]
+name = "Goldy"
+
+[[plugins]]
+name = "purr"
+enabled = true
+
+[[plugins]]
+name = "hiss"
+enabled = false
+"#;
+
+    let expected = r#"# How many cats.
+max_cats = 3 # not too many
+numbers = [1, 5]
+point = { x = 1, y = 7 }
+
+[misc]
+# The cat's name.
+name = "Goldy"
+
+# The first plugin.
+[[plugins]]
+name = "purr"
+enabled = true
+
+[[plugins]]
+name = "hiss"
+enabled = false
+"#;
+
+    assert_eq!(edit(config), expected);
+}
+
+#[test]
+fn test_add_array_of_tables_element() {
+    let config = r#"max_cats = 15
+numbers = [1, 2, 3, 4]
+
+[point]
+x = 1
+y = 2
+
+[misc]
+name = "Goldy"
+
+[[plugins]]
+name = "purr"
+enabled = true
+
+[[plugins]]
+name = "hiss"
+enabled = true
+
+[[plugins]]
+name = "meow"
+enabled = false
+
+[[plugins]]
+name = "nap"
+enabled = true
+"#;
+
+    let edited = edit(config);
+
+    assert!(edited.contains("numbers = [1, 2, 3, 4]"));
+    assert!(edited.ends_with("[[plugins]]\nname = \"meow\"\nenabled = false\n\n[[plugins]]\nname = \"nap\"\nenabled = true\n"), "{}", edited);
+}
+
+#[test]
+fn test_insert_missing_keys() -> Result<(), Error> {
+    let template = r#"version = 2
+
+# How many cats.
+max_cats = 15
+
+# How fast the cats run.
+speed = 1.5 # in cats per second
+
+[misc]
+# The cat's name.
+name = "Goldy"
+
+# Whether the cat naps.
+nap = true
+
+# Settings for the vet.
+[vet]
+# The vet's name.
+name = "Dr. Whiskers"
+"#;
+
+    let disk_config = r#"version = 2
+max_cats = 3 # I only have three
+
+[misc]
+nap = false
+"#;
+
+    let config = r#"version = 2
+max_cats = 3
+speed = 2.0
+
+[misc]
+name = "Meow"
+nap = false
+
+[vet]
+name = "Dr. Whiskers"
+"#;
+
+    let mut document: DocumentMut = disk_config.parse().unwrap();
+    let config_document: DocumentMut = config.parse().unwrap();
+
+    edit_config_document(&mut document, &config_document);
+    insert_missing_keys(&mut document, &config_document, template)?;
+
+    let expected = r#"version = 2
+max_cats = 3 # I only have three
+
+# How fast the cats run.
+speed = 2.0 # in cats per second
+
+[misc]
+# The cat's name.
+name = "Meow"
+nap = false
+
+# Settings for the vet.
+[vet]
+# The vet's name.
+name = "Dr. Whiskers"
+"#;
+
+    assert_eq!(document.to_string(), expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_add_keys_to_inline_tables_and_array_elements() {
+    let config = r#"max_cats = 15
+numbers = [1, 2, 3]
+
+[point]
+x = 1
+y = 2
+z = 3
+
+[misc]
+name = "Goldy"
+
+[[plugins]]
+name = "purr"
+enabled = true
+priority = 1
+
+[[plugins]]
+name = "hiss"
+enabled = true
+
+[[plugins]]
+name = "meow"
+enabled = false
+"#;
+
+    let edited = edit(config);
+
+    assert!(edited.contains("point = { x = 1, y = 2, z = 3 }\n"), "{}", edited);
+    assert!(edited.contains("# The first plugin.\n[[plugins]]\nname = \"purr\"\nenabled = true\npriority = 1\n\n[[plugins]]\nname = \"hiss\""), "{}", edited);
+}
+
+#[test]
+fn test_remove_keys_from_inline_tables_and_array_elements() {
+    // e.g: 'Option's set to 'None' aren't serialized
+    let config = r#"max_cats = 15
+numbers = [1, 2, 3]
+
+[point]
+x = 1
+
+[misc]
+name = "Goldy"
+
+[[plugins]]
+name = "purr"
+
+[[plugins]]
+name = "hiss"
+enabled = true
+
+[[plugins]]
+name = "meow"
+enabled = false
+"#;
+
+    let edited = edit(config);
+
+    assert!(edited.contains("point = { x = 1 }\n"), "{}", edited);
+    assert!(edited.contains("# The first plugin.\n[[plugins]]\nname = \"purr\"\n\n[[plugins]]\nname = \"hiss\""), "{}", edited);
+}
+
+#[test]
+fn test_keys_outside_of_inline_tables_and_array_elements_are_kept() {
+    let mut document: DocumentMut = "max_cats = 15\nfrom_a_newer_version = true\n\n[misc]\nname = \"Goldy\"\n".parse().unwrap();
+    let config_document: DocumentMut = "max_cats = 15\nspeed = 2.0\n\n[misc]\nname = \"Goldy\"\nnap = true\n".parse().unwrap();
+
+    edit_config_document(&mut document, &config_document);
+
+    // missing keys are left to 'insert_missing_keys' so they get the template's docstrings
+    assert_eq!(document.to_string(), "max_cats = 15\nfrom_a_newer_version = true\n\n[misc]\nname = \"Goldy\"\n");
+}
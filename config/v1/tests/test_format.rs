@@ -0,0 +1,107 @@
+use cirrus_config_v1::{config::{format::{ConfigFormat, TomlFormat}, parse_config}, error::Error};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE_CONFIG: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = 15
+
+[misc]
+# Path to the custom folder.
+custom_folder = "~/cats"
+sizes = [1, 2, 3]
+"#;
+
+#[derive(Debug, Default, PartialEq, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: i64,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, PartialEq, Hash, Serialize, Deserialize)]
+struct Misc {
+    custom_folder: String,
+    sizes: Vec<i64>,
+}
+
+impl cirrus_config_v1::config::CConfig for Config {}
+
+#[test]
+fn test_toml_format_is_untouched() -> Result<(), Error> {
+    assert_eq!(TomlFormat.file_name(), "config.toml");
+
+    assert_eq!(TomlFormat.to_toml_string(TEMPLATE_CONFIG)?, TEMPLATE_CONFIG);
+    assert_eq!(TomlFormat.to_config_string(TEMPLATE_CONFIG, Some("# old"))?, TEMPLATE_CONFIG);
+
+    let config = parse_config::<Config>(&TomlFormat.to_toml_string(TEMPLATE_CONFIG)?, TEMPLATE_CONFIG)?;
+
+    assert_eq!(config.misc, Misc { custom_folder: "~/cats".to_string(), sizes: vec![1, 2, 3] });
+
+    Ok(())
+}
+
+/// Writes the template in `format`, then reads it back the way the config would be loaded.
+#[cfg(any(feature = "ron", feature = "json5", feature = "yaml"))]
+fn round_trip(format: &dyn ConfigFormat) -> Result<String, Error> {
+    let config_string = format.to_config_string(TEMPLATE_CONFIG, None)?;
+
+    let config = parse_config::<Config>(&format.to_toml_string(&config_string)?, TEMPLATE_CONFIG)?;
+
+    assert_eq!(config.max_cats, 15);
+    assert_eq!(config.misc, Misc { custom_folder: "~/cats".to_string(), sizes: vec![1, 2, 3] });
+
+    Ok(config_string)
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_format() -> Result<(), Error> {
+    use cirrus_config_v1::config::format::YamlFormat;
+
+    let config_string = round_trip(&YamlFormat)?;
+
+    // keys keep the template's order
+    assert!(config_string.starts_with("version: 1\nmax_cats: 15\nmisc:\n"));
+
+    let saved_config_string = YamlFormat.to_config_string(
+        TEMPLATE_CONFIG, Some(&format!("# My cats.\n\n{config_string}"))
+    )?;
+
+    assert!(saved_config_string.starts_with("# My cats.\n\nversion: 1\n"));
+
+    assert!(
+        matches!(
+            YamlFormat.to_toml_string("version: 1\nmax_cats: ~\n"),
+            Err(Error::ConfigFormatFailure { .. })
+        )
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "json5")]
+#[test]
+fn test_json5_format() -> Result<(), Error> {
+    use cirrus_config_v1::config::format::Json5Format;
+
+    round_trip(&Json5Format)?;
+
+    let toml_string = Json5Format.to_toml_string(
+        "// My cats.\n{ version: 1, max_cats: 15, misc: { custom_folder: '~/cats', sizes: [1, 2, 3,], }, }"
+    )?;
+
+    assert_eq!(parse_config::<Config>(&toml_string, TEMPLATE_CONFIG)?.max_cats, 15);
+
+    Ok(())
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_format() -> Result<(), Error> {
+    use cirrus_config_v1::config::format::RonFormat;
+
+    round_trip(&RonFormat)?;
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use cirrus_config_v1::{config::lenient::ConfigDiagnosticReason, error::Error, global::{parse_global_config, GlobalConfig, GlobalConfigOverrides, GLOBAL_TEMPLATE_CONFIG_TOML}};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn test_global_template_matches_default() -> Result<(), Error> {
+    let (global_config, diagnostics) = parse_global_config(GLOBAL_TEMPLATE_CONFIG_TOML)?;
+
+    assert!(diagnostics.is_empty());
+    assert_eq!(global_config, GlobalConfig::default());
+
+    Ok(())
+}
+
+#[test]
+fn test_global_config_parse() -> Result<(), Error> {
+    let config_toml_string = r##"version = 1
+theme = "light"
+accent_colour = "#e05f78"
+reduced_motion = "yes please"
+check_for_updates = false
+"##;
+
+    let (global_config, diagnostics) = parse_global_config(config_toml_string)?;
+
+    assert_eq!(global_config.theme.as_deref(), Some("light"));
+    assert_eq!(global_config.accent_colour.as_deref(), Some("#e05f78"));
+    assert_eq!(global_config.locale, None);
+    assert!(!global_config.reduced_motion);
+    assert!(!global_config.check_for_updates);
+
+    let reduced_motion_diagnostic = diagnostics.iter()
+        .find(|diagnostic| diagnostic.key_path == "reduced_motion")
+        .expect("invalid 'reduced_motion' should be diagnosed");
+
+    assert!(matches!(reduced_motion_diagnostic.reason, ConfigDiagnosticReason::InvalidType { .. }));
+
+    Ok(())
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct AppConfig {
+    version: i8,
+    #[serde(default)]
+    global: GlobalConfigOverrides,
+}
+
+#[test]
+fn test_app_config_overrides_global_config() {
+    let app_config: AppConfig = toml::from_str(r#"version = 1
+
+[global]
+theme = "dark"
+editor = ""
+check_for_updates = false
+"#).unwrap();
+
+    let global_config = GlobalConfig {
+        theme: Some("light".into()),
+        editor: Some("nvim".into()),
+        reduced_motion: true,
+        ..Default::default()
+    };
+
+    let app_global_config = global_config.with_overrides(&app_config.global);
+
+    assert_eq!(app_global_config.theme.as_deref(), Some("dark"));
+    // empty strings inherit the global value
+    assert_eq!(app_global_config.editor.as_deref(), Some("nvim"));
+    assert!(app_global_config.reduced_motion);
+    assert!(!app_global_config.check_for_updates);
+
+    let app_config_without_overrides: AppConfig = toml::from_str("version = 1").unwrap();
+
+    assert_eq!(global_config.with_overrides(&app_config_without_overrides.global), global_config);
+}
+
+#[test]
+fn test_global_template_matches_struct() {
+    cirrus_config_v1::assert_config_template!(GlobalConfig, GLOBAL_TEMPLATE_CONFIG_TOML);
+}
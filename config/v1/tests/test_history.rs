@@ -0,0 +1,107 @@
+use cirrus_config_v1::{config::{history::ConfigHistory, CConfig}, error::Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Hash, PartialEq, Serialize, Deserialize)]
+struct Config {
+    max_cats: u8,
+    misc: Misc,
+}
+
+#[derive(Debug, Clone, Default, Hash, PartialEq, Serialize, Deserialize)]
+struct Misc {
+    name: String,
+}
+
+impl CConfig for Config {}
+
+#[test]
+fn test_undo_and_redo() -> Result<(), Error> {
+    let mut history = ConfigHistory::default();
+    let mut config = Config { max_cats: 15, misc: Misc { name: "Goldy".into() } };
+
+    assert!(!history.record(&config)?); // just the snapshot
+
+    config.max_cats = 20;
+    assert!(history.record(&config)?);
+
+    config.misc.name = "Whiskers".into();
+    assert!(history.record(&config)?);
+
+    assert!(history.undo(&mut config)?);
+    assert_eq!(config, Config { max_cats: 20, misc: Misc { name: "Goldy".into() } });
+
+    assert!(history.undo(&mut config)?);
+    assert_eq!(config.max_cats, 15);
+    assert!(!history.undo(&mut config)?);
+
+    assert!(history.redo(&mut config)?);
+    assert_eq!(config.max_cats, 20);
+    assert!(!history.record(&config)?); // redoing isn't a new change
+
+    // a new change forgets what could be redone
+    config.max_cats = 3;
+    assert!(history.record(&config)?);
+    assert!(!history.can_redo());
+
+    assert!(history.undo(&mut config)?);
+    assert_eq!(config, Config { max_cats: 20, misc: Misc { name: "Goldy".into() } });
+
+    Ok(())
+}
+
+#[test]
+fn test_changes_are_coalesced_and_bounded() -> Result<(), Error> {
+    let mut history = ConfigHistory::new(2);
+    let mut config = Config::default();
+
+    history.record(&config)?;
+
+    // like dragging a slider, each frame changes the same key
+    for max_cats in 1..=10 {
+        config.max_cats = max_cats;
+        history.record(&config)?;
+    }
+
+    assert!(history.undo(&mut config)?);
+    assert_eq!(config.max_cats, 0);
+    assert!(!history.can_undo());
+
+    for name in ["a", "b", "c"] {
+        config.misc.name = name.into();
+        history.record(&config)?;
+
+        config.max_cats += 1;
+        history.record(&config)?;
+    }
+
+    let mut undo_count = 0;
+
+    while history.undo(&mut config)? {
+        undo_count += 1;
+    }
+
+    assert_eq!(undo_count, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_reloads_are_not_undoable() -> Result<(), Error> {
+    let mut history = ConfigHistory::default();
+    let mut config = Config { max_cats: 15, misc: Misc { name: "Goldy".into() } };
+
+    history.record(&config)?;
+
+    config.max_cats = 20;
+    assert!(history.record(&config)?);
+
+    // like the config file being edited by hand and reloaded from disk
+    config = Config { max_cats: 30, misc: Misc { name: "Whiskers".into() } };
+    history.resnapshot(&config)?;
+
+    assert!(!history.record(&config)?);
+    assert!(!history.undo(&mut config)?);
+    assert_eq!(config, Config { max_cats: 30, misc: Misc { name: "Whiskers".into() } });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use cirrus_config_v1::{config::{key_path::ConfigKeyPath, layered::{ConfigLayer, ConfigOverride}, CConfig}, error::Error, template::Template, ConfigKeys};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE_CONFIG: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max-cats = 15
+
+[misc]
+# The theme the cats are rendered with.
+theme = "dark"
+enableCustomFolder = false
+sizes = [1, 2]
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize, ConfigKeys)]
+#[config_keys(crate = cirrus_config_v1)]
+struct Config {
+    version: i8,
+    #[serde(rename = "max-cats")]
+    max_cats: i64,
+    misc: Misc<Vec<u8>>,
+    #[serde(skip)]
+    _cache: String,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize, ConfigKeys)]
+#[config_keys(crate = cirrus_config_v1)]
+#[serde(rename_all = "camelCase")]
+struct Misc<S> {
+    theme: Theme,
+    enable_custom_folder: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    custom_folder: Option<String>,
+    #[serde(flatten)]
+    extra: Extra<S>,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize, ConfigKeys)]
+#[config_keys(crate = cirrus_config_v1)]
+struct Extra<S> {
+    sizes: S,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Hash, Serialize, Deserialize, ConfigKeys)]
+#[config_keys(crate = cirrus_config_v1)]
+#[serde(rename_all = "lowercase")]
+enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl CConfig for Config {}
+
+#[test]
+fn test_key_paths_follow_serde() -> Result<(), Error> {
+    let key_paths = Config::key_paths();
+
+    assert_eq!(key_paths.version().as_str(), "version");
+    assert_eq!(key_paths.max_cats().as_str(), "max-cats");
+    assert_eq!(key_paths.misc().as_ref(), "misc");
+    assert_eq!(key_paths.misc().theme(), ConfigKeyPath::<Theme>::new("misc.theme"));
+    assert_eq!(key_paths.misc().enable_custom_folder().as_str(), "misc.enableCustomFolder");
+    assert_eq!(key_paths.misc().extra().sizes().as_str(), "misc.sizes");
+
+    // every key path is in the template
+    let mut template = Template::new(TEMPLATE_CONFIG);
+    template.parse_keys()?;
+
+    for key_path in [&*key_paths.max_cats(), &key_paths.misc().theme(), &key_paths.misc().extra().sizes()] {
+        assert!(template.keys.contains_key(key_path), "'{key_path}' is not in the template");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_get_and_set_by_key_path() -> Result<(), Error> {
+    let mut config: Config = toml::from_str(TEMPLATE_CONFIG).unwrap();
+    let misc = Config::key_paths().misc();
+
+    assert_eq!(Config::key_paths().max_cats().get(&config)?, 15);
+    assert_eq!(misc.theme().get(&config)?, Theme::Dark);
+    assert_eq!(misc.custom_folder().get(&config)?, None);
+
+    misc.theme().set(&mut config, Theme::Light)?;
+    misc.custom_folder().set(&mut config, Some("~/cats".to_string()))?;
+    misc.extra().sizes().set(&mut config, vec![3])?;
+
+    assert_eq!(config.misc.theme, Theme::Light);
+    assert_eq!(config.misc.custom_folder.as_deref(), Some("~/cats"));
+    assert_eq!(config.misc.extra.sizes, vec![3]);
+
+    misc.custom_folder().set(&mut config, None)?;
+    assert_eq!(config.misc.custom_folder, None);
+
+    let config_override = ConfigOverride::new(&misc.theme(), &Theme::Light, ConfigLayer::CommandLine)?;
+
+    assert_eq!(config_override.key_path, "misc.theme");
+    assert_eq!(config_override.value.as_str(), Some("light"));
+
+    Ok(())
+}
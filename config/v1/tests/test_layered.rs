@@ -0,0 +1,94 @@
+use cirrus_config_v1::{config::{CConfig, layered::{apply_overrides, cli_overrides, env_overrides_from_vars, mask_overrides, resolve_config, ConfigLayer}}, error::Error};
+use serde::{Deserialize, Serialize};
+use toml_edit::DocumentMut;
+
+const TEMPLATE: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = 15
+
+[misc]
+# Whether to look for cats in a custom folder.
+enable_custom_folder = false
+name = "Goldy"
+"#;
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: u8,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Misc {
+    enable_custom_folder: bool,
+    name: String,
+}
+
+impl CConfig for Config {}
+
+#[test]
+fn test_layers() -> Result<(), Error> {
+    let vars = vec![
+        (String::from("CTK_CAT_APP_MISC__ENABLE_CUSTOM_FOLDER"), String::from("true")),
+        (String::from("CTK_CAT_APP_MAX_CATS"), String::from("20")),
+        (String::from("CTK_DOG_APP_MAX_CATS"), String::from("0")),
+        (String::from("HOME"), String::from("/home/goldy")),
+    ];
+
+    let mut overrides = env_overrides_from_vars("cat-app", vars);
+    assert_eq!(overrides.len(), 2);
+
+    overrides.extend(cli_overrides(&[String::from("max_cats=30")])?);
+
+    let resolved = resolve_config::<Config>(TEMPLATE, Some("max_cats = 3\n[misc]\nname = \"Meow\""), overrides)?;
+
+    assert_eq!(resolved.config.max_cats, 30);
+    assert!(resolved.config.misc.enable_custom_folder);
+    assert_eq!(resolved.config.misc.name, "Meow");
+
+    assert_eq!(resolved.source_of("version"), Some(ConfigLayer::Template));
+    assert_eq!(resolved.source_of("misc.name"), Some(ConfigLayer::UserFile));
+    assert_eq!(resolved.source_of("misc.enable_custom_folder"), Some(ConfigLayer::Environment));
+    assert_eq!(resolved.source_of("max_cats"), Some(ConfigLayer::CommandLine));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_cli_override() {
+    assert!(matches!(
+        cli_overrides(&[String::from("max_cats")]),
+        Err(Error::InvalidConfigOverride { .. })
+    ));
+
+    let overrides = cli_overrides(&[String::from("misc.name=~/cats")]).unwrap();
+    assert_eq!(overrides[0].value.as_str(), Some("~/cats"));
+}
+
+#[test]
+fn test_mask_overrides() -> Result<(), Error> {
+    let disk_string = "max_cats = 3\n\n[[plugins]]\nname = \"purr\"\nenabled = true\n\n[[plugins]]\nname = \"hiss\"\nenabled = true\n";
+    let disk_document = disk_string.parse::<DocumentMut>().unwrap();
+
+    let overrides = cli_overrides(&[
+        String::from("plugins[0].enabled=false"),
+        String::from("plugins[1].enabled=false"),
+        String::from("max_cats=30"),
+        String::from("misc.name=Meow"),
+    ])?;
+
+    let mut updated_document = disk_document.clone();
+    apply_overrides(&mut updated_document, &overrides)?;
+
+    // changed in the app after being overridden, so it's no longer the override's value
+    updated_document["plugins"].as_array_of_tables_mut().unwrap()
+        .get_mut(1).unwrap()["enabled"] = toml_edit::value(true);
+
+    mask_overrides(&mut updated_document, &disk_document, &overrides);
+
+    assert_eq!(updated_document.to_string(), disk_string);
+
+    Ok(())
+}
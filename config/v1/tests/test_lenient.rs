@@ -0,0 +1,154 @@
+use cirrus_config_v1::{config::{CConfig, lenient::{ConfigDiagnostic, ConfigDiagnosticReason, parse_config_lenient}}, error::Error};
+use serde::{Deserialize, Serialize};
+
+const TEMPLATE: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = 15
+
+# How fast the cats run.
+speed = 1.5
+
+[misc]
+# The cat's name.
+name = "Goldy"
+theme = "dark"
+"#;
+
+#[derive(Debug, Default, Hash, PartialEq, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: u8,
+    #[serde(with = "speed")]
+    speed: u32,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, Hash, PartialEq, Serialize, Deserialize)]
+struct Misc {
+    name: String,
+    theme: Theme,
+}
+
+#[derive(Debug, Default, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+// floats don't implement Hash
+mod speed {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(speed: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(*speed as f32 / 10.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+        Ok((f32::deserialize(deserializer)? * 10.0) as u32)
+    }
+}
+
+impl CConfig for Config {}
+
+#[test]
+fn test_valid_config() -> Result<(), Error> {
+    let (config, diagnostics) = parse_config_lenient::<Config>(
+        "version = 1\nmax_cats = 3\nspeed = 2\n[misc]\nname = \"Meow\"\ntheme = \"light\"",
+        TEMPLATE
+    )?;
+
+    assert!(diagnostics.is_empty());
+    assert_eq!(config.max_cats, 3);
+    assert_eq!(config.speed, 20);
+    assert_eq!(config.misc.theme, Theme::Light);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_and_missing_keys() -> Result<(), Error> {
+    let config_toml_string = r#"version = 1
+max_cats = 9000 # too many for a u8
+speed = "fast"
+
+[misc]
+theme = "rainbow"
+"#;
+
+    let (config, diagnostics) = parse_config_lenient::<Config>(config_toml_string, TEMPLATE)?;
+
+    assert_eq!(
+        config,
+        Config {
+            version: 1,
+            max_cats: 15,
+            speed: 15,
+            misc: Misc { name: String::from("Goldy"), theme: Theme::Dark }
+        }
+    );
+
+    assert_eq!(diagnostics.len(), 4);
+
+    assert_eq!(
+        diagnostics[0],
+        ConfigDiagnostic {
+            key_path: String::from("speed"),
+            reason: ConfigDiagnosticReason::InvalidType { expected: "float".into(), found: "string".into() },
+            offending_value: Some(String::from("\"fast\"")),
+        }
+    );
+
+    assert_eq!(
+        diagnostics[1],
+        ConfigDiagnostic {
+            key_path: String::from("misc.name"),
+            reason: ConfigDiagnosticReason::MissingKey,
+            offending_value: None,
+        }
+    );
+
+    let invalid_value_key_paths: Vec<(&str, Option<&str>)> = diagnostics[2..].iter()
+        .map(|diagnostic| {
+            assert!(matches!(diagnostic.reason, ConfigDiagnosticReason::InvalidValue { .. }));
+
+            (diagnostic.key_path.as_str(), diagnostic.offending_value.as_deref())
+        })
+        .collect();
+
+    assert_eq!(
+        invalid_value_key_paths,
+        vec![("max_cats", Some("9000")), ("misc.theme", Some("\"rainbow\""))]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unparsable_config() -> Result<(), Error> {
+    let (config, diagnostics) = parse_config_lenient::<Config>("max_cats = = 3", TEMPLATE)?;
+
+    assert_eq!(config.max_cats, 15);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].reason, ConfigDiagnosticReason::ParseFailure { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_constraint_violation() -> Result<(), Error> {
+    let (config, diagnostics) = parse_config_lenient::<Config>(
+        "version = 1\nmax_cats = 51\nspeed = 2\n[misc]\nname = \"Meow\"\ntheme = \"light\"",
+        &TEMPLATE.replace("max_cats = 15", "# @range 1..=50\nmax_cats = 15")
+    )?;
+
+    assert_eq!(config.max_cats, 15);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key_path, "max_cats");
+    assert_eq!(diagnostics[0].offending_value, Some(String::from("51")));
+    assert!(matches!(diagnostics[0].reason, ConfigDiagnosticReason::ConstraintViolation { .. }));
+
+    Ok(())
+}
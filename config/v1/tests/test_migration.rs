@@ -0,0 +1,112 @@
+use std::fs;
+
+use cirrus_config_v1::{config::{migrate_config_file, migration::{MigrationStep, Migrations}}, error::Error};
+use toml_edit::{DocumentMut, Value};
+
+const OLD_CONFIG: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = "15" # don't go too high
+
+# Whether kitties are allowed to nap.
+nap = true
+
+[misc]
+# Path to the custom folder.
+custom_folder = "~/cats"
+legacy_renderer = false
+
+[key_binds]
+# Spawns a cat.
+spawn = "ctrl+c"
+"#;
+
+fn migrations() -> Migrations {
+    Migrations::new()
+        .add(3, vec![
+            MigrationStep::move_table("key_binds", "misc.key_binds"),
+        ])
+        .add(2, vec![
+            MigrationStep::rename_key("misc.custom_folder", "misc.custom_folder_path"),
+            MigrationStep::rename_key("nap", "behaviour.allow_naps"),
+            MigrationStep::change_type(
+                "max_cats",
+                |value| value.as_str()?.parse::<i64>().ok().map(Value::from)
+            ),
+            MigrationStep::drop_key("misc.legacy_renderer"),
+        ])
+}
+
+#[test]
+fn test_migrations() -> Result<(), Error> {
+    let mut document: DocumentMut = OLD_CONFIG.parse().unwrap();
+
+    let migrations = migrations();
+
+    assert_eq!(migrations.latest_version(), Some(3));
+    assert_eq!(migrations.migrate(&mut document)?, Some(1));
+
+    assert_eq!(document["version"].as_integer(), Some(3));
+    assert_eq!(document["max_cats"].as_integer(), Some(15));
+    assert_eq!(document["behaviour"]["allow_naps"].as_bool(), Some(true));
+    assert_eq!(document["misc"]["custom_folder_path"].as_str(), Some("~/cats"));
+    assert_eq!(document["misc"]["key_binds"]["spawn"].as_str(), Some("ctrl+c"));
+
+    assert!(document["misc"].get("custom_folder").is_none());
+    assert!(document["misc"].get("legacy_renderer").is_none());
+    assert!(document.get("key_binds").is_none());
+
+    let migrated_config = document.to_string();
+
+    // comments must survive the migration
+    assert!(migrated_config.contains("# Maximum amount of 🐈 cats.\nmax_cats = 15 # don't go too high"));
+    assert!(migrated_config.contains("# Path to the custom folder.\ncustom_folder_path = \"~/cats\""));
+    assert!(migrated_config.contains("# Whether kitties are allowed to nap.\nallow_naps = true"));
+    assert!(migrated_config.contains("# Spawns a cat.\nspawn = \"ctrl+c\""));
+
+    // nothing left to do on an already migrated config
+    assert_eq!(migrations.migrate(&mut document)?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_type_change() {
+    let mut document: DocumentMut = OLD_CONFIG.parse().unwrap();
+
+    let migrations = Migrations::new()
+        .add(2, vec![
+            MigrationStep::change_type("nap", |value| value.as_str().map(Value::from))
+        ]);
+
+    assert!(
+        matches!(
+            migrations.migrate(&mut document),
+            Err(Error::ConfigMigrationFailure { version: 2, .. })
+        )
+    );
+}
+
+#[test]
+fn test_migrate_config_file() -> Result<(), Error> {
+    let config_dir_path = std::env::temp_dir().join("cirrus_config_test_migrate_config_file");
+
+    fs::create_dir_all(&config_dir_path).unwrap();
+
+    let config_path = config_dir_path.join("config.toml");
+    let backup_path = config_dir_path.join("config.toml.v1.bak");
+
+    fs::write(&config_path, OLD_CONFIG).unwrap();
+    let _ = fs::remove_file(&backup_path);
+
+    assert!(migrate_config_file(&config_path, &migrations())?);
+
+    assert_eq!(fs::read_to_string(&backup_path).unwrap(), OLD_CONFIG);
+    assert!(fs::read_to_string(&config_path).unwrap().starts_with("version = 3\n"));
+
+    assert!(!migrate_config_file(&config_path, &migrations())?);
+
+    fs::remove_dir_all(&config_dir_path).unwrap();
+
+    Ok(())
+}
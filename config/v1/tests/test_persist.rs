@@ -0,0 +1,40 @@
+use std::fs;
+
+use cirrus_config_v1::{config::persist::{list_config_backups, restore_config_backup, write_config_file}, error::Error};
+
+#[test]
+fn test_rolling_backups_and_restore() -> Result<(), Error> {
+    let config_dir_path = std::env::temp_dir().join("cirrus_config_test_persist");
+    let _ = fs::remove_dir_all(&config_dir_path);
+    fs::create_dir_all(&config_dir_path).unwrap();
+
+    let config_path = config_dir_path.join("config.toml");
+
+    for max_cats in 1..=5 {
+        write_config_file(&config_path, &format!("max_cats = {max_cats}\n"), 3)?;
+    }
+
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), "max_cats = 5\n");
+    assert!(!config_dir_path.join("config.toml.tmp").exists());
+
+    let backups = list_config_backups(&config_path)?;
+
+    assert_eq!(backups.iter().map(|backup| backup.index).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(fs::read_to_string(&backups[0].path).unwrap(), "max_cats = 4\n");
+    assert_eq!(fs::read_to_string(&backups[2].path).unwrap(), "max_cats = 2\n");
+
+    restore_config_backup(&config_path, 3, 3)?;
+
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), "max_cats = 2\n");
+    // the config we restored over is now the latest backup
+    assert_eq!(fs::read_to_string(config_dir_path.join("config.toml.bak.1")).unwrap(), "max_cats = 5\n");
+
+    assert!(matches!(
+        restore_config_backup(&config_path, 9, 3),
+        Err(Error::FailedToRestoreConfigBackup { index: 9, .. })
+    ));
+
+    fs::remove_dir_all(&config_dir_path).unwrap();
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::fs;
+
+use cirrus_config_v1::{config::profile::{edit_profile_document, overlay_profile, ConfigProfiles}, error::Error};
+use toml_edit::DocumentMut;
+
+const BASE_CONFIG: &str = r#"max_cats = 15
+
+[misc]
+name = "Goldy"
+theme = "dark"
+"#;
+
+#[test]
+fn test_manage_profiles() -> Result<(), Error> {
+    let config_dir_path = std::env::temp_dir().join("cirrus_config_test_profile");
+    let _ = fs::remove_dir_all(&config_dir_path);
+    fs::create_dir_all(&config_dir_path).unwrap();
+
+    let profiles = ConfigProfiles::new(&config_dir_path.join("config.toml"));
+
+    assert!(profiles.list()?.is_empty());
+    assert_eq!(profiles.active()?, None);
+
+    let presentation_path = profiles.create("presentation")?;
+    assert_eq!(presentation_path, config_dir_path.join("profiles").join("presentation.toml"));
+
+    fs::write(&presentation_path, "max_cats = 2\n").unwrap();
+
+    profiles.duplicate("presentation", "low-power")?;
+    assert_eq!(profiles.read("low-power")?, "max_cats = 2\n");
+
+    assert_eq!(profiles.list()?, vec!["low-power", "presentation"]);
+
+    assert!(matches!(profiles.create("presentation"), Err(Error::ProfileAlreadyExists { .. })));
+    assert!(matches!(profiles.create("../meow"), Err(Error::InvalidProfileName { .. })));
+    assert!(matches!(profiles.switch(Some("gaming")), Err(Error::ProfileNotFound { .. })));
+
+    profiles.switch(Some("low-power"))?;
+    assert_eq!(profiles.active()?.as_deref(), Some("low-power"));
+
+    // deleting the active profile switches back to the base config
+    profiles.delete("low-power")?;
+    assert_eq!(profiles.active()?, None);
+    assert_eq!(profiles.list()?, vec!["presentation"]);
+
+    fs::remove_dir_all(&config_dir_path).unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn test_overlay_profile() -> Result<(), Error> {
+    let config = overlay_profile(BASE_CONFIG, "[misc]\ntheme = \"light\"\n")?;
+
+    assert!(config.contains("max_cats = 15"));
+    assert!(config.contains(r#"name = "Goldy""#));
+    assert!(config.contains(r#"theme = "light""#));
+
+    assert_eq!(overlay_profile(BASE_CONFIG, "")?, BASE_CONFIG);
+
+    Ok(())
+}
+
+#[test]
+fn test_edit_profile_document() {
+    let base_document: DocumentMut = BASE_CONFIG.parse().unwrap();
+    let mut profile_document: DocumentMut = "# fewer cats for slides\nmax_cats = 2\n".parse().unwrap();
+
+    let config_document: DocumentMut = r#"max_cats = 4
+
+[misc]
+name = "Goldy"
+theme = "light"
+"#.parse().unwrap();
+
+    edit_profile_document(&mut profile_document, &base_document, &config_document);
+
+    let profile = profile_document.to_string();
+
+    assert!(profile.contains("# fewer cats for slides\nmax_cats = 4"));
+    assert!(profile.contains(r#"theme = "light""#));
+    // unchanged from the base config so it's left out of the profile
+    assert!(!profile.contains("name"));
+}
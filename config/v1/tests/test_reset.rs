@@ -0,0 +1,107 @@
+use cirrus_config_v1::{config::reset::{diff_against_template, reset_config_keys}, error::Error};
+use toml_edit::DocumentMut;
+
+const TEMPLATE: &str = r#"version = 1
+
+# Maximum amount of 🐈 cats.
+max_cats = 15
+
+[misc]
+# The cat's name.
+name = "Goldy"
+theme = "dark"
+
+[[plugins]]
+name = "purr"
+"#;
+
+const USER_CONFIG: &str = r#"version = 1
+
+# I like a lot of cats
+max_cats = 200 # yes, 200
+
+[misc]
+name = "Whiskers" # my cat
+theme = "light"
+custom = true
+
+[[plugins]]
+name = "hiss"
+
+[[plugins]]
+name = "meow"
+"#;
+
+fn reset(key_path: Option<&str>) -> Result<String, Error> {
+    let mut document: DocumentMut = USER_CONFIG.parse().unwrap();
+
+    reset_config_keys(&mut document, TEMPLATE, key_path)?;
+
+    Ok(document.to_string())
+}
+
+#[test]
+fn test_reset_key_keeps_comments() -> Result<(), Error> {
+    let config = reset(Some("max_cats"))?;
+
+    assert!(config.contains("# I like a lot of cats\nmax_cats = 15 # yes, 200"));
+    assert!(config.contains(r#"name = "Whiskers" # my cat"#));
+
+    Ok(())
+}
+
+#[test]
+fn test_reset_table_and_whole_config() -> Result<(), Error> {
+    let config = reset(Some("misc"))?;
+
+    assert!(config.contains("max_cats = 200"));
+    assert!(config.contains(r#"name = "Goldy" # my cat"#));
+    assert!(config.contains(r#"theme = "dark""#));
+    // keys that aren't in the template are left alone
+    assert!(config.contains("custom = true"));
+
+    let config = reset(None)?;
+
+    assert!(config.contains("max_cats = 15 # yes, 200"));
+    assert!(config.contains(r#"theme = "dark""#));
+    assert_eq!(config.matches("[[plugins]]").count(), 1);
+    assert!(config.contains(r#"name = "purr""#));
+
+    assert!(matches!(reset(Some("misc.colour")), Err(Error::UnknownConfigKey { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn test_reset_inserts_missing_keys() -> Result<(), Error> {
+    let mut document: DocumentMut = "version = 1\n\n[misc]\nname = \"Whiskers\"\n".parse().unwrap();
+
+    reset_config_keys(&mut document, TEMPLATE, Some("misc"))?;
+
+    let config = document.to_string();
+
+    assert!(config.contains("[misc]\nname = \"Goldy\"\ntheme = \"dark\""));
+    assert!(!config.contains("max_cats"));
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_against_template() -> Result<(), Error> {
+    let document: DocumentMut = USER_CONFIG.parse().unwrap();
+
+    let diffs = diff_against_template(&document, TEMPLATE)?;
+
+    let key_paths: Vec<&str> = diffs.iter().map(|diff| diff.key_path.as_str()).collect();
+
+    assert_eq!(key_paths, vec!["max_cats", "misc.name", "misc.theme", "plugins", "misc.custom"]);
+
+    assert_eq!(diffs[0].default_value.as_ref().and_then(|value| value.as_integer()), Some(15));
+    assert_eq!(diffs[0].current_value.as_ref().and_then(|value| value.as_integer()), Some(200));
+    assert!(diffs[4].default_value.is_none());
+
+    let template_document: DocumentMut = TEMPLATE.parse().unwrap();
+    assert!(diff_against_template(&template_document, TEMPLATE)?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+
+use cirrus_config_v1::{config::{history::ConfigKeyChange, subscription::ConfigSubscriptions, CConfig}, error::Error};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Config {
+    version: i8,
+    max_cats: i64,
+    misc: Misc,
+}
+
+#[derive(Debug, Default, Hash, Serialize, Deserialize)]
+struct Misc {
+    theme: String,
+    allow_naps: bool,
+}
+
+impl CConfig for Config {}
+
+fn key_paths(changes: &[ConfigKeyChange]) -> Vec<&str> {
+    changes.iter().map(|change| change.key_path.as_str()).collect()
+}
+
+#[test]
+fn test_subscriptions() -> Result<(), Error> {
+    let mut config = Config::default();
+    let mut subscriptions = ConfigSubscriptions::new();
+
+    let misc_changes = Arc::new(Mutex::new(Vec::new()));
+    let all_changes = Arc::new(Mutex::new(Vec::new()));
+
+    let misc_id = subscriptions.subscribe("misc", {
+        let misc_changes = misc_changes.clone();
+        move |change| misc_changes.lock().unwrap().push(change.clone())
+    });
+
+    subscriptions.subscribe("", {
+        let all_changes = all_changes.clone();
+        move |change| all_changes.lock().unwrap().push(change.clone())
+    });
+
+    let (_, max_cats_receiver) = subscriptions.subscribe_channel("max_cats");
+
+    // the first check only takes a snapshot
+    assert!(subscriptions.check(&config)?.is_empty());
+
+    config.max_cats = 15;
+    config.misc.theme = "light".to_string();
+
+    assert_eq!(key_paths(&subscriptions.check(&config)?), vec!["max_cats", "misc.theme"]);
+
+    // nothing changed since
+    assert!(subscriptions.check(&config)?.is_empty());
+
+    let misc_change = misc_changes.lock().unwrap()[0].clone();
+
+    assert_eq!(misc_changes.lock().unwrap().len(), 1);
+    assert_eq!(misc_change.key_path, "misc.theme");
+    assert_eq!(misc_change.before.and_then(|value| value.as_str().map(str::to_string)).as_deref(), Some(""));
+    assert_eq!(misc_change.after.and_then(|value| value.as_str().map(str::to_string)).as_deref(), Some("light"));
+
+    assert_eq!(key_paths(&all_changes.lock().unwrap()), vec!["max_cats", "misc.theme"]);
+
+    let max_cats_change = max_cats_receiver.try_recv().unwrap();
+
+    assert_eq!(max_cats_change.after.and_then(|value| value.as_integer()), Some(15));
+    assert!(max_cats_receiver.try_recv().is_err());
+
+    assert!(subscriptions.unsubscribe(misc_id));
+    assert!(!subscriptions.unsubscribe(misc_id));
+
+    drop(max_cats_receiver);
+
+    config.max_cats = 16;
+    config.misc.allow_naps = true;
+    subscriptions.check(&config)?;
+
+    assert_eq!(misc_changes.lock().unwrap().len(), 1);
+    assert_eq!(all_changes.lock().unwrap().len(), 4);
+
+    Ok(())
+}
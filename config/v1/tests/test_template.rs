@@ -1,4 +1,5 @@
-use cirrus_config_v1::{error::Error, template::Template};
+use cirrus_config_v1::{error::Error, template::{KeyDeprecation, KeyRange, Template}};
+use toml_edit::DocumentMut;
 
 #[test]
 fn test_valid_template() -> Result<(), Error> {
@@ -11,7 +12,10 @@ fn test_valid_template() -> Result<(), Error> {
     let meow_key = &template.keys["max_meows"];
 
     assert_eq!(meow_key.defined_toml_value.as_integer(), Some(15));
-    assert_eq!(meow_key.docstring.description.short, None); // TODO: change to some once brief descriptions are implemented
+    assert_eq!(
+        meow_key.docstring.description.short,
+        Some(String::from("Adjust this value to limit the maximum amount of 🐈 cats that are allowed to spawn in."))
+    );
 
     println!("--> {:?}", meow_key.docstring.description.long.as_ref().unwrap());
 
@@ -27,11 +31,135 @@ fn test_valid_template() -> Result<(), Error> {
     let allow_sentient_ai_key = &template.keys["allow_sentient_ai"];
 
     assert_eq!(allow_sentient_ai_key.defined_toml_value.as_bool(), Some(true));
-    assert_eq!(allow_sentient_ai_key.docstring.description.short, None); // TODO: change to some once brief descriptions are implemented
+    assert_eq!(
+        allow_sentient_ai_key.docstring.description.short,
+        Some(String::from("If you enable this, the AI will eventually kill us all."))
+    );
     assert_eq!(
         allow_sentient_ai_key.docstring.description.long,
         Some(String::from("If you enable this, the AI will eventually kill us all."))
     );
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_docstring_annotations() -> Result<(), Error> {
+    let template_string = r#"# @brief How the cats look.
+# The theme the 🐈 cats are rendered with.
+#
+# @choices ["dark", "light"]
+# @example "dark"
+# @since 0.3.0
+theme = "dark"
+
+# Whether cats may use the old renderer.
+# @deprecated Use 'theme' instead.
+legacy_renderer = false
+
+# @deprecated
+old_key = 1
+"#;
+
+    let mut template = Template::new(template_string);
+
+    template.parse_keys()?;
+
+    let theme_docstring = &template.keys["theme"].docstring;
+
+    assert_eq!(theme_docstring.description.short, Some(String::from("How the cats look.")));
+    assert_eq!(
+        theme_docstring.description.long,
+        Some(String::from("The theme the 🐈 cats are rendered with."))
+    );
+    assert_eq!(theme_docstring.allowed_values, Some(vec![String::from("dark"), String::from("light")]));
+    assert_eq!(theme_docstring.examples, vec![String::from("\"dark\"")]);
+    assert_eq!(theme_docstring.since, Some(String::from("0.3.0")));
+    assert_eq!(theme_docstring.deprecated, None);
+
+    let legacy_renderer_docstring = &template.keys["legacy_renderer"].docstring;
+
+    assert_eq!(
+        legacy_renderer_docstring.deprecated,
+        Some(KeyDeprecation { note: Some(String::from("Use 'theme' instead.")) })
+    );
+    assert_eq!(
+        legacy_renderer_docstring.description.long,
+        Some(String::from("Whether cats may use the old renderer."))
+    );
+
+    let old_key_docstring = &template.keys["old_key"].docstring;
+
+    assert_eq!(old_key_docstring.deprecated, Some(KeyDeprecation { note: None }));
+    assert_eq!(old_key_docstring.description.short, None);
+    assert_eq!(old_key_docstring.description.long, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_constraints() -> Result<(), Error> {
+    let template_string = r#"# Maximum amount of 🐈 cats.
+# @range 1..=100
+max_cats = 15
+
+# @range 0.5..
+speed = 1.5
+
+# @range ..10
+lives = 9
+
+# @choices ["dark", "light"]
+theme = "dark"
+"#;
+
+    let mut template = Template::new(template_string);
+
+    template.parse_keys()?;
+
+    assert_eq!(template.keys["max_cats"].constraints.range, Some(KeyRange::Int(1..=100)));
+    assert_eq!(template.keys["speed"].constraints.range, Some(KeyRange::Float(0.5..=f64::INFINITY)));
+    assert_eq!(template.keys["lives"].constraints.range, Some(KeyRange::Int(i64::MIN..=9)));
+    assert_eq!(template.keys["theme"].docstring.allowed_values, Some(vec![String::from("dark"), String::from("light")]));
+
+    // annotations should not leak into the description
+    assert_eq!(
+        template.keys["max_cats"].docstring.description.long,
+        Some(String::from("Maximum amount of 🐈 cats."))
+    );
+
+    let config_document: DocumentMut = "max_cats = 200\nspeed = 2\nlives = 9\ntheme = \"rainbow\"".parse().unwrap();
+
+    let violated_key_paths: Vec<String> = template.check_constraints(&config_document)
+        .into_iter()
+        .map(|violation| violation.key_path)
+        .collect();
+
+    assert_eq!(violated_key_paths, vec![String::from("max_cats"), String::from("theme")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_array_of_tables_key_paths() -> Result<(), Error> {
+    let template_string = r#"[[plugins]]
+# The plugin's name.
+name = "purr"
+
+[[plugins]]
+name = "hiss"
+"#;
+
+    let mut template = Template::new(template_string);
+
+    template.parse_keys()?;
+
+    assert_eq!(template.ordered_paths, vec![String::from("plugins[0].name"), String::from("plugins[1].name")]);
+    assert_eq!(template.keys["plugins[1].name"].defined_toml_value.as_str(), Some("hiss"));
+    assert_eq!(
+        template.keys["plugins[0].name"].docstring.description.short,
+        Some(String::from("The plugin's name."))
+    );
+
+    Ok(())
+}
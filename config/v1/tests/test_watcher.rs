@@ -0,0 +1,42 @@
+use std::{fs, thread, time::{Duration, Instant}};
+
+use cirrus_config_v1::{config::watcher::ConfigWatcher, error::Error};
+
+fn wait_for_change(watcher: &ConfigWatcher) -> bool {
+    let started = Instant::now();
+
+    while started.elapsed() < Duration::from_secs(10) {
+        if watcher.poll_changed() {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    false
+}
+
+#[test]
+fn test_watcher_notices_config_changes() -> Result<(), Error> {
+    let config_dir_path = std::env::temp_dir().join("cirrus_config_test_watcher");
+    fs::create_dir_all(&config_dir_path).unwrap();
+
+    let config_path = config_dir_path.join("config.toml");
+    fs::write(&config_path, "max_cats = 15\n").unwrap();
+
+    let watcher = ConfigWatcher::new(&config_path)?;
+
+    assert!(!watcher.poll_changed());
+
+    // other files in the config directory should be ignored
+    fs::write(config_dir_path.join("theme.toml"), "accent_colour = \"#fff\"\n").unwrap();
+    thread::sleep(Duration::from_millis(200));
+    assert!(!watcher.poll_changed());
+
+    fs::write(&config_path, "max_cats = 3\n").unwrap();
+    assert!(wait_for_change(&watcher));
+
+    fs::remove_dir_all(&config_dir_path).unwrap();
+
+    Ok(())
+}
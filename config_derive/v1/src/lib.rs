@@ -0,0 +1,290 @@
+//! The `#[derive(ConfigKeys)]` macro of `cirrus_config`, use it through `cirrus_config::ConfigKeys`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Expr, Fields, LitStr, Path, Token};
+
+/// Generates typed key paths for a config struct, named the same way serde serializes them.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, Hash, Default, ConfigKeys)]
+/// pub struct Config {
+///     pub version: i8,
+///     pub misc: Misc,
+/// }
+///
+/// #[derive(Serialize, Deserialize, Hash, Default, ConfigKeys)]
+/// #[serde(rename_all = "kebab-case")]
+/// pub struct Misc {
+///     pub enable_custom_folder: bool,
+/// }
+///
+/// let key_path = Config::key_paths().misc().enable_custom_folder(); // ConfigKeyPath<bool>
+///
+/// assert_eq!(key_path.as_str(), "misc.enable-custom-folder");
+/// ```
+///
+/// On enums it makes the enum usable as a value in a config struct.
+///
+/// Use `#[config_keys(crate = path::to::cirrus_config)]` if `cirrus_config` isn't available as `::cirrus_config`.
+#[proc_macro_derive(ConfigKeys, attributes(config_keys))]
+pub fn derive_config_keys(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_config_keys(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_config_keys(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let crate_path = parse_crate_path(&input)?;
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(
+                syn::Error::new_spanned(name, "ConfigKeys can only be derived for structs with named fields!")
+            ),
+        },
+        // enums are values in the config, they don't have keys of their own
+        Data::Enum(_) => return Ok(
+            quote! {
+                impl #impl_generics #crate_path::config::key_path::ConfigKeys for #name #type_generics #where_clause {
+                    type KeyPaths = #crate_path::config::key_path::ConfigKeyPath<Self>;
+
+                    fn key_paths_at(key_path: String) -> Self::KeyPaths {
+                        #crate_path::config::key_path::ConfigKeyPath::new(key_path)
+                    }
+                }
+            }
+        ),
+        Data::Union(_) => return Err(
+            syn::Error::new_spanned(name, "ConfigKeys can't be derived for unions!")
+        ),
+    };
+
+    let rename_all = parse_container_rename_all(&input)?;
+
+    let key_paths_name = format_ident!("{}KeyPaths", name);
+    let visibility = &input.vis;
+
+    let mut key_path_methods = Vec::new();
+    let mut where_predicates = Vec::new();
+
+    for field in fields {
+        let serde_field = parse_serde_field(&field.attrs)?;
+
+        if serde_field.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named fields always have an ident");
+        let ty = &field.ty;
+
+        let key = match serde_field.rename {
+            Some(rename) => rename,
+            None => apply_rename_rule(rename_all.as_deref(), &ident.unraw().to_string()),
+        };
+
+        let key_path = match serde_field.flatten {
+            // a flattened struct's keys sit right next to ours
+            true => quote! { self.key_path.clone() },
+            false => quote! {
+                match self.key_path.is_empty() {
+                    true => #key.to_string(),
+                    false => format!("{}.{}", self.key_path, #key),
+                }
+            },
+        };
+
+        let doc = format!("Key path of `{}`.", key);
+
+        key_path_methods.push(
+            quote! {
+                #[doc = #doc]
+                #visibility fn #ident(&self) -> <#ty as #crate_path::config::key_path::ConfigKeys>::KeyPaths {
+                    <#ty as #crate_path::config::key_path::ConfigKeys>::key_paths_at(#key_path)
+                }
+            }
+        );
+
+        where_predicates.push(quote! { #ty: #crate_path::config::key_path::ConfigKeys });
+    }
+
+    let existing_predicates = where_clause.map(|where_clause| {
+        let predicates = &where_clause.predicates;
+        quote! { #predicates, }
+    });
+
+    let key_paths_doc = format!("Typed key paths of [`{}`], see `ConfigKeys`.", name);
+
+    Ok(
+        quote! {
+            #[doc = #key_paths_doc]
+            #[derive(Clone)]
+            #visibility struct #key_paths_name #impl_generics #where_clause {
+                key_path: String,
+                _config: ::std::marker::PhantomData<fn() -> #name #type_generics>,
+            }
+
+            impl #impl_generics #key_paths_name #type_generics where #existing_predicates #(#where_predicates,)* {
+                #(#key_path_methods)*
+            }
+
+            impl #impl_generics AsRef<str> for #key_paths_name #type_generics #where_clause {
+                /// Key path of the table itself (empty at the root).
+                fn as_ref(&self) -> &str {
+                    &self.key_path
+                }
+            }
+
+            impl #impl_generics #crate_path::config::key_path::ConfigKeys for #name #type_generics #where_clause {
+                type KeyPaths = #key_paths_name #type_generics;
+
+                fn key_paths_at(key_path: String) -> Self::KeyPaths {
+                    #key_paths_name { key_path, _config: ::std::marker::PhantomData }
+                }
+            }
+
+            impl #impl_generics #name #type_generics #where_clause {
+                /// Typed key paths to every key of the config.
+                #visibility fn key_paths() -> #key_paths_name #type_generics {
+                    <Self as #crate_path::config::key_path::ConfigKeys>::key_paths_at(String::new())
+                }
+            }
+        }
+    )
+}
+
+#[derive(Default)]
+struct SerdeField {
+    rename: Option<String>,
+    skip: bool,
+    flatten: bool,
+}
+
+fn parse_crate_path(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut crate_path = quote! { ::cirrus_config };
+
+    for attribute in input.attrs.iter().filter(|attribute| attribute.path().is_ident("config_keys")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let path: Path = meta.value()?.parse()?;
+                crate_path = quote! { #path };
+
+                return Ok(());
+            }
+
+            Err(meta.error("unknown config_keys attribute, expected 'crate'"))
+        })?;
+    }
+
+    Ok(crate_path)
+}
+
+/// The serialized name rule of `#[serde(rename_all = "...")]`.
+fn parse_container_rename_all(input: &DeriveInput) -> syn::Result<Option<String>> {
+    let mut rename_all = None;
+
+    for attribute in input.attrs.iter().filter(|attribute| attribute.path().is_ident("serde")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                rename_all = parse_serialize_name(&meta)?;
+                return Ok(());
+            }
+
+            skip_meta(meta)
+        })?;
+    }
+
+    Ok(rename_all)
+}
+
+fn parse_serde_field(attributes: &[syn::Attribute]) -> syn::Result<SerdeField> {
+    let mut serde_field = SerdeField::default();
+
+    for attribute in attributes.iter().filter(|attribute| attribute.path().is_ident("serde")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                serde_field.rename = parse_serialize_name(&meta)?;
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                serde_field.skip = true;
+            } else if meta.path.is_ident("flatten") {
+                serde_field.flatten = true;
+            } else {
+                return skip_meta(meta);
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(serde_field)
+}
+
+/// Reads `rename = "name"` or `rename(serialize = "name", deserialize = "...")`, the serialized name is what's in the config.
+fn parse_serialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<String>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+    }
+
+    let mut name = None;
+
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            name = Some(meta.value()?.parse::<LitStr>()?.value());
+            return Ok(());
+        }
+
+        skip_meta(meta)
+    })?;
+
+    Ok(name)
+}
+
+/// Consumes a serde attribute we don't care about (e.g: `default`, `with = "..."`, `bound(...)`).
+fn skip_meta(meta: ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(skip_meta)?;
+    }
+
+    Ok(())
+}
+
+/// Same as serde's `rename_all` rules for fields, which are expected to be written in snake case.
+fn apply_rename_rule(rule: Option<&str>, field: &str) -> String {
+    let pascal_case = || field.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first_char) => first_char.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+
+    match rule {
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => field.to_ascii_uppercase(),
+        Some("PascalCase") => pascal_case(),
+        Some("camelCase") => {
+            let pascal_case = pascal_case();
+            let mut chars = pascal_case.chars();
+
+            match chars.next() {
+                Some(first_char) => first_char.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        },
+        Some("kebab-case") => field.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => field.to_ascii_uppercase().replace('_', "-"),
+        // "lowercase" and "snake_case" leave snake case fields as they are
+        _ => field.to_string(),
+    }
+}
@@ -8,32 +8,31 @@ use crate::error::{Error, Result};
 
 pub mod error;
 
+#[derive(Default)]
 pub enum Preference {
+    #[default]
     None,
     Terminal,
 }
 
-impl Default for Preference {
-    fn default() -> Self { Self::None }
-}
-
 // NOTE: maybe expand this in the future
 
+/// Opens the file in the editor set in the cloudy-org global config (with the `global-config` feature),
+/// falling back to the `VISUAL` or `EDITOR` environment variables then the platform's default editor.
 pub fn open_editor(file_path: &Path, pref: Preference) -> Result<ExitStatus> {
-    let editor_env_result = env::var("VISUAL")
-        .or_else(|_| env::var("EDITOR"));
+    let preferred_editor = global_config_editor()
+        .or_else(|| env::var("VISUAL").ok())
+        .or_else(|| env::var("EDITOR").ok());
 
     let status_error_map = |error: io::Error| {
         Error::EditorCouldNotOpenFailure { error: error.to_string() }
     };
 
-    if let Ok(editor) = editor_env_result {
-        return Ok(
-            Command::new(editor)
-                .arg(file_path)
-                .status()
-                .map_err(status_error_map)?
-        );
+    if let Some(editor) = preferred_editor {
+        return Command::new(editor)
+            .arg(file_path)
+            .status()
+            .map_err(status_error_map);
     }
 
     // TODO: windows and macos needs testing..
@@ -75,4 +74,15 @@ pub fn open_editor(file_path: &Path, pref: Preference) -> Result<ExitStatus> {
             ),
         }
     }
+}
+
+#[cfg(feature = "global-config")]
+fn global_config_editor() -> Option<String> {
+    cirrus_config::global::get_global_config().ok()
+        .and_then(|global_config| global_config.editor)
+}
+
+#[cfg(not(feature = "global-config"))]
+fn global_config_editor() -> Option<String> {
+    None
 }
\ No newline at end of file
@@ -1,45 +1,235 @@
-use std::{fs, hash::{DefaultHasher, Hasher}, path::PathBuf, time::Duration};
+use std::{collections::HashMap, fs, hash::{DefaultHasher, Hasher}, path::PathBuf, sync::mpsc::Receiver, time::Duration};
 
-use cirrus_config::{config::{get_and_create_config_file, CConfig}, error::Error as ConfigError};
+use cirrus_config::{config::{edit::{edit_config_document, insert_missing_keys}, get_and_create_config_file_layered_with_format, get_and_create_config_file_lenient_with_format, get_and_create_config_file_with_format, get_and_create_config_file_with_migrations, parse_config, format::{ConfigFormat, TomlFormat}, history::{ConfigHistory, ConfigKeyChange}, subscription::{ConfigSubscriptions, SubscriptionId}, layered::{mask_overrides, resolve_config, ConfigLayer, ConfigOverride}, lenient::{parse_config_lenient, ConfigDiagnostic}, migration::Migrations, reset::{diff_against_template, reset_config_keys, ConfigKeyDiff}, profile::{edit_profile_document, overlay_profile, ConfigProfiles}, bundle::{export_config_bundle_with_format, import_config_bundle_with_format, preview_config_bundle_with_format, BundleExportOptions, ConfigBundle, ConfigBundleDiff}, persist::{list_config_backups, restore_config_backup, write_config_file, ConfigBackup, DEFAULT_BACKUP_COUNT}, watcher::ConfigWatcher, CConfig}, error::Error as ConfigError};
 use cirrus_error::error::CError;
 use egui::Context;
 use egui_notify::ToastLevel;
-use log::debug;
-use toml_edit::{Document, DocumentMut, Formatted, Item, Table, Value};
+use log::{debug, warn};
+use toml_edit::DocumentMut;
 
 use crate::{error::Error, notifier::{Notifier, toast::ToastText}, scheduler::Scheduler};
 
+/// Things that happened to the config file outside of the app, see [`ConfigManager::take_events`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigEvent {
+    /// The config file (or the active profile) was edited on disk and the config in memory was replaced with it.
+    ReloadedFromDisk,
+    /// The config file (or the active profile) was edited on disk while the config in memory also
+    /// had unsaved changes. The changes on disk win and the unsaved changes in memory are lost.
+    Conflict,
+    /// The config file (or the active profile) was edited on disk but could not be read or deserialized
+    /// so the config in memory was kept. It won't be autosaved over the broken file.
+    ReloadFailed { error: String },
+}
+
+/// How the config was loaded, so it gets reloaded from disk the same way.
+enum LoadMode {
+    Strict { template_config_toml_string: String },
+    Lenient { template_config_toml_string: String },
+    Layered { template_config_toml_string: String, overrides: Vec<ConfigOverride> },
+}
+
+/// The profile the config is loaded from and saved to, see [`ConfigManager::switch_profile`].
+struct ActiveProfile {
+    name: String,
+    path: PathBuf,
+    disk_string_copy: String,
+}
+
+impl LoadMode {
+    fn template_config_toml_string(&self) -> &str {
+        match self {
+            LoadMode::Strict { template_config_toml_string }
+                | LoadMode::Lenient { template_config_toml_string }
+                | LoadMode::Layered { template_config_toml_string, .. } => template_config_toml_string,
+        }
+    }
+}
+
 pub struct ConfigManager<T: CConfig> {
     pub config: T,
     last_config_hash: u64,
 
+    app_name: String,
     pub config_path: Option<PathBuf>,
+    /// How many rolling `config.toml.bak.N` backups are kept when saving.
+    pub backup_count: usize,
 
     config_disk_string_copy: Option<String>,
 
     config_autosave_schedule: Option<Scheduler>,
+
+    /// Problems found with the user's config when it was loaded leniently
+    /// ([`ConfigManager::new_lenient`]), these get shown as toasts on the next update.
+    pub diagnostics: Vec<ConfigDiagnostic>,
+
+    config_watcher: Option<ConfigWatcher>,
+    /// Watches the active profile's file, see [`ConfigManager::switch_profile`].
+    profile_watcher: Option<ConfigWatcher>,
+    events: Vec<ConfigEvent>,
+
+    load_mode: LoadMode,
+
+    /// The layer each config key's value came from when loaded with [`ConfigManager::new_layered`].
+    pub config_sources: HashMap<String, ConfigLayer>,
+
+    /// Changes made to the config in memory, see [`ConfigManager::undo`] and [`ConfigManager::redo`].
+    pub history: ConfigHistory,
+
+    /// Who gets told about changes to the config, see [`ConfigManager::subscribe`].
+    subscriptions: ConfigSubscriptions,
+
+    active_profile: Option<ActiveProfile>,
+
+    /// The format the config file is written in, see [`ConfigManager::new_with_format`].
+    format: Box<dyn ConfigFormat>,
 }
 
-impl<'a, T: CConfig> Default for ConfigManager<T> {
+impl<T: CConfig> Default for ConfigManager<T> {
     fn default() -> Self {
         Self {
             config: Default::default(),
             last_config_hash: 0, // hopefully this doesn't break any logic I'm about to write
+            app_name: String::new(),
             config_path: None,
+            backup_count: DEFAULT_BACKUP_COUNT,
             config_disk_string_copy: None,
-            config_autosave_schedule: None
+            config_autosave_schedule: None,
+            diagnostics: Vec::new(),
+            config_watcher: None,
+            profile_watcher: None,
+            events: Vec::new(),
+            load_mode: LoadMode::Strict { template_config_toml_string: String::new() },
+            config_sources: HashMap::new(),
+            history: ConfigHistory::default(),
+            subscriptions: ConfigSubscriptions::new(),
+            active_profile: None,
+            format: Box::new(TomlFormat),
         }
     }
 }
 
 impl<'a, T: CConfig> ConfigManager<T> {
     pub fn new(app_name: &str, template_config_toml_string: &'a str) -> Result<Self, Box<dyn CError>> {
-        let (config, path) = get_and_create_config_file::<T>(app_name, template_config_toml_string)?;
+        Self::new_with_migrations(app_name, template_config_toml_string, &Migrations::new())
+    }
 
-        let mut hasher = DefaultHasher::new();
-        config.hash(&mut hasher);
+    /// Upgrades the user's config with `migrations` before loading it. See [`Migrations`].
+    pub fn new_with_migrations(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations
+    ) -> Result<Self, Box<dyn CError>> {
+        let (config, path) = get_and_create_config_file_with_migrations::<T>(
+            app_name, template_config_toml_string, migrations
+        )?;
+
+        let mut config_manager = Self::from_config(app_name, config, path, Vec::new())?;
+        config_manager.load_mode = LoadMode::Strict {
+            template_config_toml_string: template_config_toml_string.to_string()
+        };
+
+        config_manager.load_active_profile()?;
+
+        Ok(config_manager)
+    }
+
+    /// Same as [`ConfigManager::new_with_migrations`] but the user's config file is written in `format` (e.g: `config.yaml`).
+    ///
+    /// Profiles stay in TOML and the config gets bundled as TOML, see [`ConfigFormat`] for what each format can keep on save.
+    pub fn new_with_format(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations,
+        format: impl ConfigFormat + 'static
+    ) -> Result<Self, Box<dyn CError>> {
+        let (config, path) = get_and_create_config_file_with_format::<T>(
+            app_name, template_config_toml_string, migrations, &format
+        )?;
+
+        let mut config_manager = Self::from_config(app_name, config, path, Vec::new())?;
+        config_manager.load_mode = LoadMode::Strict {
+            template_config_toml_string: template_config_toml_string.to_string()
+        };
+        config_manager.format = Box::new(format);
+
+        config_manager.load_active_profile()?;
+
+        Ok(config_manager)
+    }
+
+    /// Loads the user's config leniently, so invalid or missing keys fall back to the
+    /// template's values instead of failing. What fell back is kept in [`ConfigManager::diagnostics`].
+    pub fn new_lenient(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations
+    ) -> Result<Self, Box<dyn CError>> {
+        Self::new_lenient_with_format(app_name, template_config_toml_string, migrations, TomlFormat)
+    }
 
-        let config_hash = hasher.finish();
+    /// Same as [`ConfigManager::new_lenient`] but the user's config file is written in `format`.
+    pub fn new_lenient_with_format(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations,
+        format: impl ConfigFormat + 'static
+    ) -> Result<Self, Box<dyn CError>> {
+        let (config, path, diagnostics) = get_and_create_config_file_lenient_with_format::<T>(
+            app_name, template_config_toml_string, migrations, &format
+        )?;
+
+        let mut config_manager = Self::from_config(app_name, config, path, diagnostics)?;
+        config_manager.load_mode = LoadMode::Lenient {
+            template_config_toml_string: template_config_toml_string.to_string()
+        };
+        config_manager.format = Box::new(format);
+
+        config_manager.load_active_profile()?;
+
+        Ok(config_manager)
+    }
+
+    /// Resolves the config in layers: the template's defaults, the user's config file then the `overrides`
+    /// (see [`cirrus_config::config::layered::env_overrides`] and [`cirrus_config::config::layered::cli_overrides`]).
+    ///
+    /// The overrides only last for this run, they are never saved to the user's config file.
+    pub fn new_layered(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations,
+        overrides: Vec<ConfigOverride>
+    ) -> Result<Self, Box<dyn CError>> {
+        Self::new_layered_with_format(app_name, template_config_toml_string, migrations, overrides, TomlFormat)
+    }
+
+    /// Same as [`ConfigManager::new_layered`] but the user's config file is written in `format`.
+    pub fn new_layered_with_format(
+        app_name: &str,
+        template_config_toml_string: &'a str,
+        migrations: &Migrations,
+        overrides: Vec<ConfigOverride>,
+        format: impl ConfigFormat + 'static
+    ) -> Result<Self, Box<dyn CError>> {
+        let (resolved_config, path) = get_and_create_config_file_layered_with_format::<T>(
+            app_name, template_config_toml_string, migrations, overrides, &format
+        )?;
+
+        let mut config_manager = Self::from_config(app_name, resolved_config.config, path, Vec::new())?;
+        config_manager.config_sources = resolved_config.sources;
+        config_manager.load_mode = LoadMode::Layered {
+            template_config_toml_string: template_config_toml_string.to_string(),
+            overrides: resolved_config.overrides,
+        };
+        config_manager.format = Box::new(format);
+
+        config_manager.load_active_profile()?;
+
+        Ok(config_manager)
+    }
+
+    fn from_config(app_name: &str, config: T, path: PathBuf, diagnostics: Vec<ConfigDiagnostic>) -> Result<Self, Box<dyn CError>> {
+        let config_hash = Self::hash_config(&config);
 
         // Reading the config here really shouldn't fail as that would be caught by 
         // "get_and_create_config_file" but I guess just to be extra safe and panic-less 
@@ -47,18 +237,81 @@ impl<'a, T: CConfig> ConfigManager<T> {
         let copy_of_config_on_disk = fs::read_to_string(&path)
             .map_err(|error| ConfigError::FailedToReadConfig(error.to_string()))?;
 
+        // Hot reloading is a nicety so we don't want the app to fail to start without it.
+        let config_watcher = match ConfigWatcher::new(&path) {
+            Ok(config_watcher) => Some(config_watcher),
+            Err(error) => {
+                warn!("Config changes on disk will not be picked up! Error: {:?}", error);
+                None
+            },
+        };
+
         Ok(
             Self {
                 config,
                 last_config_hash: config_hash,
+                app_name: app_name.to_string(),
                 config_path: Some(path),
+                backup_count: DEFAULT_BACKUP_COUNT,
                 config_disk_string_copy: Some(copy_of_config_on_disk),
-                config_autosave_schedule: None
+                config_autosave_schedule: None,
+                diagnostics,
+                config_watcher,
+                profile_watcher: None,
+                events: Vec::new(),
+                load_mode: LoadMode::Strict { template_config_toml_string: String::new() },
+                config_sources: HashMap::new(),
+                history: ConfigHistory::default(),
+                subscriptions: ConfigSubscriptions::new(),
+                active_profile: None,
+                format: Box::new(TomlFormat),
             }
         )
     }
 
+    /// Returns (and clears) every [`ConfigEvent`] that has happened since the last call.
+    pub fn take_events(&mut self) -> Vec<ConfigEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     pub fn update(&mut self, ctx: &Context, notifier: &mut Notifier) {
+        if let Some(event) = self.reload_if_changed_on_disk() {
+            match &event {
+                ConfigEvent::ReloadedFromDisk => notifier.show_toast(
+                    "Config has been reloaded from disk!",
+                    ToastLevel::Info,
+                    |_| {}
+                ),
+                ConfigEvent::Conflict => notifier.show_toast(
+                    "Config was changed on disk while it had unsaved changes, the changes on disk were kept!",
+                    ToastLevel::Warning,
+                    |_| {}
+                ),
+                ConfigEvent::ReloadFailed { error } => notifier.show_toast(
+                    ToastText::Error(Error::ReloadConfigFailure { error: error.clone() }.into()),
+                    ToastLevel::Error,
+                    |_| {}
+                ),
+            }
+
+            self.events.push(event);
+        }
+
+        if let Err(error) = self.history.record(&self.config) {
+            warn!("Failed to record config change for undo! Error: {:?}", error);
+        }
+
+        // catches the edits made by the settings and anything else that mutated the config since the last frame
+        self.notify_subscribers();
+
+        for diagnostic in self.diagnostics.drain(..) {
+            notifier.show_toast(
+                ToastText::Error(diagnostic.into()),
+                ToastLevel::Warning,
+                |_| {}
+            );
+        }
+
         if self.config_autosave_schedule.is_none() {
             self.config_autosave_schedule = Some(Scheduler::new(|| {}, Duration::from_secs(10)));
         }
@@ -96,13 +349,128 @@ impl<'a, T: CConfig> ConfigManager<T> {
         ctx.request_repaint_after_secs(0.5);
     }
 
+    /// Checks the config watchers and if the config file (or the active profile) was edited outside
+    /// of the app, reads it back in so our next save doesn't overwrite the user's edits.
+    fn reload_if_changed_on_disk(&mut self) -> Option<ConfigEvent> {
+        let is_config_changed = self.config_watcher.as_ref()
+            .is_some_and(|config_watcher| config_watcher.poll_changed());
+
+        let is_profile_changed = self.profile_watcher.as_ref()
+            .is_some_and(|profile_watcher| profile_watcher.poll_changed());
+
+        if !is_config_changed && !is_profile_changed {
+            return None;
+        }
+
+        self.reload_from_disk()
+    }
+
+    /// Reads the config file (and the active profile) back into memory if either differs from what we last read or wrote.
+    fn reload_from_disk(&mut self) -> Option<ConfigEvent> {
+        let config_path = self.config_path.as_ref()?;
+
+        let config_toml_string = match fs::read_to_string(config_path) {
+            Ok(config_toml_string) => config_toml_string,
+            Err(error) => return Some(ConfigEvent::ReloadFailed { error: error.to_string() }),
+        };
+
+        let profile_toml_string = match &self.active_profile {
+            Some(active_profile) => match fs::read_to_string(&active_profile.path) {
+                Ok(profile_toml_string) => Some(profile_toml_string),
+                Err(error) => return Some(ConfigEvent::ReloadFailed { error: error.to_string() }),
+            },
+            None => None,
+        };
+
+        let is_profile_unchanged = match (&self.active_profile, &profile_toml_string) {
+            (Some(active_profile), Some(profile_toml_string)) => &active_profile.disk_string_copy == profile_toml_string,
+            _ => true,
+        };
+
+        // Our own saves trigger the watchers too.
+        if self.config_disk_string_copy.as_ref() == Some(&config_toml_string) && is_profile_unchanged {
+            return None;
+        }
+
+        debug!("Config was changed on disk, reloading it...");
+
+        let has_unsaved_changes = Self::hash_config(&self.config) != self.last_config_hash;
+
+        // Even if it fails to deserialize the disk copies must be kept up to date as that's what
+        // gets edited on save. If it's not valid toml the save will fail instead of overwriting it.
+        if let (Some(active_profile), Some(profile_toml_string)) = (&mut self.active_profile, profile_toml_string) {
+            active_profile.disk_string_copy = profile_toml_string;
+        }
+
+        let result = self.parse_config_string(&config_toml_string);
+
+        self.config_disk_string_copy = Some(config_toml_string);
+
+        match result {
+            Ok((config, diagnostics)) => {
+                self.last_config_hash = Self::hash_config(&config);
+                self.config = config;
+                self.diagnostics.extend(diagnostics);
+
+                self.resnapshot_history();
+                self.notify_subscribers();
+
+                match has_unsaved_changes {
+                    true => Some(ConfigEvent::Conflict),
+                    false => Some(ConfigEvent::ReloadedFromDisk),
+                }
+            },
+            Err(error) => Some(ConfigEvent::ReloadFailed { error }),
+        }
+    }
+
+    /// Deserializes the base config string (with the active profile overlaid) the same way the config was first loaded.
+    fn parse_config_string(&mut self, config_string: &str) -> Result<(T, Vec<ConfigDiagnostic>), String> {
+        let config_toml_string = self.format.to_toml_string(config_string)
+            .map_err(|error| error.to_string())?;
+
+        let config_toml_string = config_toml_string.as_ref();
+
+        let overlaid_config_toml_string;
+
+        let config_toml_string = match &self.active_profile {
+            Some(active_profile) => {
+                overlaid_config_toml_string = overlay_profile(config_toml_string, &active_profile.disk_string_copy)
+                    .map_err(|error| error.to_string())?;
+
+                &overlaid_config_toml_string
+            },
+            None => config_toml_string,
+        };
+
+        match &self.load_mode {
+            LoadMode::Strict { template_config_toml_string } => parse_config::<T>(
+                config_toml_string, template_config_toml_string
+            ).map(|config| (config, Vec::new()))
+                .map_err(|error| error.to_string()),
+            LoadMode::Lenient { template_config_toml_string } => parse_config_lenient::<T>(
+                config_toml_string, template_config_toml_string
+            ).map_err(|error| error.to_string()),
+            LoadMode::Layered { template_config_toml_string, overrides } => resolve_config::<T>(
+                template_config_toml_string, Some(config_toml_string), overrides.clone()
+            ).map(|resolved_config| {
+                self.config_sources = resolved_config.sources;
+                (resolved_config.config, Vec::new())
+            }).map_err(|error| error.to_string()),
+        }
+    }
+
+    fn hash_config(config: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        config.hash(&mut hasher);
+
+        hasher.finish()
+    }
+
     /// Only attempts to save config if there was a change.
     pub fn save_if_changed(&mut self) -> Result<bool, Error> {
         // We're using hashes to detect changes to the config struct.
-        let mut hasher = DefaultHasher::new();
-        self.config.hash(&mut hasher);
-
-        let current_config_hash = hasher.finish();
+        let current_config_hash = Self::hash_config(&self.config);
 
         match current_config_hash != self.last_config_hash {
             true => {
@@ -120,8 +488,6 @@ impl<'a, T: CConfig> ConfigManager<T> {
     pub fn save(&mut self) -> Result<(), Error> {
         debug!("Saving mutated config to disk...");
 
-        // TODO: now we need to make sure when a user updates the disk variant of 
-        // the config we have a watchdog that tells us so we can update our disk copy.
         if let (Some(ref mut config_disk_copy), Some(config_path)) = (
             &mut self.config_disk_string_copy, &self.config_path
         ) {
@@ -133,166 +499,427 @@ impl<'a, T: CConfig> ConfigManager<T> {
                 .expect("Failed to serialize config in memory for saving to disk!");
 
             debug!("Serializing config in memory into into toml document...");
-            let updated_config_document: Document<String> = updated_config_string.parse().unwrap();
+            let mut updated_config_document: DocumentMut = updated_config_string.parse().unwrap();
+
+            // This can fail if the config was loaded leniently from a file that isn't valid toml, in which
+            // case we'd rather not touch it than overwrite whatever the user was in the middle of writing.
+            let config_disk_toml_string = self.format.to_toml_string(config_disk_copy)
+                .map_err(|error| Error::SaveConfigFailure { error: error.to_string() })?;
+
+            if let Some(active_profile) = &mut self.active_profile {
+                return Self::save_to_profile(
+                    active_profile, &config_disk_toml_string, updated_config_document, &self.load_mode, self.backup_count
+                );
+            }
 
             debug!("Serializing copy of config in disk into into toml document...");
-            // Unwrapping for now because we already parsed and handled errors for this hence it shouldn't fail.
-            let mut config_to_write_to_disk_document = config_disk_copy.parse::<DocumentMut>().unwrap();
+            let mut config_to_write_to_disk_document = config_disk_toml_string.parse::<DocumentMut>()
+                .map_err(|error| Error::SaveConfigFailure { error: error.to_string() })?;
+
+            if let LoadMode::Layered { overrides, .. } = &self.load_mode {
+                mask_overrides(&mut updated_config_document, &config_to_write_to_disk_document, overrides);
+            }
 
             debug!("Walking and editing disk toml document...");
-            Self::walk_and_edit_toml_document(
-                &updated_config_document,
-                None,
-                &mut config_to_write_to_disk_document
-            );
+            edit_config_document(&mut config_to_write_to_disk_document, &updated_config_document);
+
+            // an older config file won't have keys that were added to the template since
+            insert_missing_keys(
+                &mut config_to_write_to_disk_document, &updated_config_document, self.load_mode.template_config_toml_string()
+            ).map_err(|error| Error::SaveConfigFailure { error: error.to_string() })?;
 
-            *config_disk_copy = config_to_write_to_disk_document.to_string();
+            let config_string_to_write = self.format.to_config_string(
+                &config_to_write_to_disk_document.to_string(), Some(config_disk_copy)
+            ).map_err(|error| Error::SaveConfigFailure { error: error.to_string() })?;
 
-            fs::write(config_path, config_disk_copy)
+            write_config_file(config_path, &config_string_to_write, self.backup_count)
                 .map_err(|error| Error::SaveConfigFailure { error: error.to_string() })?;
+
+            // only once it's on disk, otherwise the next save and the watcher would compare against what was never written
+            *config_disk_copy = config_string_to_write;
         }
 
         Ok(())
     }
 
-    fn walk_and_edit_toml_document(
-        table_to_walk: &Table,
-        key_path: Option<&String>,
-        document_to_edit: &mut DocumentMut
-    ) {
-        for (key, item) in table_to_walk.iter() {
-            let key_path = match key_path {
-                Some(path) => &format!("{}.{}", path, key),
-                None => &key.to_string(),
-            };
-
-            match item {
-                Item::None => return,
-                Item::Value(value) => {
-                    let previous_item_value = match Self::get_toml_item_by_path(&document_to_edit, key_path) {
-                        Some(previous_item) => previous_item.as_value().unwrap(),
-                        None => &Value::String(Formatted::new(String::default())),
-                    };
-
-                    let is_different = match (value, previous_item_value) {
-                        (Value::String(formatted_a), Value::String(formatted_b)) => formatted_a.value() != formatted_b.value(),
-                        (Value::Integer(formatted_a), Value::Integer(formatted_b)) => formatted_a.value() != formatted_b.value(),
-                        (Value::Float(formatted_a), Value::Float(formatted_b)) => formatted_a.value() != formatted_b.value(),
-                        (Value::Boolean(formatted_a), Value::Boolean(formatted_b)) => formatted_a.value() != formatted_b.value(),
-                        (Value::Datetime(formatted_a), Value::Datetime(formatted_b)) => formatted_a.value() != formatted_b.value(),
-                        _ => true
-                    };
-
-                    if is_different {
-                        // NOTE: very experimental, this will need a lot of testing
-                        Self::edit_toml_item_by_path(
-                            document_to_edit,
-                            key_path,
-                            Item::Value(value.clone())
-                        );
-                    }
-                },
-                Item::Table(table) => {
-                    Self::walk_and_edit_toml_document(table, Some(key_path), document_to_edit);
-                },
-                Item::ArrayOfTables(array_of_tables) => {
-                    // TODO: this needs testing!
+    /// Saves the changes to the active profile, only keys that differ from the base config end up in it.
+    fn save_to_profile(
+        active_profile: &mut ActiveProfile,
+        base_config_toml_string: &str,
+        mut updated_config_document: DocumentMut,
+        load_mode: &LoadMode,
+        backup_count: usize
+    ) -> Result<(), Error> {
+        debug!("Saving config to the '{}' profile...", active_profile.name);
 
-                    for child_table in array_of_tables {
-                        Self::walk_and_edit_toml_document(
-                            child_table, Some(key_path), document_to_edit
-                        );
-                    }
-                },
-            }
+        let save_error = |error: String| Error::SaveConfigFailure { error };
+
+        let base_document = base_config_toml_string.parse::<DocumentMut>()
+            .map_err(|error| save_error(error.to_string()))?;
+
+        let mut profile_document = active_profile.disk_string_copy.parse::<DocumentMut>()
+            .map_err(|error| save_error(error.to_string()))?;
+
+        if let LoadMode::Layered { overrides, .. } = load_mode {
+            let overlaid_document = overlay_profile(base_config_toml_string, &active_profile.disk_string_copy)
+                .map_err(|error| save_error(error.to_string()))?
+                .parse::<DocumentMut>()
+                .map_err(|error| save_error(error.to_string()))?;
+
+            mask_overrides(&mut updated_config_document, &overlaid_document, overrides);
+        }
+
+        edit_profile_document(&mut profile_document, &base_document, &updated_config_document);
+
+        let profile_string_to_write = profile_document.to_string();
+
+        write_config_file(&active_profile.path, &profile_string_to_write, backup_count)
+            .map_err(|error| save_error(error.to_string()))?;
+
+        active_profile.disk_string_copy = profile_string_to_write;
+
+        Ok(())
+    }
+
+    /// The name of the profile the config is loaded from and saved to, `None` if it's the base config.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_ref().map(|active_profile| active_profile.name.as_str())
+    }
+
+    /// Lists the names of the config's profiles (see [`ConfigProfiles`]).
+    pub fn list_profiles(&self) -> Result<Vec<String>, Error> {
+        match self.profiles() {
+            Some(profiles) => profiles.list().map_err(Self::profile_error),
+            None => Ok(Vec::new()),
         }
     }
 
-    fn get_toml_item_by_path(document: &'a DocumentMut, key_path: &String) -> Option<&'a Item> {
-        let mut item: &Item = document.as_item();
+    /// Creates an empty profile that starts off the same as the base config.
+    pub fn create_profile(&self, name: &str) -> Result<(), Error> {
+        if let Some(profiles) = self.profiles() {
+            profiles.create(name).map_err(Self::profile_error)?;
+        }
 
-        for part in key_path.split('.') {
-            if let Some(table_like) = item.as_table_like() {
-                item = table_like.get(part)?;
-            } else {
-                return None;
-            }
+        Ok(())
+    }
+
+    /// Creates the profile `new_name` as a copy of the profile `name`.
+    pub fn duplicate_profile(&mut self, name: &str, new_name: &str) -> Result<(), Error> {
+        // so unsaved changes to the profile being copied are copied too
+        self.save_if_changed()?;
+
+        if let Some(profiles) = self.profiles() {
+            profiles.duplicate(name, new_name).map_err(Self::profile_error)?;
         }
 
-        Some(item)
+        Ok(())
     }
 
-    // NOTE: very experimental, this will need a lot of testing
-    fn edit_toml_item_by_path(document: &mut DocumentMut, key_path: &str, item_value: Item) {
-        debug!("Editing toml key '{}' with value '{}'...", key_path, item_value);
+    /// Deletes the profile, switching back to the base config first if it's the active one.
+    pub fn delete_profile(&mut self, name: &str) -> Result<(), Error> {
+        if self.active_profile() == Some(name) {
+            self.switch_profile(None)?;
+        }
 
-        let mut item_to_edit = document.as_item_mut();
+        if let Some(profiles) = self.profiles() {
+            profiles.delete(name).map_err(Self::profile_error)?;
+        }
 
-        for part in key_path.split('.') {
-            match item_to_edit.as_table_like_mut() {
-                Some(table_like) => {
-                    match table_like.get_mut(part) {
-                        Some(new_item) => {
-                            item_to_edit = new_item
-                        },
-                        None => return
-                    }
-                },
-                None => return
+        Ok(())
+    }
+
+    /// Switches to the profile `name` (or the base config if `None`), saving any unsaved changes to the
+    /// current one first. The config is reloaded from the new profile and the undo history is cleared.
+    pub fn switch_profile(&mut self, name: Option<&str>) -> Result<(), Error> {
+        let Some(profiles) = self.profiles() else {
+            return Ok(());
+        };
+
+        self.save_if_changed()?;
+
+        profiles.switch(name).map_err(Self::profile_error)?;
+
+        self.load_active_profile()?;
+        self.resnapshot_history();
+
+        Ok(())
+    }
+
+    fn profiles(&self) -> Option<ConfigProfiles> {
+        self.config_path.as_ref().map(|config_path| ConfigProfiles::new(config_path))
+    }
+
+    fn profile_error(error: ConfigError) -> Error {
+        Error::ProfileFailure { error: error.to_string() }
+    }
+
+    /// Reads the active profile from disk and reloads the config with it overlaid on the base config.
+    fn load_active_profile(&mut self) -> Result<(), Error> {
+        let Some(profiles) = self.profiles() else {
+            return Ok(());
+        };
+
+        let previous_profile = self.active_profile.take();
+
+        if let Some(name) = profiles.active().map_err(Self::profile_error)? {
+            match profiles.read(&name) {
+                Ok(disk_string_copy) => self.active_profile = Some(
+                    ActiveProfile { path: profiles.profile_path(&name), name, disk_string_copy }
+                ),
+                Err(error) => warn!("Failed to read the active config profile, using the base config! Error: {}", error),
             }
         }
 
-        *item_to_edit = item_value;
-    }
-
-    // fn is_toml_value_different(value_a: &Value, value_b: &Value) -> bool {
-    //     match (value_a, value_b) {
-    //         (Value::String(formatted_a), Value::String(formatted_b)) => !(formatted_a == formatted_b),
-    //         (Value::Integer(formatted_a), Value::Integer(formatted_b)) => !(formatted_a == formatted_b),
-    //         (Value::Float(formatted_a), Value::Float(formatted_b)) => !(formatted_a == formatted_b),
-    //         (Value::Boolean(formatted_a), Value::Boolean(formatted_b)) => !(formatted_a == formatted_b),
-    //         (Value::Datetime(formatted_a), Value::Datetime(formatted_b)) => !(formatted_a == formatted_b),
-    //         (Value::Array(array), Value::Array(previous_array)) => {
-    //             let mut is_different = array.len() != previous_array.len();
-
-    //             if is_different == false {
-    //                 let mut iter_index = 0;
-
-    //                 is_different = array.iter()
-    //                     .all(|value| {
-    //                         let previous_value = previous_array.get(iter_index);
-
-    //                         iter_index += 1;
-
-    //                         match previous_value {
-    //                             Some(previous_value) => Self::is_toml_value_different(value, previous_value),
-    //                             None => true,
-    //                         }
-    //                     }
-    //                 );
-    //             }
-
-    //             is_different
-    //         },
-    //         (Value::InlineTable(inline_table), Value::InlineTable(previous_inline_table)) => {
-    //             let mut is_different = inline_table.len() != previous_inline_table.len();
-
-    //             if is_different == false {
-    //                 is_different = inline_table.iter()
-    //                     .all(|(key, value)| {
-    //                         let previous_value = previous_inline_table.get(key);
-
-    //                         match previous_value {
-    //                             Some(previous_value) => Self::is_toml_value_different(value, previous_value),
-    //                             None => true,
-    //                         }
-    //                     }
-    //                 );
-    //             }
-
-    //             is_different
-    //         },
-    //         _ => false,
-    //     }
-    // }
+        // edits to the profile by hand get reloaded just like the base config's
+        self.profile_watcher = self.active_profile.as_ref()
+            .and_then(|active_profile| match ConfigWatcher::new(&active_profile.path) {
+                Ok(profile_watcher) => Some(profile_watcher),
+                Err(error) => {
+                    warn!("Changes to the config profile on disk will not be picked up! Error: {:?}", error);
+                    None
+                },
+            });
+
+        let needs_reparse = previous_profile.is_some() || self.active_profile.is_some();
+
+        if let (true, Some(config_disk_copy)) = (needs_reparse, self.config_disk_string_copy.clone()) {
+            let (config, diagnostics) = self.parse_config_string(&config_disk_copy)
+                .map_err(|error| Error::ProfileFailure { error })?;
+
+            self.last_config_hash = Self::hash_config(&config);
+            self.config = config;
+            self.diagnostics.extend(diagnostics);
+        }
+
+        // on startup this takes the snapshot later changes are compared against
+        self.notify_subscribers();
+
+        Ok(())
+    }
+
+    /// Reverts the config in memory to before the last change. Returns `false` if there's nothing to undo.
+    pub fn undo(&mut self) -> Result<bool, Error> {
+        let undone = self.history.undo(&mut self.config)
+            .map_err(|error| Error::ConfigHistoryFailure { error: error.to_string() })?;
+
+        self.notify_subscribers();
+
+        Ok(undone)
+    }
+
+    /// Re-applies the last change undone with [`ConfigManager::undo`]. Returns `false` if there's nothing to redo.
+    pub fn redo(&mut self) -> Result<bool, Error> {
+        let redone = self.history.redo(&mut self.config)
+            .map_err(|error| Error::ConfigHistoryFailure { error: error.to_string() })?;
+
+        self.notify_subscribers();
+
+        Ok(redone)
+    }
+
+    /// Changes that didn't come from the user editing the config in the app (reloads from disk, imports,
+    /// restores, resets, overrides and profile switches) can't be undone, undoing them would revert them
+    /// in memory and the next autosave would write the old values back over the config file.
+    fn resnapshot_history(&mut self) {
+        if let Err(error) = self.history.resnapshot(&self.config) {
+            warn!("Failed to take a new snapshot of the config for undo, clearing the undo history! Error: {:?}", error);
+            self.history.clear();
+        }
+    }
+
+    /// Calls `callback` with the old and new value of the key at `key_path` whenever it changes, or of every
+    /// key inside of it if it's a table (e.g: `misc`). An empty key path subscribes to the whole config.
+    ///
+    /// Changes are picked up on settings edits, undo / redo, resets, hot reloads from disk, profile
+    /// switches and when overrides are applied (see [`ConfigManager::apply_overrides`]).
+    pub fn subscribe(
+        &mut self,
+        key_path: impl AsRef<str>,
+        callback: impl FnMut(&ConfigKeyChange) + Send + 'static
+    ) -> SubscriptionId {
+        self.subscriptions.subscribe(key_path, callback)
+    }
+
+    /// Same as [`ConfigManager::subscribe`] but the changes are sent to the returned channel,
+    /// handy for threads. The subscription is removed once the receiver is dropped.
+    pub fn subscribe_channel(&mut self, key_path: impl AsRef<str>) -> (SubscriptionId, Receiver<ConfigKeyChange>) {
+        self.subscriptions.subscribe_channel(key_path)
+    }
+
+    /// Returns `false` if there was no such subscription.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.subscriptions.unsubscribe(id)
+    }
+
+    fn notify_subscribers(&mut self) {
+        if let Err(error) = self.subscriptions.check(&self.config) {
+            warn!("Failed to notify subscribers of config changes! Error: {:?}", error);
+        }
+    }
+
+    /// Applies `overrides` on top of the config for this run only, they are never saved to the user's config file.
+    /// A config not loaded with [`ConfigManager::new_layered`] gets resolved in layers from now on.
+    pub fn apply_overrides(&mut self, overrides: Vec<ConfigOverride>) -> Result<(), Error> {
+        // the config gets re-resolved from disk below, so unsaved changes would be lost
+        self.save_if_changed()?;
+
+        let Some(config_disk_copy) = self.config_disk_string_copy.clone() else {
+            return Ok(());
+        };
+
+        let mut all_overrides = match &self.load_mode {
+            LoadMode::Layered { overrides, .. } => overrides.clone(),
+            _ => Vec::new(),
+        };
+
+        all_overrides.extend(overrides);
+
+        let template_config_toml_string = self.load_mode.template_config_toml_string().to_string();
+
+        let previous_load_mode = std::mem::replace(
+            &mut self.load_mode,
+            LoadMode::Layered { template_config_toml_string, overrides: all_overrides }
+        );
+
+        let (config, diagnostics) = match self.parse_config_string(&config_disk_copy) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                self.load_mode = previous_load_mode;
+                return Err(Error::ApplyConfigOverridesFailure { error });
+            },
+        };
+
+        self.last_config_hash = Self::hash_config(&config);
+        self.config = config;
+        self.diagnostics.extend(diagnostics);
+
+        self.resnapshot_history();
+        self.notify_subscribers();
+
+        Ok(())
+    }
+
+    /// Resets the config key at `key_path` (or every key of the table at `key_path`, e.g: `misc`) to
+    /// the template's value. The user's config file is edited in place so their comments are kept.
+    pub fn reset_key(&mut self, key_path: &str) -> Result<(), Error> {
+        self.reset(Some(key_path))
+    }
+
+    /// Resets every config key to the template's value, keeping the comments in the user's config file.
+    pub fn reset_all(&mut self) -> Result<(), Error> {
+        self.reset(None)
+    }
+
+    /// Lists every config key whose value in memory differs from the template's default.
+    pub fn changed_keys(&self) -> Result<Vec<ConfigKeyDiff>, Error> {
+        let config_document: DocumentMut = toml::to_string(&self.config)
+            .expect("Failed to serialize config in memory!")
+            .parse()
+            .unwrap();
+
+        diff_against_template(&config_document, self.load_mode.template_config_toml_string())
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })
+    }
+
+    fn reset(&mut self, key_path: Option<&str>) -> Result<(), Error> {
+        // the reset config gets read back in below, so unsaved changes to other keys would be lost
+        self.save_if_changed()?;
+
+        let (Some(config_disk_copy), Some(config_path)) = (&mut self.config_disk_string_copy, &self.config_path) else {
+            return Ok(());
+        };
+
+        // with a profile active the keys are reset in the profile (always toml), leaving the base config alone
+        let (string_to_reset, path_to_write, format): (_, _, &dyn ConfigFormat) = match &mut self.active_profile {
+            Some(active_profile) => (&mut active_profile.disk_string_copy, &active_profile.path, &TomlFormat),
+            None => (config_disk_copy, config_path, self.format.as_ref()),
+        };
+
+        let mut document = format.to_toml_string(string_to_reset)
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })?
+            .parse::<DocumentMut>()
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })?;
+
+        reset_config_keys(&mut document, self.load_mode.template_config_toml_string(), key_path)
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })?;
+
+        let reset_string = format.to_config_string(&document.to_string(), Some(string_to_reset))
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })?;
+
+        write_config_file(path_to_write, &reset_string, self.backup_count)
+            .map_err(|error| Error::ResetConfigFailure { error: error.to_string() })?;
+
+        *string_to_reset = reset_string;
+
+        let config_disk_copy = self.config_disk_string_copy.clone().unwrap_or_default();
+
+        let (config, diagnostics) = self.parse_config_string(&config_disk_copy)
+            .map_err(|error| Error::ResetConfigFailure { error })?;
+
+        self.last_config_hash = Self::hash_config(&config);
+        self.config = config;
+        self.diagnostics.extend(diagnostics);
+
+        self.resnapshot_history();
+        self.notify_subscribers();
+
+        Ok(())
+    }
+
+    /// Bundles the config (and whatever else `options` asks for) to be imported on another machine.
+    /// Unsaved changes are saved first. With a profile active, only the base config is bundled.
+    pub fn export_bundle(&mut self, options: &BundleExportOptions) -> Result<ConfigBundle, Error> {
+        self.save_if_changed()?;
+
+        export_config_bundle_with_format(&self.app_name, options, self.format.as_ref())
+            .map_err(|error| Error::ConfigBundleFailure { error: error.to_string() })
+    }
+
+    /// Validates the bundle against the template and returns what importing it would change.
+    pub fn preview_bundle_import(&self, bundle: &ConfigBundle) -> Result<ConfigBundleDiff, Error> {
+        preview_config_bundle_with_format::<T>(
+            bundle, &self.app_name, self.load_mode.template_config_toml_string(), self.format.as_ref()
+        )
+            .map_err(|error| Error::ConfigBundleFailure { error: error.to_string() })
+    }
+
+    /// Imports the bundle (see [`ConfigManager::preview_bundle_import`]) and loads it into
+    /// memory, throwing away any unsaved changes to the config in memory.
+    pub fn import_bundle(&mut self, bundle: &ConfigBundle) -> Result<(), Error> {
+        import_config_bundle_with_format::<T>(
+            bundle, &self.app_name, self.load_mode.template_config_toml_string(), self.format.as_ref()
+        )
+            .map_err(|error| Error::ConfigBundleFailure { error: error.to_string() })?;
+
+        match self.reload_from_disk() {
+            Some(ConfigEvent::ReloadFailed { error }) => Err(Error::ReloadConfigFailure { error }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Lists the rolling backups of the config file, most recent first.
+    pub fn list_backups(&self) -> Result<Vec<ConfigBackup>, Error> {
+        match &self.config_path {
+            Some(config_path) => list_config_backups(config_path)
+                .map_err(|error| Error::RestoreConfigBackupFailure { error: error.to_string() }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Restores the config file from one of its backups (see [`ConfigManager::list_backups`]) and
+    /// loads it into memory, throwing away any unsaved changes to the config in memory.
+    pub fn restore_backup(&mut self, backup: &ConfigBackup) -> Result<(), Error> {
+        let config_path = match &self.config_path {
+            Some(config_path) => config_path,
+            None => return Ok(()),
+        };
+
+        restore_config_backup(config_path, backup.index, self.backup_count)
+            .map_err(|error| Error::RestoreConfigBackupFailure { error: error.to_string() })?;
+
+        match self.reload_from_disk() {
+            Some(ConfigEvent::ReloadFailed { error }) => Err(Error::ReloadConfigFailure { error }),
+            _ => Ok(()),
+        }
+    }
 }
\ No newline at end of file
@@ -12,6 +12,13 @@ use cirrus_error::error::CError;
 #[derive(Debug)]
 pub enum Error {
     SaveConfigFailure{ error: String },
+    ReloadConfigFailure { error: String },
+    RestoreConfigBackupFailure { error: String },
+    ResetConfigFailure { error: String },
+    ConfigHistoryFailure { error: String },
+    ProfileFailure { error: String },
+    ConfigBundleFailure { error: String },
+    ApplyConfigOverridesFailure { error: String },
 
     UserConfigPathNotFound { error: String },
 }
@@ -24,6 +31,27 @@ impl Display for Error {
             Error::SaveConfigFailure { .. } => write!(
                 f, "Failed to save config toml file!"
             ),
+            Error::ReloadConfigFailure { .. } => write!(
+                f, "Failed to reload config toml file that was changed on disk!"
+            ),
+            Error::RestoreConfigBackupFailure { .. } => write!(
+                f, "Failed to restore config backup!"
+            ),
+            Error::ResetConfigFailure { .. } => write!(
+                f, "Failed to reset config to its defaults!"
+            ),
+            Error::ConfigHistoryFailure { .. } => write!(
+                f, "Failed to undo or redo config change!"
+            ),
+            Error::ProfileFailure { .. } => write!(
+                f, "Failed to load or manage config profile!"
+            ),
+            Error::ConfigBundleFailure { .. } => write!(
+                f, "Failed to export or import config bundle!"
+            ),
+            Error::ApplyConfigOverridesFailure { .. } => write!(
+                f, "Failed to apply config overrides!"
+            ),
             Error::UserConfigPathNotFound { .. } => write!(
                 f, "Failed to get user config path"
             ),
@@ -16,24 +16,19 @@ pub enum BannerDuration {
     LONG = 5,
 }
 
-impl Into<Duration> for BannerDuration {
-    fn into(self) -> Duration {
-        Duration::from_secs(self as u64)
+impl From<BannerDuration> for Duration {
+    fn from(banner_duration: BannerDuration) -> Self {
+        Duration::from_secs(banner_duration as u64)
     }
-} 
+}
 
-#[derive(Hash, Clone)]
+#[derive(Hash, Clone, Default)]
 pub enum BannerPlacement {
     TOP,
+    #[default]
     BOTTOM
 }
 
-impl Default for BannerPlacement {
-    fn default() -> Self {
-        Self::BOTTOM
-    }
-}
-
 #[derive(Hash, Clone)]
 pub struct BannerText {
     pub heading: Option<String>,
@@ -44,7 +39,7 @@ impl BannerText {
     pub fn new(heading: String, subtext: Option<String>) -> Self {
         Self {
             heading: Some(heading),
-            subtext: subtext
+            subtext
         }
     }
 
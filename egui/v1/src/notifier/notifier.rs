@@ -3,7 +3,7 @@ use std::{sync::{Arc, RwLock}, time::Duration};
 use egui_notify::{Toast, ToastLevel, Toasts};
 use egui::{Align2, Color32, FontId, Id, Margin, Order, Pos2, RichText, Style, TextFormat, Ui, WidgetText, text::LayoutJob};
 
-use crate::{notifier::{banner::{Banner, BannerPlacement, BannerText}, toast::{ToastError, ToastText}}, scheduler::Scheduler};
+use crate::{notifier::{banner::{Banner, BannerPlacement, BannerText}, toast::{ToastError, ToastText}}, scheduler::Scheduler, styling::colours::SemanticColours};
 
 #[derive(Clone, Default)]
 pub struct Loading {
@@ -27,6 +27,13 @@ pub struct Notifier {
     banner: Option<Banner>,
     loading_lock: Arc<RwLock<Option<Loading>>>,
     style_ref: Option<Arc<Style>>,
+    semantic_colours: SemanticColours,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Notifier {
@@ -38,7 +45,8 @@ impl Notifier {
 
             banner: None,
             loading_lock: Arc::new(RwLock::new(None)),
-            style_ref: None
+            style_ref: None,
+            semantic_colours: SemanticColours::default(),
         }
     }
 
@@ -67,16 +75,14 @@ impl Notifier {
                     None => (Color32::WHITE, Color32::BLACK)
                 };
 
-                let mut normal_text_format = TextFormat::default();
-
-                normal_text_format.font_id = FontId::monospace(13.0);
-                normal_text_format.color = text_colour;
+                let normal_text_format = TextFormat {
+                    font_id: FontId::monospace(13.0),
+                    color: text_colour,
+                    ..Default::default()
+                };
 
                 job.append(
-                    &format!(
-                        "{}",
-                        textwrap::wrap(&message, 65).join("\n")
-                    ),
+                    &textwrap::wrap(&message, 65).join("\n"),
                     0.0, 
                     normal_text_format.clone()
                 );
@@ -84,11 +90,12 @@ impl Notifier {
                 if !self.config.hide_detailed_toast_errors {
                     job.append(" \n\nDetailed Error:\n\n", 0.0, normal_text_format);
 
-                    let mut code_block_format = TextFormat::default();
-
-                    code_block_format.font_id = FontId::monospace(13.0);
-                    code_block_format.background = code_block_background_colour;
-                    code_block_format.color = text_colour.blend(Color32::RED.gamma_multiply(0.3));
+                    let code_block_format = TextFormat {
+                        font_id: FontId::monospace(13.0),
+                        background: code_block_background_colour,
+                        color: text_colour.blend(self.semantic_colours.error.gamma_multiply(0.3)),
+                        ..Default::default()
+                    };
 
                     job.append(
                         &textwrap::wrap(&error, 65).join("\n"),
@@ -118,9 +125,18 @@ impl Notifier {
             },
         };
 
+        // same icons as egui-notify's levels but in the theme's colours
+        let themed_level = match &level {
+            ToastLevel::Info => ToastLevel::Custom("ℹ".to_string(), self.semantic_colours.info),
+            ToastLevel::Warning => ToastLevel::Custom("⚠".to_string(), self.semantic_colours.warning),
+            ToastLevel::Error => ToastLevel::Custom("！".to_string(), self.semantic_colours.error),
+            ToastLevel::Success => ToastLevel::Custom("✅".to_string(), self.semantic_colours.success),
+            level => level.clone(),
+        };
+
         let mut toast = Toast::custom(
             text, // textwrap::wrap(&text, 65).join("\n")
-            level.clone()
+            themed_level
         );
 
         if level == ToastLevel::Error {
@@ -149,13 +165,10 @@ impl Notifier {
     }
 
     pub fn set_loading(&self, message: Option<impl ToString>) {
-        let message = match message {
-            Some(message) => Some(message.to_string()),
-            None => None,
-        };
+        let message = message.map(|message| message.to_string());
 
         if let Some(msg) = &message {
-            log::info!("{}", msg.to_string());
+            log::info!("{}", msg);
         }
 
         if let Ok(mut loading) = self.loading_lock.write() {
@@ -172,6 +185,7 @@ impl Notifier {
     /// Renders toast notifications, overlayer banner and runs update loop for `Notifier.loading`.
     pub fn show(&mut self, ui: &Ui) {
         self.style_ref = Some(ui.style().clone());
+        self.semantic_colours = SemanticColours::get(ui.ctx());
 
         if let Ok(loading) = self.loading_lock.try_read() {
             self.loading = loading.clone();
@@ -191,14 +205,16 @@ impl Notifier {
                 1.0
             };
 
-            let subtext_colour = Color32::GRAY.gamma_multiply(alpha);
-            let background_colour = Color32::from_black_alpha((180.0 * alpha) as u8);
-            let heading_text_colour = Color32::from_white_alpha((255.0 * alpha) as u8);
+            let visuals = &ui.style().visuals;
+
+            let subtext_colour = visuals.weak_text_color().gamma_multiply(alpha);
+            let background_colour = visuals.window_fill.gamma_multiply(0.7 * alpha);
+            let heading_text_colour = visuals.strong_text_color().gamma_multiply(alpha);
 
             egui::Area::new(
                 Id::new("overlayer_banner_area")
                     .with(&banner.text)
-                    .with(&banner.duration)
+                    .with(banner.duration)
                     .with(&banner.placement)
             )
                 .fixed_pos(
@@ -73,7 +73,7 @@ impl<T> Scheduler<T> {
     /// This function only returns 'Some()' ONCE, and that is when the 
     /// callback is called and enough time has elapsed since the set duration / delay.
     pub fn update(&mut self) -> Option<T> {
-        if self.done == true {
+        if self.done {
             return None;
         }
 
@@ -0,0 +1,63 @@
+use cirrus_theming::{colour::Colour, fallbacks::ThemeFallbacks, palette::ColourPalette, theme::Theme};
+use egui::{Color32, Context, Id};
+
+/// The theme's semantic colours as egui colours.
+///
+/// [`Styling::apply`](crate::styling::Styling::apply) stores them in the egui context so widgets that don't
+/// have the theme at hand (e.g: the [`Notifier`](crate::notifier::Notifier)) can get them with [`SemanticColours::get`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SemanticColours {
+    pub success: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+    pub info: Color32,
+    pub selection: Color32,
+    pub link: Color32,
+}
+
+impl Default for SemanticColours {
+    /// The default dark theme's colours.
+    fn default() -> Self {
+        Self::from_palette(&Theme::default_dark(&ThemeFallbacks::default()).palette)
+    }
+}
+
+impl SemanticColours {
+    pub fn from_palette(palette: &ColourPalette) -> Self {
+        Self {
+            success: to_color32(palette.success),
+            warning: to_color32(palette.warning),
+            error: to_color32(palette.error),
+            info: to_color32(palette.info),
+            selection: to_color32(palette.selection),
+            link: to_color32(palette.link),
+        }
+    }
+
+    /// The colours of the theme last applied with `Styling::apply`, or the default
+    /// dark / light theme's if no theme has been applied yet.
+    pub fn get(ctx: &Context) -> Self {
+        if let Some(semantic_colours) = ctx.data(|data| data.get_temp::<Self>(Self::id())) {
+            return semantic_colours;
+        }
+
+        match ctx.style().visuals.dark_mode {
+            true => Self::default(),
+            false => Self::from_palette(&Theme::default_light().palette),
+        }
+    }
+
+    pub(crate) fn store(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Self::id(), self));
+    }
+
+    fn id() -> Id {
+        Id::new("cirrus_semantic_colours")
+    }
+}
+
+pub(crate) fn to_color32(colour: Colour) -> Color32 {
+    let [red, green, blue, alpha] = colour.to_rgba();
+
+    Color32::from_rgba_unmultiplied(red, green, blue, alpha)
+}
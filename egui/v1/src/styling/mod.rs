@@ -1,9 +1,13 @@
-use cirrus_theming::theme::Theme;
+use cirrus_theming::{colour_scheme::POLL_INTERVAL, manager::{ThemeEvent, ThemeManager}, theme::Theme};
 use egui::{Context, Style, TextStyle};
 
 pub mod fonts;
+pub mod motion;
+pub mod colours;
 pub(crate) mod visuals;
 
+use colours::SemanticColours;
+
 /// Cirrus handled styling for egui... so you don't have to do allat.
 pub struct Styling<'a> {
     theme: &'a Theme,
@@ -27,6 +31,7 @@ impl<'a> Styling<'a> {
     pub fn set_all(&mut self) -> &Self {
         self
             .set_visuals()
+            .set_motion(None)
             .set_fonts(None)
     }
 
@@ -38,5 +43,24 @@ impl<'a> Styling<'a> {
             }
         );
         ctx.set_style(self.egui_style.clone());
+
+        SemanticColours::from_palette(&self.theme.palette).store(ctx);
+    }
+
+    /// Styles egui again (with [`Styling::set_all`]) whenever the theme changes while the app is
+    /// running, e.g: an `"auto"` theme following the system switching to dark mode. Call it every frame.
+    pub fn apply_theme_changes(ctx: &Context, theme_manager: &mut ThemeManager) -> Option<ThemeEvent> {
+        if theme_manager.follows_system_colour_scheme() {
+            // the change is picked up in the background so make sure we get a frame to show it
+            ctx.request_repaint_after(POLL_INTERVAL);
+        }
+
+        let theme_event = theme_manager.update()?;
+
+        Styling::new(&theme_manager.theme)
+            .set_all()
+            .apply(ctx);
+
+        Some(theme_event)
     }
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use cirrus_config::global::get_global_config;
+
+use crate::styling::Styling;
+
+impl Styling<'_> {
+    /// Disables egui's animations if `reduced_motion` is true. If `None`, the
+    /// `reduced_motion` key of the cloudy-org global config is used.
+    pub fn set_motion(&mut self, reduced_motion: Option<bool>) -> &mut Self {
+        let reduced_motion = match reduced_motion {
+            Some(reduced_motion) => reduced_motion,
+            None => get_global_config()
+                .map(|global_config| global_config.reduced_motion)
+                .unwrap_or_default(),
+        };
+
+        if reduced_motion {
+            self.egui_style.animation_time = 0.0;
+        }
+
+        self
+    }
+}
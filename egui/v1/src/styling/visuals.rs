@@ -1,17 +1,17 @@
 use egui::{Color32, CornerRadius, Shadow, Stroke, Visuals, style::{HandleShape, Selection, Widgets}};
 
-use crate::styling::Styling;
+use crate::styling::{colours::{to_color32, SemanticColours}, Styling};
 
 impl Styling<'_> {
     pub fn set_visuals(&mut self) -> &mut Self {
         let is_dark = self.theme.palette.is_dark;
         let colour_palette = &self.theme.palette;
 
-        let primary_colour = Color32::from_hex(&colour_palette.primary.to_hex_string()).unwrap();
-        let interactive_colour = Color32::from_hex(&colour_palette.interactive.to_hex_string()).unwrap();
-        let surface_colour = Color32::from_hex(&colour_palette.surface.to_hex_string()).unwrap();
-        let text_colour = Color32::from_hex(&colour_palette.text.to_hex_string()).unwrap();
-        let accent_colour = Color32::from_hex(&colour_palette.accent.to_hex_string()).unwrap();
+        let primary_colour = to_color32(colour_palette.primary);
+        let interactive_colour = to_color32(colour_palette.interactive);
+        let surface_colour = to_color32(colour_palette.surface);
+        let text_colour = to_color32(colour_palette.text);
+        let semantic_colours = SemanticColours::from_palette(colour_palette);
 
         // Derive stroke colour from subtle text colour and put surface colour behind it.
         let stroke_colour = surface_colour.blend(
@@ -73,12 +73,12 @@ impl Styling<'_> {
         self.egui_style.visuals = Visuals { 
             dark_mode: is_dark,
             override_text_color: Some(text_colour),
-            widgets: widgets,
+            widgets,
             // Some widgets version of "visuals.widgets.active" is "visuals.selection" 
             // like with the custom toggle button in cirrus hence this change is so the 
             // stroke is more visible when the toggle button widget is active.
             selection: Selection {
-                bg_fill: accent_colour.blend(surface_colour.gamma_multiply(0.3)),
+                bg_fill: semantic_colours.selection,
                 stroke: Stroke { width: 2.0, color: stroke_colour },
             },
             window_shadow: Shadow::NONE,
@@ -91,7 +91,9 @@ impl Styling<'_> {
             extreme_bg_color: interactive_colour,
             text_edit_bg_color: Some(primary_colour),
             handle_shape: HandleShape::Circle,
-            hyperlink_color: accent_colour,
+            hyperlink_color: semantic_colours.link,
+            warn_fg_color: semantic_colours.warning,
+            error_fg_color: semantic_colours.error,
             ..default_visuals
         };
 
@@ -17,6 +17,12 @@ pub struct AboutApplicationInfo<'a> {
     pub copyright: String,
 }
 
+impl Default for About {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl About {
     pub fn new() -> Self {
         Self {
@@ -87,7 +93,7 @@ impl About {
             ui.add_space(10.0);
             ui.heading("Created with ❤ by:");
 
-            reusable_frame.clone()
+            reusable_frame
                 .outer_margin(Margin::symmetric(15, 5))
                 .show(ui, |ui| {
                     egui::Grid::new("about_authors_grid")
@@ -1,7 +1,7 @@
 use cirrus_config::template::TemplateKeys;
 use egui::{Color32, CornerRadius, Frame, Margin, RichText, Stroke, TextWrapMode, Ui, Vec2};
 
-use crate::widgets::{buttons::toggle_button::ToggleButton, settings::{any_section::input_widgets::InputWidgets, section::{Section, SectionDisplayInfo}}};
+use crate::{styling::colours::SemanticColours, widgets::{buttons::toggle_button::ToggleButton, settings::{any_section::input_widgets::InputWidgets, section::{Section, SectionDisplayInfo}}}};
 
 pub enum AnySection<'a> {
     String(Section<'a, String>),
@@ -91,6 +91,8 @@ impl AnySection<'_> {
             false => (24.0, Vec2::new(5.0, 2.8))
         };
 
+        self.apply_template_constraints(template_keys);
+
         ui.heading(RichText::new(self.get_title()).size(heading_size));
 
         ui.horizontal(|ui| {
@@ -153,11 +155,15 @@ impl AnySection<'_> {
                                     section.config_key_path
                                 )
                             );
+
+                            ui.label(
+                                RichText::new("⚠").color(SemanticColours::get(ui.ctx()).warning)
+                            );
                         },
                     }
                 },
                 AnySection::Bool(section) => {
-                    ToggleButton::new(&mut section.config_key_value)
+                    ToggleButton::new(section.config_key_value)
                         .size(widget_size)
                         .show(ui);
                 },
@@ -202,7 +208,7 @@ impl AnySection<'_> {
                         ui.vertical(|ui| {
                             let last_section_index = sections.len() - 1;
 
-                            for (index, any_section) in sections.into_iter().enumerate() {
+                            for (index, any_section) in sections.iter_mut().enumerate() {
                                 ui.spacing_mut().item_spacing.y = 0.0;
 
                                 let surface_colour = match (index % 2) == 0 {
@@ -259,18 +265,45 @@ impl AnySection<'_> {
 
             ui.separator();
 
-            ui.add(
+            let (short_description, hover_description) = self.get_description(template_keys);
+
+            let response = ui.add(
                 egui::Label::new(
-                    match self.get_description(template_keys) {
+                    match short_description {
                         Some(description) => description,
                         // TODO: something better than just no description text
                         None => String::from("No Description"),
                     }
                 ).wrap_mode(TextWrapMode::Wrap)
             );
+
+            if let Some(hover_description) = hover_description {
+                response.on_hover_text(hover_description);
+            }
         });
     }
 
+    fn apply_template_constraints(&mut self, template_keys: &TemplateKeys) {
+        macro_rules! apply {
+            ($section:expr) => {
+                if let Some(template_key) = template_keys.get(&$section.config_key_path) {
+                    $section.overrides.apply_template_constraints(&template_key.constraints);
+                }
+            };
+        }
+
+        match self {
+            AnySection::String(section) => apply!(section),
+            AnySection::OptionalString(section) => apply!(section),
+            AnySection::Bool(section) => apply!(section),
+            AnySection::IntTiny(section) => apply!(section),
+            AnySection::IntSmall(section) => apply!(section),
+            AnySection::FloatSmall(section) => apply!(section),
+            AnySection::IntBig(section) => apply!(section),
+            AnySection::ChildSections { .. } => {},
+        }
+    }
+
     fn get_title(&self) -> String {
         let config_key_path = match self {
             AnySection::String(section) => &section.config_key_path,
@@ -316,7 +349,9 @@ impl AnySection<'_> {
         }
     }
 
-    fn get_description<'a>(&'a self, template_keys: &TemplateKeys) -> Option<String> {
+    /// Returns the short description to show inline and the full description
+    /// (with any deprecation notice) to show on hover if there's more to it.
+    fn get_description(&self, template_keys: &TemplateKeys) -> (Option<String>, Option<String>) {
         let config_key_path = match self {
             AnySection::String(section) => &section.config_key_path,
             AnySection::OptionalString(section) => &section.config_key_path,
@@ -325,15 +360,36 @@ impl AnySection<'_> {
             AnySection::IntSmall(section) => &section.config_key_path,
             AnySection::FloatSmall(section) => &section.config_key_path,
             AnySection::IntBig(section) => &section.config_key_path,
-            AnySection::ChildSections { .. } => return None,
+            AnySection::ChildSections { .. } => return (None, None),
         };
 
         let display_info = self.get_display_info();
 
-        match template_keys.get(config_key_path) {
-            Some(template_key) => template_key.docstring.description.long.clone(),
-            None => display_info.description
+        let docstring = match template_keys.get(config_key_path) {
+            Some(template_key) => &template_key.docstring,
+            None => return (display_info.description, None),
+        };
+
+        let short = docstring.description.short.clone();
+
+        let mut hover = docstring.description.long.clone()
+            .filter(|long| Some(long) != short.as_ref());
+
+        if let Some(deprecation) = &docstring.deprecated {
+            let deprecation_text = match &deprecation.note {
+                Some(note) => format!("⚠ Deprecated: {}", note),
+                None => String::from("⚠ Deprecated"),
+            };
+
+            hover = Some(
+                match hover {
+                    Some(hover) => format!("{}\n\n{}", deprecation_text, hover),
+                    None => deprecation_text,
+                }
+            );
         }
+
+        (short, hover)
     }
 
     // TODO: return SectionDisplayInfo as a reference
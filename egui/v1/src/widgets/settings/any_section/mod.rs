@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod any_section;
 pub use any_section::*;
 
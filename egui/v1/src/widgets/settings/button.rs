@@ -4,6 +4,12 @@ pub struct SettingsButton {
     size: Vec2
 }
 
+impl Default for SettingsButton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SettingsButton {
     pub fn new() -> Self {
         Self {
@@ -1,5 +1,8 @@
 use std::{ops::RangeInclusive};
 
+use cirrus_config::{config::key_path::ConfigKeyPath, template::{KeyConstraints, KeyRange}};
+use toml_edit::Value;
+
 pub struct Section<'a, T> {
     pub(crate) config_key_path: String,
     pub(crate) config_key_value: &'a mut T,
@@ -38,4 +41,103 @@ impl<'a, T> Section<'a, T> {
             display_info,
         }
     }
-}
\ No newline at end of file
+
+    /// Same as [`Section::new`] but with a typed key path (see [`cirrus_config::ConfigKeys`]),
+    /// so the key path can't point to a key of a different type than the value.
+    pub fn from_key_path(
+        config_key_path: ConfigKeyPath<T>,
+        config_key_value: &'a mut T,
+        overrides: SectionOverrides<T>,
+        display_info: SectionDisplayInfo
+    ) -> Self {
+        Self::new(config_key_path.into(), config_key_value, overrides, display_info)
+    }
+}
+
+impl<T: FromTemplateValue> SectionOverrides<T> {
+    /// Fills in the choices and range declared in the template (`@choices` and `@range`)
+    /// unless the developer has already set them, which always takes priority.
+    pub(crate) fn apply_template_constraints(&mut self, constraints: &KeyConstraints) {
+        if self.choices.is_none() {
+            self.choices = constraints.choices.as_ref().and_then(
+                |choices| choices.iter()
+                    .map(T::from_template_value)
+                    .collect::<Option<Vec<T>>>()
+            );
+        }
+
+        if self.int_range.is_none() {
+            self.int_range = constraints.range.as_ref()
+                .and_then(T::from_template_range);
+        }
+    }
+}
+
+/// Converts the template's constraint values into a section's value type.
+pub trait FromTemplateValue: Sized {
+    fn from_template_value(value: &Value) -> Option<Self>;
+
+    fn from_template_range(_range: &KeyRange) -> Option<RangeInclusive<Self>> {
+        None
+    }
+}
+
+impl FromTemplateValue for String {
+    fn from_template_value(value: &Value) -> Option<Self> {
+        value.as_str().map(str::to_string)
+    }
+}
+
+impl FromTemplateValue for Option<String> {
+    fn from_template_value(value: &Value) -> Option<Self> {
+        value.as_str().map(|string| Some(string.to_string()))
+    }
+}
+
+impl FromTemplateValue for bool {
+    fn from_template_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+macro_rules! impl_from_template_value_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl FromTemplateValue for $int {
+                fn from_template_value(value: &Value) -> Option<Self> {
+                    value.as_integer()?.try_into().ok()
+                }
+
+                fn from_template_range(range: &KeyRange) -> Option<RangeInclusive<Self>> {
+                    match range {
+                        // clamp open ended ranges to what the type can hold
+                        KeyRange::Int(range) => Some(
+                            (*range.start()).clamp(<$int>::MIN as i64, <$int>::MAX as i64) as $int
+                                ..=(*range.end()).clamp(<$int>::MIN as i64, <$int>::MAX as i64) as $int
+                        ),
+                        KeyRange::Float(_) => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_template_value_for_int!(u8, i32, i64);
+
+impl FromTemplateValue for f32 {
+    fn from_template_value(value: &Value) -> Option<Self> {
+        value.as_float()
+            .or_else(|| value.as_integer().map(|integer| integer as f64))
+            .map(|float| float as f32)
+    }
+
+    fn from_template_range(range: &KeyRange) -> Option<RangeInclusive<Self>> {
+        let (start, end) = match range {
+            KeyRange::Int(range) => (*range.start() as f64, *range.end() as f64),
+            KeyRange::Float(range) => (*range.start(), *range.end()),
+        };
+
+        Some((start.max(f32::MIN as f64) as f32)..=(end.min(f32::MAX as f64) as f32))
+    }
+}
@@ -4,12 +4,18 @@ use cirrus_config::{config::CConfig, template::TemplateKeys};
 use egui_notify::ToastLevel;
 use egui::{Color32, Context, CornerRadius, Frame, Key, Margin, Modifiers, RichText, Stroke, Ui};
 
-use crate::{config_manager::ConfigManager, notifier::{Notifier, toast::ToastText}, widgets::settings::any_section::AnySection};
+use crate::{config_manager::ConfigManager, notifier::{Notifier, toast::ToastText}, styling::colours::to_color32, widgets::settings::any_section::AnySection};
 
 pub struct Settings<'a> {
     any_sections: Vec<AnySection<'a>>
 }
 
+impl Default for Settings<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// This widget is idempotent, meaning it can be initialized on every 
 /// Egui frame and still handle state persistently and avoid a full re-init.
 impl<'a> Settings<'a> {
@@ -26,12 +32,26 @@ impl<'a> Settings<'a> {
     }
 
     /// Handles settings panel open and closing input as well as saving config on exit all for us.
+    /// While the panel is open, Ctrl+Z and Ctrl+Shift+Z undo and redo changes to the config.
     pub fn handle_input<T: CConfig>(
         ctx: &Context,
         config_manager: &mut ConfigManager<T>,
         notifier: &mut Notifier,
         show_state: &mut bool
     ) {
+        // text fields have their own undo
+        if *show_state && !ctx.wants_keyboard_input() {
+            let history_result = match ctx.input_mut(|input| input.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z)) {
+                true => Some(config_manager.redo()),
+                false => ctx.input_mut(|input| input.consume_key(Modifiers::COMMAND, Key::Z))
+                    .then(|| config_manager.undo()),
+            };
+
+            if let Some(Err(error)) = history_result {
+                notifier.show_toast(ToastText::Error(error.into()), ToastLevel::Error, |_| {});
+            }
+        }
+
         let mut save_and_toast = |force_save: bool| {
             if force_save {
                 match config_manager.save() {
@@ -71,7 +91,7 @@ impl<'a> Settings<'a> {
         if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(Key::Comma)) {
             *show_state = !*show_state;
 
-            if *show_state == false {
+            if !*show_state {
                 save_and_toast(false);
             }
         }
@@ -81,8 +101,8 @@ impl<'a> Settings<'a> {
         ui.vertical_centered(|ui| {
             ui.set_max_width(ui.available_width().min(900.0));
 
-            let surface_colour = Color32::from_hex(&theme.palette.surface.to_hex_string()).unwrap();
-            let text_colour = Color32::from_hex(&theme.palette.text.to_hex_string()).unwrap();
+            let surface_colour = to_color32(theme.palette.surface);
+            let text_colour = to_color32(theme.palette.text);
 
             // blend surface colour with the background colour (which is the primary colour).
             let settings_grid_colour = surface_colour.gamma_multiply(0.2);
@@ -91,7 +111,7 @@ impl<'a> Settings<'a> {
 
             let settings_section_stroke = Stroke::new(1.0, text_colour.gamma_multiply(0.3));
 
-            let grid = Frame::group(&ui.style())
+            let grid = Frame::group(ui.style())
                 .corner_radius(CornerRadius {nw: 15, ne: 15, sw: 10, se: 10})
                 .outer_margin(Margin::same(7))
                 .stroke(Stroke::NONE)
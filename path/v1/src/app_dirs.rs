@@ -0,0 +1,280 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::{error::{Error, Result}, get_cloudy_home_path, get_user_cache_cloudy_folder_path, get_user_config_cloudy_folder_path, CLOUDY_FOLDER_NAME};
+
+/// The kinds of per-app directories [`AppDirs`] hands out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppDirKind {
+    /// Files the user may edit (e.g: `config.toml`).
+    Config,
+    /// Files the app owns and that should be kept (e.g: a database).
+    Data,
+    /// Files that should survive a restart but aren't worth backing up (e.g: history, window size).
+    State,
+    /// Files that can be deleted at any time.
+    Cache,
+    /// Log files, they live in the state directory.
+    Logs,
+    /// Sockets, pipes and lock files, removed on logout. Only the user can access it.
+    Runtime,
+}
+
+/// The directories an app gets to store its files in, one per [`AppDirKind`].
+///
+/// | Kind    | Linux                               | Other platforms                                    |
+/// |---------|-------------------------------------|----------------------------------------------------|
+/// | Config  | `$XDG_CONFIG_HOME/cloudy/{app}`     | local config folder `/Cloudy/{app}`                |
+/// | Data    | `$XDG_DATA_HOME/cloudy/{app}`       | local data folder `/Cloudy/{app}/data`             |
+/// | State   | `$XDG_STATE_HOME/cloudy/{app}`      | local data folder `/Cloudy/{app}/state`            |
+/// | Cache   | `$XDG_CACHE_HOME/cloudy/{app}`      | cache folder `/Cloudy/{app}` (`/cache` on Windows) |
+/// | Logs    | `$XDG_STATE_HOME/cloudy/{app}/logs` | state directory `/logs`                            |
+/// | Runtime | `$XDG_RUNTIME_DIR/cloudy/{app}`     | cache directory `/runtime`                         |
+///
+/// Off Linux the local data folder is the same folder as the local config folder (and so is the cache folder
+/// on Windows), hence the extra subfolders keeping each kind apart.
+///
+/// With `$CLOUDY_HOME` set (see [`get_cloudy_home_path`](crate::get_cloudy_home_path)) everything is
+/// redirected to `$CLOUDY_HOME/{config,data,state,cache,runtime}/{app}` instead.
+///
+/// ```ignore
+/// let app_dirs = AppDirs::new("imageviewer");
+///
+/// let history_path = app_dirs.state_dir()?.join("history.toml");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppDirs {
+    app_name: String,
+    root_path: Option<PathBuf>,
+}
+
+impl AppDirs {
+    pub fn new(app_name: impl Into<String>) -> Self {
+        Self { app_name: app_name.into(), root_path: None }
+    }
+
+    /// Same as [`AppDirs::new`] but every directory lives in `root_path` as if `$CLOUDY_HOME` was set to it,
+    /// without touching the environment of the whole process (e.g: for tests).
+    pub fn with_root(app_name: impl Into<String>, root_path: impl Into<PathBuf>) -> Self {
+        Self { app_name: app_name.into(), root_path: Some(root_path.into()) }
+    }
+
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Path of the directory without creating it, see [`AppDirs::create`].
+    pub fn path(&self, kind: AppDirKind) -> Result<PathBuf> {
+        log::trace!("Getting {:?} directory of '{}'...", kind, self.app_name);
+
+        let app_name = self.app_name.as_str();
+
+        if kind == AppDirKind::Logs {
+            return Ok(self.path(AppDirKind::State)?.join("logs"));
+        }
+
+        if let Some(root_path) = self.root_path.clone().or_else(get_cloudy_home_path) {
+            let folder_name = match kind {
+                AppDirKind::Config => "config",
+                AppDirKind::Data => "data",
+                AppDirKind::State | AppDirKind::Logs => "state",
+                AppDirKind::Cache => "cache",
+                AppDirKind::Runtime => "runtime",
+            };
+
+            return Ok(root_path.join(folder_name).join(app_name));
+        }
+
+        let path = match kind {
+            AppDirKind::Config => get_user_config_cloudy_folder_path()?.join(app_name),
+            AppDirKind::Cache => match cfg!(target_os = "windows") {
+                // the cache folder is the local config folder on windows
+                true => get_user_cache_cloudy_folder_path()?.join(app_name).join("cache"),
+                false => get_user_cache_cloudy_folder_path()?.join(app_name),
+            },
+            AppDirKind::Data => match cfg!(target_os = "linux") {
+                true => self.local_data_path()?,
+                // the local data folder is the local config folder off linux
+                false => self.local_data_path()?.join("data"),
+            },
+            // only linux has a state directory
+            AppDirKind::State => match dirs::state_dir() {
+                Some(state_dir) => state_dir.join(CLOUDY_FOLDER_NAME).join(app_name),
+                None => self.local_data_path()?.join("state"),
+            },
+            AppDirKind::Logs => unreachable!("logs live in the state directory"),
+            // $XDG_RUNTIME_DIR may not be set (e.g: outside of a login session)
+            AppDirKind::Runtime => runtime_path(dirs::runtime_dir(), app_name, &self.path(AppDirKind::Cache)?),
+        };
+
+        Ok(path)
+    }
+
+    fn local_data_path(&self) -> Result<PathBuf> {
+        Ok(
+            dirs::data_local_dir()
+                .ok_or(Error::PathNotFoundForPlatform)?
+                .join(CLOUDY_FOLDER_NAME)
+                .join(&self.app_name)
+        )
+    }
+
+    /// Path of the directory, creating it (and its parents) if it's missing.
+    ///
+    /// On unix the runtime directory is made accessible to the user only, as the XDG spec asks of it.
+    pub fn create(&self, kind: AppDirKind) -> Result<PathBuf> {
+        let path = self.path(kind)?;
+
+        if !path.exists() {
+            log::debug!("{:?} directory missing ({}), creating dir for '{}'...", kind, path.display(), self.app_name);
+
+            fs::create_dir_all(&path)
+                .map_err(|error| Error::FailedToCreateDirectory { path: path.clone(), error: error.to_string() })?;
+        }
+
+        #[cfg(unix)]
+        if kind == AppDirKind::Runtime {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o700))
+                .map_err(|error| Error::FailedToCreateDirectory { path: path.clone(), error: error.to_string() })?;
+        }
+
+        Ok(path)
+    }
+
+    /// Same as `create(AppDirKind::Config)`.
+    pub fn config_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::Config)
+    }
+
+    /// Same as `create(AppDirKind::Data)`.
+    pub fn data_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::Data)
+    }
+
+    /// Same as `create(AppDirKind::State)`.
+    pub fn state_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::State)
+    }
+
+    /// Same as `create(AppDirKind::Cache)`.
+    pub fn cache_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::Cache)
+    }
+
+    /// Same as `create(AppDirKind::Logs)`.
+    pub fn log_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::Logs)
+    }
+
+    /// Same as `create(AppDirKind::Runtime)`.
+    pub fn runtime_dir(&self) -> Result<PathBuf> {
+        self.create(AppDirKind::Runtime)
+    }
+}
+
+/// The app's folder in the user's runtime directory, or in its cache directory if there's no runtime directory.
+fn runtime_path(runtime_dir: Option<PathBuf>, app_name: &str, cache_path: &Path) -> PathBuf {
+    match runtime_dir {
+        Some(runtime_dir) => runtime_dir.join(CLOUDY_FOLDER_NAME).join(app_name),
+        None => cache_path.join("runtime"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Path, PathBuf}};
+
+    use crate::{app_dirs::runtime_path, AppDirKind, AppDirs, CLOUDY_FOLDER_NAME};
+
+    fn temp_root_path(test_name: &str) -> PathBuf {
+        let root_path = std::env::temp_dir()
+            .join(format!("cirrus_path_test_{}_{}", test_name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&root_path);
+
+        root_path
+    }
+
+    #[test]
+    fn test_root_redirects_every_dir() {
+        let root_path = temp_root_path("redirect");
+        let app_dirs = AppDirs::with_root("purr", &root_path);
+
+        let expected = [
+            (AppDirKind::Config, root_path.join("config/purr")),
+            (AppDirKind::Data, root_path.join("data/purr")),
+            (AppDirKind::State, root_path.join("state/purr")),
+            (AppDirKind::Cache, root_path.join("cache/purr")),
+            (AppDirKind::Logs, root_path.join("state/purr/logs")),
+            (AppDirKind::Runtime, root_path.join("runtime/purr")),
+        ];
+
+        for (kind, path) in expected {
+            assert_eq!(app_dirs.path(kind).unwrap(), path, "{:?}", kind);
+        }
+
+        assert!(!root_path.exists(), "'path' shouldn't create anything");
+    }
+
+    #[test]
+    fn test_every_dir_is_separate() {
+        let app_dirs = AppDirs::new("purr");
+
+        let kinds = [AppDirKind::Config, AppDirKind::Data, AppDirKind::State, AppDirKind::Cache, AppDirKind::Runtime];
+
+        let Ok(paths) = kinds.iter().map(|kind| app_dirs.path(*kind)).collect::<Result<Vec<_>, _>>() else {
+            return; // no home directory in this environment
+        };
+
+        for (index, path) in paths.iter().enumerate() {
+            for other_path in &paths[index + 1..] {
+                assert_ne!(path, other_path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_runtime_path_falls_back_to_cache_dir() {
+        let cache_path = Path::new("/cache/cloudy/purr");
+
+        assert_eq!(
+            runtime_path(Some(PathBuf::from("/run/user/1000")), "purr", cache_path),
+            Path::new("/run/user/1000").join(CLOUDY_FOLDER_NAME).join("purr")
+        );
+        assert_eq!(runtime_path(None, "purr", cache_path), cache_path.join("runtime"));
+    }
+
+    #[test]
+    fn test_create_makes_dirs() {
+        let root_path = temp_root_path("create");
+        let app_dirs = AppDirs::with_root("purr", &root_path);
+
+        let log_path = app_dirs.log_dir().unwrap();
+
+        assert!(log_path.is_dir());
+        assert_eq!(app_dirs.log_dir().unwrap(), log_path, "creating it twice should be fine");
+
+        fs::remove_dir_all(&root_path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_runtime_dir_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root_path = temp_root_path("runtime");
+        let app_dirs = AppDirs::with_root("purr", &root_path);
+
+        let runtime_path = app_dirs.path(AppDirKind::Runtime).unwrap();
+
+        // permissions are fixed even if the directory already existed
+        fs::create_dir_all(&runtime_path).unwrap();
+        fs::set_permissions(&runtime_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(app_dirs.runtime_dir().unwrap(), runtime_path);
+        assert_eq!(fs::metadata(&runtime_path).unwrap().permissions().mode() & 0o777, 0o700);
+
+        fs::remove_dir_all(&root_path).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use std::{env, fmt::Display};
+use std::{env, fmt::Display, path::PathBuf};
 
 static PLATFORM: &str = env::consts::OS;
 
@@ -7,6 +7,7 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 #[derive(Debug)]
 pub enum Error {
     PathNotFoundForPlatform,
+    FailedToCreateDirectory { path: PathBuf, error: String },
 }
 
 impl Display for Error {
@@ -17,6 +18,9 @@ impl Display for Error {
                 "This path is not supported for your platform yet ({PLATFORM}) in 'cirrus_path'! \
                     Feel free to add support or report it here: https://github.com/cloudy-org/cirrus",
             ),
+            Error::FailedToCreateDirectory { path, error } => write!(
+                f, "Failed to create the directory '{}': {error}", path.display()
+            ),
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,15 @@
-use std::path::PathBuf;
+use std::{env, path::PathBuf};
 
 use crate::error::{Error, Result};
 
 pub mod error;
+pub mod app_dirs;
+
+pub use app_dirs::{AppDirKind, AppDirs};
+
+/// Environment variable that redirects every cloudy-org folder into one root folder, handy
+/// for hermetic tests (e.g: `CLOUDY_HOME=/tmp/cloudy-test`). See [`get_cloudy_home_path`].
+pub const CLOUDY_HOME_ENV_VAR: &str = "CLOUDY_HOME";
 
 #[cfg(target_os = "windows")]
 const CLOUDY_FOLDER_NAME: &str = "Cloudy";
@@ -10,12 +17,33 @@ const CLOUDY_FOLDER_NAME: &str = "Cloudy";
 #[cfg(not(target_os = "windows"))]
 const CLOUDY_FOLDER_NAME: &str = "cloudy";
 
+/// The root folder set with `$CLOUDY_HOME`, if set every cloudy-org folder lives in it instead:
+///
+/// ```text
+/// $CLOUDY_HOME/
+///     config/         <- get_user_config_cloudy_folder_path()
+///     cache/          <- get_user_cache_cloudy_folder_path()
+///     data/themes/    <- get_user_cloudy_themes_folder_path()
+///     state/, runtime/ (and data/{app}) <- AppDirs
+/// ```
+///
+/// System theme folders are left out entirely so nothing outside of it is read.
+pub fn get_cloudy_home_path() -> Option<PathBuf> {
+    env::var_os(CLOUDY_HOME_ENV_VAR)
+        .filter(|cloudy_home| !cloudy_home.is_empty())
+        .map(PathBuf::from)
+}
+
 /// **Linux:** `~/.config/cloudy`
 /// 
 /// **Windows:** `C:\Users\{user}\AppData\Local\Cloudy`
 pub fn get_user_config_cloudy_folder_path() -> Result<PathBuf> {
     log::trace!("Getting user's local configuration cloudy-org folder...");
 
+    if let Some(cloudy_home_path) = get_cloudy_home_path() {
+        return Ok(cloudy_home_path.join("config"));
+    }
+
     match dirs::config_local_dir() {
         Some(local_config_dir) => Ok(local_config_dir.join(CLOUDY_FOLDER_NAME)),
         None => Err(Error::PathNotFoundForPlatform)
@@ -28,6 +56,10 @@ pub fn get_user_config_cloudy_folder_path() -> Result<PathBuf> {
 pub fn get_user_cache_cloudy_folder_path() -> Result<PathBuf> {
     log::trace!("Getting user's local cache cloudy-org folder...");
 
+    if let Some(cloudy_home_path) = get_cloudy_home_path() {
+        return Ok(cloudy_home_path.join("cache"));
+    }
+
     match dirs::cache_dir() {
         Some(cache_dir) => Ok(cache_dir.join(CLOUDY_FOLDER_NAME)),
         None => Err(Error::PathNotFoundForPlatform)
@@ -40,6 +72,10 @@ pub fn get_user_cache_cloudy_folder_path() -> Result<PathBuf> {
 pub fn get_user_cloudy_themes_folder_path() -> Result<PathBuf> {
     log::trace!("Getting user's cloudy-org theme folder...");
 
+    if let Some(cloudy_home_path) = get_cloudy_home_path() {
+        return Ok(cloudy_home_path.join("data").join("themes"));
+    }
+
     match dirs::data_local_dir() {
         Some(local_data_dir) => Ok(local_data_dir.join(CLOUDY_FOLDER_NAME).join("themes")),
         None => Err(Error::PathNotFoundForPlatform)
@@ -52,6 +88,10 @@ pub fn get_user_cloudy_themes_folder_path() -> Result<PathBuf> {
 pub fn get_system_cloudy_themes_folder_paths() -> Result<Vec<PathBuf>> {
     log::trace!("Getting system cloudy-org folders that could contain themes...");
 
+    if get_cloudy_home_path().is_some() {
+        return Ok(Vec::new());
+    }
+
     #[cfg(target_os = "linux")]
     {
         use xdg::BaseDirectories;
@@ -1,6 +1,7 @@
 use crate::{error::Result, tiny_lexer::{Token, tokenize}};
 
 #[derive(Debug)]
+#[cfg_attr(not(feature = "egui"), allow(dead_code))]
 pub struct Keys {
     pub(crate) tokens: Vec<Token>
 }
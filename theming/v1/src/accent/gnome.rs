@@ -0,0 +1,77 @@
+use std::{io::ErrorKind, process::Command};
+
+use crate::{accent::AccentColourBackend, colour::Colour, error::{Error, Result}};
+
+/// The colours GNOME's accent colour names stand for (libadwaita's accent colours).
+const GNOME_ACCENT_COLOURS: [(&str, u32); 9] = [
+    ("blue", 0x3584e4),
+    ("teal", 0x2190a4),
+    ("green", 0x3a944a),
+    ("yellow", 0xc88800),
+    ("orange", 0xed5b00),
+    ("red", 0xe62d42),
+    ("pink", 0xd56199),
+    ("purple", 0x9141ac),
+    ("slate", 0x6f8396),
+];
+
+/// Reads GNOME's `org.gnome.desktop.interface accent-color` setting with `gsettings`.
+pub struct GnomeAccentBackend {
+    program: String,
+    args: Vec<String>,
+}
+
+impl GnomeAccentBackend {
+    pub fn new() -> Self {
+        Self::with_command("gsettings", ["get", "org.gnome.desktop.interface", "accent-color"])
+    }
+
+    /// Runs `program` instead of `gsettings`, its output is read the same way (e.g: `'blue'`).
+    pub fn with_command<S: Into<String>>(program: impl Into<String>, args: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Default for GnomeAccentBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccentColourBackend for GnomeAccentBackend {
+    fn name(&self) -> &'static str {
+        "GNOME settings"
+    }
+
+    fn accent_colour(&self) -> Result<Option<Colour>> {
+        let output = match Command::new(&self.program).args(&self.args).output() {
+            Ok(output) => output,
+            // not a GNOME system
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(
+                Error::AccentColourReadFailure { backend: self.name().to_string(), error: error.to_string() }
+            ),
+        };
+
+        // older GNOME versions don't have the key
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let accent_colour_name = stdout.trim().trim_matches('\'');
+
+        match GNOME_ACCENT_COLOURS.iter().find(|(name, _)| *name == accent_colour_name) {
+            Some((_, hex)) => Ok(Some(Colour::from_hex(*hex))),
+            None => Err(
+                Error::AccentColourReadFailure {
+                    backend: self.name().to_string(),
+                    error: format!("Unknown accent colour '{accent_colour_name}'!"),
+                }
+            ),
+        }
+    }
+}
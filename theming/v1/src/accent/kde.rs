@@ -0,0 +1,61 @@
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+use crate::{accent::{colour_from_rgb_string, AccentColourBackend}, colour::Colour, error::{Error, Result}, ini::find_ini_value};
+
+/// Reads the accent colour from KDE Plasma's `kdeglobals` file (usually `~/.config/kdeglobals`).
+///
+/// Plasma writes a custom accent colour to `AccentColor` under `[General]`, when the accent colour comes from
+/// the colour scheme instead the selection colour under `[Colors:Selection]` is used.
+pub struct KdeAccentBackend {
+    kdeglobals_path: Option<PathBuf>,
+}
+
+impl KdeAccentBackend {
+    pub fn new() -> Self {
+        Self { kdeglobals_path: dirs::config_dir().map(|config_dir| config_dir.join("kdeglobals")) }
+    }
+
+    /// Reads from the `kdeglobals` file at `kdeglobals_path` instead.
+    pub fn from_path(kdeglobals_path: impl Into<PathBuf>) -> Self {
+        Self { kdeglobals_path: Some(kdeglobals_path.into()) }
+    }
+}
+
+impl Default for KdeAccentBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccentColourBackend for KdeAccentBackend {
+    fn name(&self) -> &'static str {
+        "kdeglobals"
+    }
+
+    fn accent_colour(&self) -> Result<Option<Colour>> {
+        let Some(kdeglobals_path) = &self.kdeglobals_path else {
+            return Ok(None);
+        };
+
+        let kdeglobals_string = match fs::read_to_string(kdeglobals_path) {
+            Ok(kdeglobals_string) => kdeglobals_string,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(
+                Error::AccentColourReadFailure { backend: self.name().to_string(), error: error.to_string() }
+            ),
+        };
+
+        let accent_colour = find_ini_value(&kdeglobals_string, "General", "AccentColor")
+            .or_else(|| find_ini_value(&kdeglobals_string, "Colors:Selection", "BackgroundNormal"));
+
+        match accent_colour {
+            Some(rgb_string) => colour_from_rgb_string(rgb_string)
+                .map(Some)
+                .ok_or_else(|| Error::AccentColourReadFailure {
+                    backend: self.name().to_string(),
+                    error: format!("'{rgb_string}' is not an 'r,g,b' colour!"),
+                }),
+            None => Ok(None),
+        }
+    }
+}
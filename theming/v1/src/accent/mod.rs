@@ -0,0 +1,90 @@
+//! Reading the accent colour the user picked in their desktop's settings.
+
+use std::env;
+
+use crate::{colour::Colour, error::Result};
+
+mod kde;
+mod gnome;
+#[cfg(all(target_os = "linux", feature = "portal"))]
+mod portal;
+
+pub use kde::KdeAccentBackend;
+pub use gnome::GnomeAccentBackend;
+#[cfg(all(target_os = "linux", feature = "portal"))]
+pub use portal::PortalAccentBackend;
+
+/// A place the system accent colour can be read from.
+pub trait AccentColourBackend {
+    /// Name used in logs (e.g: `kdeglobals`).
+    fn name(&self) -> &'static str;
+
+    /// `Ok(None)` when this backend isn't available or the user hasn't set an accent colour.
+    fn accent_colour(&self) -> Result<Option<Colour>>;
+}
+
+/// The backends worth asking on this desktop, in the order they should be asked.
+///
+/// The XDG Desktop Portal comes first as every desktop that has one exposes the accent colour the same way,
+/// then the desktop's own settings are read (picked with `$XDG_CURRENT_DESKTOP`).
+pub fn get_system_accent_colour_backends() -> Vec<Box<dyn AccentColourBackend>> {
+    let mut backends: Vec<Box<dyn AccentColourBackend>> = Vec::new();
+
+    if !cfg!(target_os = "linux") {
+        return backends;
+    }
+
+    #[cfg(all(target_os = "linux", feature = "portal"))]
+    backends.push(Box::new(PortalAccentBackend::new()));
+
+    let current_desktop = env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_uppercase();
+
+    for desktop in current_desktop.split(':') {
+        match desktop {
+            "KDE" => backends.push(Box::new(KdeAccentBackend::new())),
+            "GNOME" => backends.push(Box::new(GnomeAccentBackend::new())),
+            _ => continue,
+        }
+    }
+
+    backends
+}
+
+/// The first accent colour found by [`get_system_accent_colour_backends`], backends that fail are logged and skipped.
+pub fn get_system_accent_colour() -> Option<Colour> {
+    find_accent_colour(&get_system_accent_colour_backends())
+}
+
+/// The first accent colour found by the `backends`, in order.
+pub fn find_accent_colour(backends: &[Box<dyn AccentColourBackend>]) -> Option<Colour> {
+    for backend in backends {
+        log::debug!("Reading system accent colour from {}...", backend.name());
+
+        match backend.accent_colour() {
+            Ok(Some(colour)) => {
+                log::debug!("Found system accent colour '{}' in {}!", colour.to_hex_string(), backend.name());
+                return Some(colour);
+            },
+            Ok(None) => log::debug!("No accent colour set in {}.", backend.name()),
+            Err(error) => log::warn!("{}", error),
+        }
+    }
+
+    None
+}
+
+/// `r,g,b` with each channel between 0 and 255 (e.g: `61,174,233`).
+pub(crate) fn colour_from_rgb_string(rgb_string: &str) -> Option<Colour> {
+    let channels: Vec<u8> = rgb_string.split(',')
+        .map(|channel| channel.trim().parse::<u8>())
+        .collect::<std::result::Result<_, _>>()
+        .ok()?;
+
+    match channels.as_slice() {
+        // kdeglobals may have an alpha channel too, we don't care about it
+        [red, green, blue] | [red, green, blue, _] => Some(Colour::from_rgb(*red, *green, *blue)),
+        _ => None,
+    }
+}
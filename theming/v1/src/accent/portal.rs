@@ -0,0 +1,60 @@
+use zbus::{blocking::Connection, zvariant::Value};
+
+use crate::{accent::AccentColourBackend, colour::Colour, error::{Error, Result}, portal::{PortalSettings, APPEARANCE_NAMESPACE}};
+
+const ACCENT_COLOUR_KEY: &str = "accent-color";
+
+/// Reads `accent-color` from the XDG Desktop Portal's `org.freedesktop.appearance` settings over D-Bus.
+pub struct PortalAccentBackend {
+    settings: PortalSettings,
+}
+
+impl PortalAccentBackend {
+    /// Asks the portal on the session bus.
+    pub fn new() -> Self {
+        Self { settings: PortalSettings::session() }
+    }
+
+    /// Asks the portal on `connection` instead of the session bus's.
+    pub fn with_connection(connection: Connection) -> Self {
+        Self { settings: PortalSettings::with_connection(connection) }
+    }
+}
+
+impl Default for PortalAccentBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccentColourBackend for PortalAccentBackend {
+    fn name(&self) -> &'static str {
+        "XDG Desktop Portal"
+    }
+
+    fn accent_colour(&self) -> Result<Option<Colour>> {
+        let to_error = |error: String| Error::AccentColourReadFailure { backend: self.name().to_string(), error };
+
+        let value = match self.settings.read(APPEARANCE_NAMESPACE, ACCENT_COLOUR_KEY) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(None),
+            Err(error) => return Err(to_error(error.to_string())),
+        };
+
+        let value: Value = value.try_clone()
+            .map_err(|error| to_error(error.to_string()))?
+            .into();
+
+        let (red, green, blue) = <(f64, f64, f64)>::try_from(value)
+            .map_err(|error| to_error(format!("The accent colour is not an (r, g, b) tuple! Error: {error}")))?;
+
+        // values outside of 0-1 mean the user didn't pick an accent colour
+        if [red, green, blue].iter().any(|channel| !(0.0..=1.0).contains(channel)) {
+            return Ok(None);
+        }
+
+        let to_channel = |channel: f64| (channel * 255.0).round() as u8;
+
+        Ok(Some(Colour::from_rgb(to_channel(red), to_channel(green), to_channel(blue))))
+    }
+}
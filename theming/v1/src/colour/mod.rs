@@ -0,0 +1,207 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+
+mod parse;
+mod spaces;
+
+pub use spaces::{Hsl, Hsv, Oklch};
+
+/// An sRGB colour with straight (not premultiplied) alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour {
+    /// The colour as `0xRRGGBB`, the alpha is kept separately in `alpha`.
+    pub hex: u32,
+    /// 0 is fully transparent and 255 is opaque.
+    pub alpha: u8,
+}
+
+impl Colour {
+    pub const BLACK: Self = Self { hex: 0x000000, alpha: u8::MAX };
+    pub const WHITE: Self = Self { hex: 0xffffff, alpha: u8::MAX };
+    pub const TRANSPARENT: Self = Self { hex: 0x000000, alpha: 0 };
+
+    /// An opaque colour from `0xRRGGBB`.
+    pub fn from_hex(hex: u32) -> Self {
+        Self { hex: hex & 0xffffff, alpha: u8::MAX }
+    }
+
+    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::from_rgba(red, green, blue, u8::MAX)
+    }
+
+    pub fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self { hex: u32::from_be_bytes([0, red, green, blue]), alpha }
+    }
+
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` (the `#` is optional).
+    ///
+    /// Use [`Colour::parse`] to also accept `rgb()` and `hsl()` colours.
+    pub fn from_hex_string(hex_string: &str) -> Result<Self, Error> {
+        parse::parse_hex(hex_string)
+    }
+
+    /// Parses a hex colour (see [`Colour::from_hex_string`]) or a CSS
+    /// style `rgb()`, `rgba()`, `hsl()` or `hsla()` colour.
+    ///
+    /// e.g: `#3daee9`, `#fff8`, `rgb(61, 174, 233)`, `rgb(61 174 233 / 50%)` or `hsl(200deg, 80%, 58%)`.
+    pub fn parse(colour_string: &str) -> Result<Self, Error> {
+        parse::parse(colour_string)
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` if the colour isn't opaque.
+    pub fn to_hex_string(&self) -> String {
+        match self.is_opaque() {
+            true => format!("#{:06x}", self.hex),
+            false => format!("#{:06x}{:02x}", self.hex, self.alpha),
+        }
+    }
+
+    pub fn to_rgb(&self) -> [u8; 3] {
+        let [_, red, green, blue] = self.hex.to_be_bytes();
+
+        [red, green, blue]
+    }
+
+    pub fn to_rgba(&self) -> [u8; 4] {
+        let [red, green, blue] = self.to_rgb();
+
+        [red, green, blue, self.alpha]
+    }
+
+    pub fn with_alpha(&self, alpha: u8) -> Self {
+        Self { hex: self.hex, alpha }
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.alpha == u8::MAX
+    }
+
+    /// Mixes `amount` (0.0 - 1.0) of `other` into this colour, alpha included.
+    pub fn mix(&self, other: Colour, amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+
+        let [red, green, blue, alpha] = self.to_rgba();
+        let [other_red, other_green, other_blue, other_alpha] = other.to_rgba();
+
+        let mix_channel = |channel: u8, other_channel: u8| {
+            let channel = f32::from(channel);
+
+            (channel + (f32::from(other_channel) - channel) * amount).round() as u8
+        };
+
+        Self::from_rgba(
+            mix_channel(red, other_red),
+            mix_channel(green, other_green),
+            mix_channel(blue, other_blue),
+            mix_channel(alpha, other_alpha)
+        )
+    }
+
+    /// Raises the HSL lightness by `amount` (0.0 - 1.0), e.g: `0.1` takes 40% lightness to 50%.
+    pub fn lighten(&self, amount: f32) -> Self {
+        let mut hsl = self.to_hsl();
+        hsl.lightness = (hsl.lightness + amount).clamp(0.0, 1.0);
+
+        Self::from_hsl(hsl).with_alpha(self.alpha)
+    }
+
+    /// Lowers the HSL lightness by `amount` (0.0 - 1.0).
+    pub fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Raises the HSL saturation by `amount` (0.0 - 1.0).
+    pub fn saturate(&self, amount: f32) -> Self {
+        let mut hsl = self.to_hsl();
+        hsl.saturation = (hsl.saturation + amount).clamp(0.0, 1.0);
+
+        Self::from_hsl(hsl).with_alpha(self.alpha)
+    }
+
+    /// Lowers the HSL saturation by `amount` (0.0 - 1.0).
+    pub fn desaturate(&self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// WCAG 2 relative luminance, from 0.0 (black) to 1.0 (white). Alpha is ignored.
+    pub fn relative_luminance(&self) -> f32 {
+        let [red, green, blue] = self.to_linear_rgb();
+
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    /// WCAG 2 contrast ratio between this colour and `other`, from 1.0 to 21.0.
+    ///
+    /// Text should have at least 4.5 (or 3.0 if it's large) against its background.
+    pub fn contrast_ratio(&self, other: Colour) -> f32 {
+        let luminance = self.relative_luminance();
+        let other_luminance = other.relative_luminance();
+
+        (luminance.max(other_luminance) + 0.05) / (luminance.min(other_luminance) + 0.05)
+    }
+
+    /// Red, green and blue between 0.0 and 1.0.
+    pub(crate) fn to_rgb_f32(self) -> [f32; 3] {
+        self.to_rgb().map(|channel| f32::from(channel) / 255.0)
+    }
+
+    /// An opaque colour from red, green and blue between 0.0 and 1.0 (clamped).
+    pub(crate) fn from_rgb_f32([red, green, blue]: [f32; 3]) -> Self {
+        let to_channel = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        Self::from_rgb(to_channel(red), to_channel(green), to_channel(blue))
+    }
+
+    pub(crate) fn to_linear_rgb(self) -> [f32; 3] {
+        self.to_rgb_f32().map(|channel| match channel <= 0.04045 {
+            true => channel / 12.92,
+            false => ((channel + 0.055) / 1.055).powf(2.4),
+        })
+    }
+
+    pub(crate) fn from_linear_rgb(linear_rgb: [f32; 3]) -> Self {
+        Self::from_rgb_f32(
+            linear_rgb.map(|channel| match channel <= 0.0031308 {
+                true => channel * 12.92,
+                false => 1.055 * channel.powf(1.0 / 2.4) - 0.055,
+            })
+        )
+    }
+}
+
+impl Default for Colour {
+    fn default() -> Self {
+        Self::BLACK
+    }
+}
+
+impl From<u32> for Colour {
+    fn from(value: u32) -> Self {
+        Self::from_hex(value)
+    }
+}
+
+impl FromStr for Colour {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for Colour {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for Colour {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{colour::{Colour, Hsl}, error::Error};
+
+pub(crate) fn parse(colour_string: &str) -> Result<Colour, Error> {
+    let colour_string = colour_string.trim();
+
+    let Some((function_name, arguments)) = colour_string.strip_suffix(')')
+        .and_then(|colour_string| colour_string.split_once('(')) else {
+        return parse_hex(colour_string);
+    };
+
+    let to_error = |error: String| Error::ColourParseFailure {
+        colour_string: colour_string.to_string(),
+        error,
+    };
+
+    // both the legacy "rgb(r, g, b)" and modern "rgb(r g b / a)" syntax
+    let arguments: Vec<&str> = arguments.split([',', '/', ' '])
+        .map(str::trim)
+        .filter(|argument| !argument.is_empty())
+        .collect();
+
+    let (channels, alpha) = match arguments.as_slice() {
+        [first, second, third] => ([*first, *second, *third], None),
+        [first, second, third, alpha] => ([*first, *second, *third], Some(*alpha)),
+        _ => return Err(to_error(format!("Expected 3 or 4 values but got {}!", arguments.len()))),
+    };
+
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha).map_err(to_error)?,
+        None => u8::MAX,
+    };
+
+    let colour = match function_name.trim().to_lowercase().as_str() {
+        "rgb" | "rgba" => {
+            let [red, green, blue] = channels;
+
+            Colour::from_rgb(
+                parse_rgb_channel(red).map_err(to_error)?,
+                parse_rgb_channel(green).map_err(to_error)?,
+                parse_rgb_channel(blue).map_err(to_error)?
+            )
+        },
+        "hsl" | "hsla" => {
+            let [hue, saturation, lightness] = channels;
+
+            Colour::from_hsl(
+                Hsl {
+                    hue: parse_hue(hue).map_err(to_error)?,
+                    saturation: parse_percentage(saturation).map_err(to_error)?,
+                    lightness: parse_percentage(lightness).map_err(to_error)?,
+                }
+            )
+        },
+        function_name => return Err(
+            to_error(format!("'{function_name}()' is not supported, use 'rgb()' or 'hsl()'!"))
+        ),
+    };
+
+    Ok(colour.with_alpha(alpha))
+}
+
+pub(crate) fn parse_hex(hex_string: &str) -> Result<Colour, Error> {
+    let to_error = |error: &str| Error::HexCodeParseFailure {
+        error: error.to_string(),
+        hex_string: hex_string.to_string(),
+    };
+
+    let hex_digits = hex_string.trim().trim_start_matches('#');
+
+    let digits: Vec<u8> = hex_digits.chars()
+        .map(|char| char.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()
+        .ok_or_else(|| to_error("Not a hex digit!"))?;
+
+    // "#rgb" and "#rgba" are short for "#rrggbb" and "#rrggbbaa"
+    let channels: Vec<u8> = match digits.len() {
+        3 | 4 => digits.iter().map(|digit| digit * 0x11).collect(),
+        6 | 8 => digits.chunks(2).map(|digits| digits[0] * 0x10 + digits[1]).collect(),
+        _ => return Err(to_error("Expected 3, 4, 6 or 8 hex digits!")),
+    };
+
+    match channels.as_slice() {
+        [red, green, blue] => Ok(Colour::from_rgb(*red, *green, *blue)),
+        [red, green, blue, alpha] => Ok(Colour::from_rgba(*red, *green, *blue, *alpha)),
+        _ => unreachable!(),
+    }
+}
+
+/// 0 - 255 or a percentage.
+fn parse_rgb_channel(channel: &str) -> Result<u8, String> {
+    let value = match channel.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? / 100.0 * 255.0,
+        None => parse_number(channel)?,
+    };
+
+    match (0.0..=255.0).contains(&value) {
+        true => Ok(value.round() as u8),
+        false => Err(format!("'{channel}' is not between 0 and 255 (or 0% and 100%)!")),
+    }
+}
+
+/// 0.0 - 1.0 or a percentage.
+fn parse_alpha(alpha: &str) -> Result<u8, String> {
+    let value = match alpha.strip_suffix('%') {
+        Some(percentage) => parse_number(percentage)? / 100.0,
+        None => parse_number(alpha)?,
+    };
+
+    match (0.0..=1.0).contains(&value) {
+        true => Ok((value * 255.0).round() as u8),
+        false => Err(format!("The alpha '{alpha}' is not between 0 and 1 (or 0% and 100%)!")),
+    }
+}
+
+/// Degrees, with or without the "deg" unit.
+fn parse_hue(hue: &str) -> Result<f32, String> {
+    parse_number(hue.strip_suffix("deg").unwrap_or(hue))
+}
+
+/// 0% - 100%, the "%" is optional like in CSS.
+fn parse_percentage(percentage: &str) -> Result<f32, String> {
+    let value = parse_number(percentage.strip_suffix('%').unwrap_or(percentage))?;
+
+    match (0.0..=100.0).contains(&value) {
+        true => Ok(value / 100.0),
+        false => Err(format!("'{percentage}' is not between 0% and 100%!")),
+    }
+}
+
+fn parse_number(number: &str) -> Result<f32, String> {
+    number.trim().parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("'{number}' is not a number!"))
+}
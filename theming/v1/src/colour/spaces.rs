@@ -0,0 +1,161 @@
+use crate::colour::Colour;
+
+/// Hue in degrees (0 - 360), saturation and lightness between 0.0 and 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+}
+
+/// Hue in degrees (0 - 360), saturation and value between 0.0 and 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsv {
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+}
+
+/// OKLCH, the polar form of the perceptual OKLab colour space. Unlike HSL, colours with
+/// the same lightness here look equally light, which makes it good for deriving colours.
+///
+/// Lightness is between 0.0 and 1.0, chroma from 0.0 (grey) up to around 0.37
+/// for the most saturated sRGB colours and hue is in degrees (0 - 360).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub lightness: f32,
+    pub chroma: f32,
+    pub hue: f32,
+}
+
+impl Colour {
+    pub fn to_hsl(&self) -> Hsl {
+        let [red, green, blue] = self.to_rgb_f32();
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let chroma = max - min;
+
+        let lightness = (max + min) / 2.0;
+
+        let saturation = match chroma == 0.0 {
+            true => 0.0,
+            false => chroma / (1.0 - (2.0 * lightness - 1.0).abs()),
+        };
+
+        Hsl { hue: hue([red, green, blue], max, chroma), saturation, lightness }
+    }
+
+    /// An opaque colour from HSL, out of range saturation and lightness are clamped.
+    pub fn from_hsl(hsl: Hsl) -> Self {
+        let saturation = hsl.saturation.clamp(0.0, 1.0);
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+
+        from_hue_and_chroma(hsl.hue, chroma, lightness - chroma / 2.0)
+    }
+
+    pub fn to_hsv(&self) -> Hsv {
+        let [red, green, blue] = self.to_rgb_f32();
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let chroma = max - min;
+
+        let saturation = match max == 0.0 {
+            true => 0.0,
+            false => chroma / max,
+        };
+
+        Hsv { hue: hue([red, green, blue], max, chroma), saturation, value: max }
+    }
+
+    /// An opaque colour from HSV, out of range saturation and value are clamped.
+    pub fn from_hsv(hsv: Hsv) -> Self {
+        let saturation = hsv.saturation.clamp(0.0, 1.0);
+        let value = hsv.value.clamp(0.0, 1.0);
+
+        let chroma = value * saturation;
+
+        from_hue_and_chroma(hsv.hue, chroma, value - chroma)
+    }
+
+    pub fn to_oklch(&self) -> Oklch {
+        // the OKLab matrices are precise past what f32 holds
+        let [red, green, blue] = self.to_linear_rgb().map(f64::from);
+
+        let long = (0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue).cbrt();
+        let medium = (0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue).cbrt();
+        let short = (0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue).cbrt();
+
+        let lightness = 0.2104542553 * long + 0.7936177850 * medium - 0.0040720468 * short;
+        let a = 1.9779984951 * long - 2.4285922050 * medium + 0.4505937099 * short;
+        let b = 0.0259040371 * long + 0.7827717662 * medium - 0.8086757660 * short;
+
+        let chroma = a.hypot(b);
+
+        // greys don't have a hue, rounding errors would give them a random one
+        let hue = match chroma < 1e-4 {
+            true => 0.0,
+            false => b.atan2(a).to_degrees().rem_euclid(360.0),
+        };
+
+        Oklch { lightness: lightness as f32, chroma: chroma as f32, hue: hue as f32 }
+    }
+
+    /// An opaque colour from OKLCH. Colours outside of sRGB have their channels clamped.
+    pub fn from_oklch(oklch: Oklch) -> Self {
+        let (hue_sin, hue_cos) = f64::from(oklch.hue).to_radians().sin_cos();
+
+        let lightness = f64::from(oklch.lightness.clamp(0.0, 1.0));
+        let chroma = f64::from(oklch.chroma.max(0.0));
+        let a = chroma * hue_cos;
+        let b = chroma * hue_sin;
+
+        let long = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let medium = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let short = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        let linear_rgb = [
+            4.0767416621 * long - 3.3077115913 * medium + 0.2309699292 * short,
+            -1.2684380046 * long + 2.6097574011 * medium - 0.3413193965 * short,
+            -0.0041960863 * long - 0.7034186147 * medium + 1.7076147010 * short,
+        ];
+
+        Self::from_linear_rgb(linear_rgb.map(|channel| channel as f32))
+    }
+}
+
+/// The hue (in degrees) shared by HSL and HSV.
+fn hue([red, green, blue]: [f32; 3], max: f32, chroma: f32) -> f32 {
+    if chroma == 0.0 {
+        return 0.0;
+    }
+
+    let hue = if max == red {
+        ((green - blue) / chroma).rem_euclid(6.0)
+    } else if max == green {
+        (blue - red) / chroma + 2.0
+    } else {
+        (red - green) / chroma + 4.0
+    };
+
+    hue * 60.0
+}
+
+fn from_hue_and_chroma(hue: f32, chroma: f32, offset: f32) -> Colour {
+    let hue_sector = hue.rem_euclid(360.0) / 60.0;
+    let second_largest = chroma * (1.0 - (hue_sector.rem_euclid(2.0) - 1.0).abs());
+
+    let [red, green, blue] = match hue_sector as u8 {
+        0 => [chroma, second_largest, 0.0],
+        1 => [second_largest, chroma, 0.0],
+        2 => [0.0, chroma, second_largest],
+        3 => [0.0, second_largest, chroma],
+        4 => [second_largest, 0.0, chroma],
+        _ => [chroma, 0.0, second_largest],
+    };
+
+    Colour::from_rgb_f32([red + offset, green + offset, blue + offset])
+}
@@ -0,0 +1,66 @@
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+use crate::{colour_scheme::{ColourScheme, ColourSchemeBackend}, error::{Error, Result}, ini::find_ini_value};
+
+/// Reads the colour scheme from GTK's `settings.ini` files (`~/.config/gtk-4.0/settings.ini` then `gtk-3.0`).
+///
+/// Dark if `gtk-application-prefer-dark-theme` is on or the `gtk-theme-name` is a dark variant (e.g: `Adwaita-dark`).
+pub struct GtkColourSchemeBackend {
+    settings_paths: Vec<PathBuf>,
+}
+
+impl GtkColourSchemeBackend {
+    pub fn new() -> Self {
+        let settings_paths = match dirs::config_dir() {
+            Some(config_dir) => ["gtk-4.0", "gtk-3.0"].iter()
+                .map(|gtk_folder| config_dir.join(gtk_folder).join("settings.ini"))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Self { settings_paths }
+    }
+
+    /// Reads from the `settings.ini` files at `settings_paths` instead, the first one that exists wins.
+    pub fn from_paths(settings_paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self { settings_paths: settings_paths.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl Default for GtkColourSchemeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColourSchemeBackend for GtkColourSchemeBackend {
+    fn name(&self) -> &'static str {
+        "GTK settings"
+    }
+
+    fn colour_scheme(&self) -> Result<ColourScheme> {
+        for settings_path in &self.settings_paths {
+            let settings_string = match fs::read_to_string(settings_path) {
+                Ok(settings_string) => settings_string,
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => return Err(
+                    Error::ColourSchemeReadFailure { backend: self.name().to_string(), error: error.to_string() }
+                ),
+            };
+
+            let prefer_dark_theme = find_ini_value(&settings_string, "Settings", "gtk-application-prefer-dark-theme");
+
+            if matches!(prefer_dark_theme, Some("1") | Some("true")) {
+                return Ok(ColourScheme::Dark);
+            }
+
+            return match find_ini_value(&settings_string, "Settings", "gtk-theme-name") {
+                Some(theme_name) if theme_name.to_lowercase().ends_with("-dark") => Ok(ColourScheme::Dark),
+                Some(_) => Ok(ColourScheme::Light),
+                None => Ok(ColourScheme::NoPreference),
+            };
+        }
+
+        Ok(ColourScheme::NoPreference)
+    }
+}
@@ -0,0 +1,67 @@
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+use crate::{accent::colour_from_rgb_string, colour_scheme::{ColourScheme, ColourSchemeBackend}, error::{Error, Result}, ini::find_ini_value};
+
+/// Reads the colour scheme from KDE Plasma's `kdeglobals` file (usually `~/.config/kdeglobals`).
+///
+/// Plasma colour schemes don't say if they're dark or light so it's worked out from the window background colour.
+pub struct KdeColourSchemeBackend {
+    kdeglobals_path: Option<PathBuf>,
+}
+
+impl KdeColourSchemeBackend {
+    pub fn new() -> Self {
+        Self { kdeglobals_path: dirs::config_dir().map(|config_dir| config_dir.join("kdeglobals")) }
+    }
+
+    /// Reads from the `kdeglobals` file at `kdeglobals_path` instead.
+    pub fn from_path(kdeglobals_path: impl Into<PathBuf>) -> Self {
+        Self { kdeglobals_path: Some(kdeglobals_path.into()) }
+    }
+}
+
+impl Default for KdeColourSchemeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColourSchemeBackend for KdeColourSchemeBackend {
+    fn name(&self) -> &'static str {
+        "kdeglobals"
+    }
+
+    fn colour_scheme(&self) -> Result<ColourScheme> {
+        let Some(kdeglobals_path) = &self.kdeglobals_path else {
+            return Ok(ColourScheme::NoPreference);
+        };
+
+        let kdeglobals_string = match fs::read_to_string(kdeglobals_path) {
+            Ok(kdeglobals_string) => kdeglobals_string,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(ColourScheme::NoPreference),
+            Err(error) => return Err(
+                Error::ColourSchemeReadFailure { backend: self.name().to_string(), error: error.to_string() }
+            ),
+        };
+
+        let Some(rgb_string) = find_ini_value(&kdeglobals_string, "Colors:Window", "BackgroundNormal") else {
+            return Ok(ColourScheme::NoPreference);
+        };
+
+        let background_colour = colour_from_rgb_string(rgb_string)
+            .ok_or_else(|| Error::ColourSchemeReadFailure {
+                backend: self.name().to_string(),
+                error: format!("'{rgb_string}' is not an 'r,g,b' colour!"),
+            })?;
+
+        let [red, green, blue] = background_colour.to_rgb();
+
+        // perceived brightness (ITU-R BT.601), good enough to tell dark from light
+        let brightness = 0.299 * f32::from(red) + 0.587 * f32::from(green) + 0.114 * f32::from(blue);
+
+        match brightness < 128.0 {
+            true => Ok(ColourScheme::Dark),
+            false => Ok(ColourScheme::Light),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Reading (and watching) whether the user prefers dark or light apps in their desktop's settings.
+
+use std::env;
+
+use crate::error::Result;
+
+mod kde;
+mod gtk;
+mod watcher;
+#[cfg(all(target_os = "linux", feature = "portal"))]
+mod portal;
+
+pub use kde::KdeColourSchemeBackend;
+pub use gtk::GtkColourSchemeBackend;
+pub use watcher::{ColourSchemeWatcher, POLL_INTERVAL};
+#[cfg(all(target_os = "linux", feature = "portal"))]
+pub use portal::PortalColourSchemeBackend;
+
+/// The colour scheme the user prefers apps to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColourScheme {
+    #[default]
+    NoPreference,
+    Dark,
+    Light,
+}
+
+impl ColourScheme {
+    /// Whether a dark theme should be used, dark when there's no preference as that's our default.
+    pub fn is_dark(&self) -> bool {
+        !matches!(self, ColourScheme::Light)
+    }
+}
+
+/// A place the preferred colour scheme can be read from.
+pub trait ColourSchemeBackend {
+    /// Name used in logs (e.g: `kdeglobals`).
+    fn name(&self) -> &'static str;
+
+    /// [`ColourScheme::NoPreference`] when this backend isn't available or the user hasn't picked one.
+    fn colour_scheme(&self) -> Result<ColourScheme>;
+}
+
+/// The backends worth asking on this desktop, in the order they should be asked.
+///
+/// Like with the accent colour, the XDG Desktop Portal comes first then the desktop's own settings.
+pub fn get_system_colour_scheme_backends() -> Vec<Box<dyn ColourSchemeBackend>> {
+    let mut backends: Vec<Box<dyn ColourSchemeBackend>> = Vec::new();
+
+    if !cfg!(target_os = "linux") {
+        return backends;
+    }
+
+    #[cfg(all(target_os = "linux", feature = "portal"))]
+    backends.push(Box::new(PortalColourSchemeBackend::new()));
+
+    let current_desktop = env::var("XDG_CURRENT_DESKTOP")
+        .unwrap_or_default()
+        .to_uppercase();
+
+    if current_desktop.split(':').any(|desktop| desktop == "KDE") {
+        backends.push(Box::new(KdeColourSchemeBackend::new()));
+    }
+
+    // most other desktops set the GTK theme
+    backends.push(Box::new(GtkColourSchemeBackend::new()));
+
+    backends
+}
+
+/// The first colour scheme preference found by [`get_system_colour_scheme_backends`].
+pub fn get_system_colour_scheme() -> ColourScheme {
+    find_colour_scheme(&get_system_colour_scheme_backends())
+}
+
+/// The first colour scheme preference found by the `backends`, in order. Backends that fail are logged and skipped.
+pub fn find_colour_scheme(backends: &[Box<dyn ColourSchemeBackend>]) -> ColourScheme {
+    for backend in backends {
+        log::debug!("Reading preferred colour scheme from {}...", backend.name());
+
+        match backend.colour_scheme() {
+            Ok(ColourScheme::NoPreference) => log::debug!("No colour scheme preference in {}.", backend.name()),
+            Ok(colour_scheme) => {
+                log::debug!("Found preferred colour scheme '{:?}' in {}!", colour_scheme, backend.name());
+                return colour_scheme;
+            },
+            Err(error) => log::warn!("{}", error),
+        }
+    }
+
+    ColourScheme::NoPreference
+}
@@ -0,0 +1,71 @@
+use zbus::blocking::{Connection, MessageIterator};
+
+use crate::{colour_scheme::{ColourScheme, ColourSchemeBackend}, error::{Error, Result}, portal::{PortalSettings, APPEARANCE_NAMESPACE}};
+
+const COLOUR_SCHEME_KEY: &str = "color-scheme";
+
+/// Reads `color-scheme` from the XDG Desktop Portal's `org.freedesktop.appearance` settings over D-Bus.
+pub struct PortalColourSchemeBackend {
+    settings: PortalSettings,
+}
+
+impl PortalColourSchemeBackend {
+    /// Asks the portal on the session bus.
+    pub fn new() -> Self {
+        Self { settings: PortalSettings::session() }
+    }
+
+    /// Asks the portal on `connection` instead of the session bus's.
+    pub fn with_connection(connection: Connection) -> Self {
+        Self { settings: PortalSettings::with_connection(connection) }
+    }
+
+    /// The portal's signals for changes to the colour scheme along with the connection to close to stop them.
+    /// Fails if there's no portal to send them or it doesn't have a colour scheme setting.
+    pub(crate) fn watch(&self) -> Result<(MessageIterator, Connection)> {
+        let to_error = |error: String| Error::ColourSchemeReadFailure { backend: self.name().to_string(), error };
+
+        let changes = self.settings.watch(APPEARANCE_NAMESPACE, COLOUR_SCHEME_KEY)
+            .map_err(|error| to_error(error.to_string()))?
+            .ok_or_else(|| to_error("There's no D-Bus session bus!".to_string()))?;
+
+        // subscribed first so a change made in between isn't missed
+        match self.settings.read(APPEARANCE_NAMESPACE, COLOUR_SCHEME_KEY) {
+            Ok(Some(_)) => Ok(changes),
+            Ok(None) => Err(to_error("The portal has no colour scheme setting!".to_string())),
+            Err(error) => Err(to_error(error.to_string())),
+        }
+    }
+}
+
+impl Default for PortalColourSchemeBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColourSchemeBackend for PortalColourSchemeBackend {
+    fn name(&self) -> &'static str {
+        "XDG Desktop Portal"
+    }
+
+    fn colour_scheme(&self) -> Result<ColourScheme> {
+        let to_error = |error: String| Error::ColourSchemeReadFailure { backend: self.name().to_string(), error };
+
+        let value = match self.settings.read(APPEARANCE_NAMESPACE, COLOUR_SCHEME_KEY) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(ColourScheme::NoPreference),
+            Err(error) => return Err(to_error(error.to_string())),
+        };
+
+        let colour_scheme = u32::try_from(&*value)
+            .map_err(|error| to_error(format!("The colour scheme is not a number! Error: {error}")))?;
+
+        match colour_scheme {
+            1 => Ok(ColourScheme::Dark),
+            2 => Ok(ColourScheme::Light),
+            // 0 is "no preference", anything else is unknown to us
+            _ => Ok(ColourScheme::NoPreference),
+        }
+    }
+}
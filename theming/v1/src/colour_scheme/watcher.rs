@@ -0,0 +1,161 @@
+use std::{sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, Receiver, Sender}, Arc}, thread::{self, JoinHandle}, time::Duration};
+
+#[cfg(all(target_os = "linux", feature = "portal"))]
+use zbus::blocking::Connection;
+
+use crate::colour_scheme::{find_colour_scheme, get_system_colour_scheme_backends, ColourScheme, ColourSchemeBackend};
+#[cfg(all(target_os = "linux", feature = "portal"))]
+use crate::{colour_scheme::PortalColourSchemeBackend, error::Result};
+
+/// How often the system's colour scheme preference is checked for changes when it has to be polled.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches the system's colour scheme preference for changes (e.g: the desktop switching to dark mode at night).
+///
+/// When the XDG Desktop Portal has the preference, the watcher waits on a background thread for the portal to say
+/// it changed. Otherwise the preference is read every [`POLL_INTERVAL`]. The thread stops once this is dropped.
+pub struct ColourSchemeWatcher {
+    receiver: Receiver<ColourScheme>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    /// Closed on drop so the thread stops waiting on the portal's signals.
+    #[cfg(all(target_os = "linux", feature = "portal"))]
+    portal_connection: Option<Connection>,
+}
+
+impl ColourSchemeWatcher {
+    /// Watches the backends from [`get_system_colour_scheme_backends`], starting from `colour_scheme`.
+    pub fn new(colour_scheme: ColourScheme) -> Self {
+        #[cfg(all(target_os = "linux", feature = "portal"))]
+        match Self::with_portal(colour_scheme, PortalColourSchemeBackend::new(), get_system_colour_scheme_backends) {
+            Ok(watcher) => return watcher,
+            Err(error) => log::debug!("Can't be told about colour scheme changes, polling for them instead! Error: {}", error),
+        }
+
+        Self::with_backends(colour_scheme, get_system_colour_scheme_backends, POLL_INTERVAL)
+    }
+
+    /// Watches the backends returned by `backends` (called on the watcher's thread) every `poll_interval`.
+    pub fn with_backends(
+        colour_scheme: ColourScheme,
+        backends: impl FnOnce() -> Vec<Box<dyn ColourSchemeBackend>> + Send + 'static,
+        poll_interval: Duration
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let stop = stop.clone();
+
+            thread::spawn(move || {
+                let backends = backends();
+                let mut last_colour_scheme = colour_scheme;
+
+                while !stop.load(Ordering::Relaxed) {
+                    thread::park_timeout(poll_interval);
+
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if !send_if_changed(&sender, &backends, &mut last_colour_scheme) {
+                        break;
+                    }
+                }
+            })
+        };
+
+        Self {
+            receiver,
+            stop,
+            thread: Some(thread),
+            #[cfg(all(target_os = "linux", feature = "portal"))]
+            portal_connection: None,
+        }
+    }
+
+    /// Reads the backends returned by `backends` (called on the watcher's thread) whenever `portal` says the
+    /// colour scheme changed, instead of polling. Fails if the portal isn't there or doesn't have the preference.
+    #[cfg(all(target_os = "linux", feature = "portal"))]
+    pub fn with_portal(
+        colour_scheme: ColourScheme,
+        portal: PortalColourSchemeBackend,
+        backends: impl FnOnce() -> Vec<Box<dyn ColourSchemeBackend>> + Send + 'static
+    ) -> Result<Self> {
+        let (changes, portal_connection) = portal.watch()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let stop = stop.clone();
+
+            thread::spawn(move || {
+                let backends = backends();
+                let mut last_colour_scheme = colour_scheme;
+
+                // ends once the connection gets closed
+                for change in changes {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    if let Err(error) = change {
+                        log::debug!("Stopped watching the portal for colour scheme changes! Error: {}", error);
+                        break;
+                    }
+
+                    if !send_if_changed(&sender, &backends, &mut last_colour_scheme) {
+                        break;
+                    }
+                }
+            })
+        };
+
+        Ok(
+            Self {
+                receiver,
+                stop,
+                thread: Some(thread),
+                portal_connection: Some(portal_connection),
+            }
+        )
+    }
+
+    /// Returns the new colour scheme if it changed since the last time this was called.
+    /// Never blocks, so it's fine to call every frame.
+    pub fn poll_changed(&self) -> Option<ColourScheme> {
+        self.receiver.try_iter().last()
+    }
+}
+
+impl Drop for ColourSchemeWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+
+        #[cfg(all(target_os = "linux", feature = "portal"))]
+        if let Some(connection) = self.portal_connection.take() {
+            let _ = connection.close();
+        }
+
+        if let Some(thread) = self.thread.take() {
+            // wake it up instead of waiting out the poll interval
+            thread.thread().unpark();
+        }
+    }
+}
+
+/// Reads the backends and sends the colour scheme if it's changed. Returns `false` once nobody is listening.
+fn send_if_changed(sender: &Sender<ColourScheme>, backends: &[Box<dyn ColourSchemeBackend>], last_colour_scheme: &mut ColourScheme) -> bool {
+    let colour_scheme = find_colour_scheme(backends);
+
+    if colour_scheme == *last_colour_scheme {
+        return true;
+    }
+
+    log::debug!("System colour scheme changed from '{:?}' to '{:?}'!", last_colour_scheme, colour_scheme);
+
+    *last_colour_scheme = colour_scheme;
+
+    sender.send(colour_scheme).is_ok()
+}
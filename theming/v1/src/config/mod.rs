@@ -1 +1,2 @@
-pub mod v1;
\ No newline at end of file
+pub mod v1;
+pub mod v2;
\ No newline at end of file
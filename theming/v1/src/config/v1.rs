@@ -1,6 +1,6 @@
 use serde::{Deserialize};
 
-use crate::{colour::Colour, error::Error, fallbacks::ThemeFallbacks, palette::{ColourPalette, TRANSPARENT_HEX}, theme::Theme};
+use crate::{colour::Colour, error::Error, fallbacks::ThemeFallbacks, palette::ColourPalette, theme::Theme};
 
 #[derive(Deserialize)]
 pub struct ThemeConfigV1 {
@@ -14,12 +14,12 @@ pub struct ThemeConfigV1 {
 }
 
 #[derive(Deserialize)]
-struct Metadata {
+pub(crate) struct Metadata {
     pub name: String,
 }
 
-#[derive(Deserialize)]
-struct ThemePalette {
+#[derive(Deserialize, Default)]
+pub(crate) struct ThemePalette {
     #[serde(default)]
     pub primary: Option<String>,
     #[serde(default)]
@@ -30,38 +30,48 @@ struct ThemePalette {
     pub text: Option<String>,
     #[serde(default)]
     pub accent: Option<String>,
+    #[serde(default)]
+    pub success: Option<String>,
+    #[serde(default)]
+    pub warning: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub info: Option<String>,
+    #[serde(default)]
+    pub selection: Option<String>,
+    #[serde(default)]
+    pub link: Option<String>,
 }
 
 pub fn parse(toml_string: &str, fallbacks: &ThemeFallbacks) -> Result<Theme, Error> {
-    let theme_config: ThemeConfigV1 = toml::from_str(&toml_string)
+    let theme_config: ThemeConfigV1 = toml::from_str(toml_string)
         .map_err(|error| Error::ThemeTomlParseFailure { error: error.to_string() })?;
 
     let is_dark = theme_config.dark_mode;
     // let theme_features = theme_config.features;
     let theme_palette = theme_config.palette;
 
-    let transparent_colour = Colour::from_hex(TRANSPARENT_HEX);
-
     let primary_colour: Colour = match theme_palette.primary {
         Some(hex_string) => Colour::try_from(hex_string)?,
-        None => transparent_colour,
+        None => Colour::TRANSPARENT,
     };
 
     let interactive_colour: Colour = match theme_palette.interactive {
         Some(hex_string) => Colour::try_from(hex_string)?,
-        None => transparent_colour,
+        None => Colour::TRANSPARENT,
     };
 
     let surface_colour = match theme_palette.surface {
         Some(hex_string) => Colour::try_from(hex_string)?,
-        None => transparent_colour,
+        None => Colour::TRANSPARENT,
     };
 
     let text_colour = match theme_palette.text {
         Some(hex_string) => Colour::try_from(hex_string)?,
         None => match is_dark {
-            true => Colour::from_hex(0xffffff),
-            false => Colour::from_hex(0x000000)
+            true => Colour::WHITE,
+            false => Colour::BLACK
         },
     };
 
@@ -70,24 +80,41 @@ pub fn parse(toml_string: &str, fallbacks: &ThemeFallbacks) -> Result<Theme, Err
         None => fallbacks.system_derived_accent_colour,
     };
 
+    let mut palette = ColourPalette::new(
+        theme_config.dark_mode,
+        primary_colour,
+        interactive_colour,
+        surface_colour,
+        text_colour,
+        accent_colour
+    );
+
+    // the semantic colours are derived from the ones above if they're not set
+    for (hex_string, colour) in [
+        (theme_palette.success, &mut palette.success),
+        (theme_palette.warning, &mut palette.warning),
+        (theme_palette.error, &mut palette.error),
+        (theme_palette.info, &mut palette.info),
+        (theme_palette.selection, &mut palette.selection),
+        (theme_palette.link, &mut palette.link),
+    ] {
+        if let Some(hex_string) = hex_string {
+            *colour = Colour::try_from(hex_string)?;
+        }
+    }
+
     Ok(
         Theme {
             name: theme_config.metadata.name,
             // features: Features {
             //     derive_accent_from_system: theme_features.derive_accent_from_system,
             // },
-            palette: ColourPalette {
-                is_dark: theme_config.dark_mode,
-                primary: primary_colour,
-                interactive: interactive_colour,
-                surface: surface_colour,
-                text: text_colour,
-                accent: accent_colour
-            },
+            palette,
         }
     )
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Default)]
 struct ThemeFeatures {
     pub derive_accent_from_system: bool,
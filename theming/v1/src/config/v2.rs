@@ -0,0 +1,152 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+use crate::{colour::Colour, config::v1::{Metadata, ThemePalette}, error::Error, fallbacks::ThemeFallbacks, palette::ColourPalette, system::find_theme_path_in_system, theme::Theme};
+
+/// Version 2 themes can be partial, every palette colour (and `dark_mode`) left out
+/// comes from the theme in `extends`, or the default dark / light theme if it's not set.
+#[derive(Deserialize)]
+pub struct ThemeConfigV2 {
+    #[allow(dead_code)]
+    version: i8,
+    /// `"dark"`, `"light"` or `"<pack>/<theme>"` of an installed theme.
+    #[serde(default)]
+    extends: Option<String>,
+    #[serde(default)]
+    dark_mode: Option<bool>,
+    metadata: Metadata,
+    #[serde(default)]
+    palette: ThemePalette,
+}
+
+pub fn parse(
+    toml_string: &str,
+    theme_path: &Path,
+    fallbacks: &ThemeFallbacks,
+    extended_by: &mut Vec<PathBuf>
+) -> Result<Theme, Error> {
+    let theme_config: ThemeConfigV2 = toml::from_str(toml_string)
+        .map_err(|error| Error::ThemeTomlParseFailure { error: error.to_string() })?;
+
+    let parent_theme = match &theme_config.extends {
+        Some(extends) => {
+            extended_by.push(canonical_theme_path(theme_path));
+
+            let parent_theme = parse_parent_theme(extends, theme_path, fallbacks, extended_by);
+
+            extended_by.pop();
+
+            parent_theme?
+        },
+        None => match theme_config.dark_mode.unwrap_or(true) {
+            true => Theme::default_dark(fallbacks),
+            false => Theme::default_light(),
+        },
+    };
+
+    let theme_palette = theme_config.palette;
+    let parent_palette = parent_theme.palette;
+
+    let colour_or = |hex_string: Option<String>, parent_colour: Colour| -> Result<Colour, Error> {
+        match hex_string {
+            Some(hex_string) => Colour::try_from(hex_string),
+            None => Ok(parent_colour),
+        }
+    };
+
+    let is_accent_changed = theme_palette.accent.is_some();
+    let is_surface_changed = theme_palette.surface.is_some();
+
+    let accent = colour_or(theme_palette.accent, parent_palette.accent)?;
+    let surface = colour_or(theme_palette.surface, parent_palette.surface)?;
+
+    // selection and link are derived from the accent (and surface), so they're only taken from the parent
+    // if this theme keeps the parent's, changing just the accent changes them too. The other semantic
+    // colours come from the parent like the rest.
+    let selection = match theme_palette.selection {
+        Some(hex_string) => Colour::try_from(hex_string)?,
+        None if is_accent_changed || is_surface_changed => ColourPalette::derive_selection(accent, surface),
+        None => parent_palette.selection,
+    };
+
+    let link = match theme_palette.link {
+        Some(hex_string) => Colour::try_from(hex_string)?,
+        None if is_accent_changed => accent,
+        None => parent_palette.link,
+    };
+
+    Ok(
+        Theme {
+            name: theme_config.metadata.name,
+            palette: ColourPalette {
+                is_dark: theme_config.dark_mode.unwrap_or(parent_palette.is_dark),
+                primary: colour_or(theme_palette.primary, parent_palette.primary)?,
+                interactive: colour_or(theme_palette.interactive, parent_palette.interactive)?,
+                surface,
+                text: colour_or(theme_palette.text, parent_palette.text)?,
+                accent,
+                success: colour_or(theme_palette.success, parent_palette.success)?,
+                warning: colour_or(theme_palette.warning, parent_palette.warning)?,
+                error: colour_or(theme_palette.error, parent_palette.error)?,
+                info: colour_or(theme_palette.info, parent_palette.info)?,
+                selection,
+                link,
+            },
+        }
+    )
+}
+
+fn parse_parent_theme(
+    extends: &str,
+    theme_path: &Path,
+    fallbacks: &ThemeFallbacks,
+    extended_by: &mut Vec<PathBuf>
+) -> Result<Theme, Error> {
+    match extends.to_lowercase().as_str() {
+        "dark" => return Ok(Theme::default_dark(fallbacks)),
+        "light" => return Ok(Theme::default_light()),
+        _ => {},
+    }
+
+    let theme_not_found = || Error::ThemeExtendsNotFound { extends: extends.to_string() };
+
+    let (theme_pack_name, theme_code_name) = extends.split_once('/')
+        .ok_or_else(theme_not_found)?;
+
+    // themes in the same themes folder (e.g: the same pack) come first
+    let parent_theme_path = theme_path.parent()
+        .and_then(Path::parent)
+        .map(|themes_path| themes_path.join(theme_pack_name).join(theme_code_name.to_lowercase()))
+        .filter(|parent_theme_path| parent_theme_path.is_dir())
+        .or_else(|| find_theme_path_in_system(theme_pack_name, theme_code_name))
+        .ok_or_else(theme_not_found)?;
+
+    if extended_by.contains(&canonical_theme_path(&parent_theme_path)) {
+        let mut theme_code_names: Vec<String> = extended_by.iter()
+            .map(|theme_path| theme_pack_and_code_name(theme_path))
+            .collect();
+
+        theme_code_names.push(theme_pack_and_code_name(&parent_theme_path));
+
+        return Err(Error::ThemeExtendsCycle { theme_code_names });
+    }
+
+    log::debug!("Resolving parent theme '{extends}' from '{}'...", parent_theme_path.display());
+
+    Theme::parse_from_path_extended_by(parent_theme_path, fallbacks, extended_by)
+}
+
+fn canonical_theme_path(theme_path: &Path) -> PathBuf {
+    fs::canonicalize(theme_path).unwrap_or_else(|_| theme_path.to_path_buf())
+}
+
+/// `<pack>/<theme>` of a theme's path.
+fn theme_pack_and_code_name(theme_path: &Path) -> String {
+    let file_name = |path: Option<&Path>| path
+        .and_then(Path::file_name)
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    format!("{}/{}", file_name(theme_path.parent()), file_name(Some(theme_path)))
+}
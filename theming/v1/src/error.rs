@@ -10,8 +10,14 @@ pub enum Error {
 
     PathNotATheme { path: PathBuf },
 
-    GlobalConfigParseFailure { error: String },
+    ThemeExtendsNotFound { extends: String },
+    ThemeExtendsCycle { theme_code_names: Vec<String> },
+
     HexCodeParseFailure { error: String, hex_string: String },
+    ColourParseFailure { error: String, colour_string: String },
+
+    AccentColourReadFailure { backend: String, error: String },
+    ColourSchemeReadFailure { backend: String, error: String },
 }
 
 impl Display for Error {
@@ -20,6 +26,9 @@ impl Display for Error {
             Error::HexCodeParseFailure { hex_string, .. }  => write!(
                 f, "Failed to parse hex code! Hex code: '{hex_string}'"
             ),
+            Error::ColourParseFailure { error, colour_string } => write!(
+                f, "Failed to parse the colour '{colour_string}'! Error: {error}"
+            ),
             Error::ThemeTomlUnsupported { version } => write!(
                 f,
                 "Unsupported theme version! This current version of the toolkit does not \
@@ -32,17 +41,27 @@ impl Display for Error {
             Error::ThemeTomlNoVersionKey { theme_code_name, theme_toml_path } => write!(
                 f,
                 "Failed to parse the '{theme_code_name}' theme, no 'version' key found in '{}'!",
-                theme_toml_path.to_string_lossy().to_string()
+                theme_toml_path.to_string_lossy()
+            ),
+            Error::AccentColourReadFailure { backend, error } => write!(
+                f, "Failed to read the system accent colour from {backend}! Error: {error}"
+            ),
+            Error::ColourSchemeReadFailure { backend, error } => write!(
+                f, "Failed to read the preferred colour scheme from {backend}! Error: {error}"
+            ),
+            Error::ThemeExtendsNotFound { extends } => write!(
+                f,
+                "The theme '{extends}' to extend was not found! Expected \"dark\", \"light\" or \"<pack>/<theme>\" \
+                    of a theme installed in your cloudy themes folder."
+            ),
+            Error::ThemeExtendsCycle { theme_code_names } => write!(
+                f, "Themes can't extend each other in a loop! ({})", theme_code_names.join(" -> ")
             ),
             Error::PathNotATheme { path } => write!(
                 f,
                 "The path at '{}' was not a theme!",
                 path.display()
             ),
-            Error::GlobalConfigParseFailure { .. } => write!(
-                f, "Failed to read the global cloudy config file ('~/.config/cloudy/config.toml'), \
-                it may be corrupted or the reason below may state otherwise!"
-            ),
         }
     }
 }
\ No newline at end of file
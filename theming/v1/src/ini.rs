@@ -0,0 +1,26 @@
+/// The value of `key` in the `[group]` of an ini file (e.g: `kdeglobals` or GTK's `settings.ini`).
+pub(crate) fn find_ini_value<'a>(ini_string: &'a str, group: &str, key: &str) -> Option<&'a str> {
+    let mut in_group = false;
+
+    for line in ini_string.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_group = line == format!("[{group}]");
+            continue;
+        }
+
+        if !in_group {
+            continue;
+        }
+
+        if let Some((line_key, value)) = line.split_once('=') {
+            // keys may be localized or locked (e.g: `AccentColor[$i]`)
+            let line_key = line_key.split('[').next().unwrap_or_default().trim();
+
+            if line_key == key {
+                return Some(value.trim());
+            }
+        }
+    }
+
+    None
+}
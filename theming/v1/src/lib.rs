@@ -1,5 +1,8 @@
 mod config;
 mod system;
+mod ini;
+#[cfg(all(target_os = "linux", feature = "portal"))]
+mod portal;
 
 pub mod theme;
 pub mod colour;
@@ -7,4 +10,6 @@ pub mod palette;
 pub mod error;
 pub mod manager;
 pub mod fallbacks;
-pub mod features;
\ No newline at end of file
+pub mod features;
+pub mod accent;
+pub mod colour_scheme;
\ No newline at end of file
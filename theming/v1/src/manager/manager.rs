@@ -1,9 +1,16 @@
-use std::{env, fs, path::PathBuf};
+use std::env;
 
-use toml::Table;
-use cirrus_path::get_user_config_cloudy_folder_path;
+use cirrus_config::global::{get_global_config, GlobalConfig};
 
-use crate::{colour::Colour, error::{Error, Result}, fallbacks::ThemeFallbacks, manager::origin::ThemeOrigin, palette::DEFAULT_ACCENT_HEX, system::find_theme_in_system, theme::Theme};
+use crate::{accent::get_system_accent_colour, colour::Colour, colour_scheme::{get_system_colour_scheme, ColourScheme, ColourSchemeWatcher}, fallbacks::ThemeFallbacks, manager::origin::ThemeOrigin, palette::DEFAULT_ACCENT_HEX, system::find_theme_in_system, theme::Theme};
+
+/// Things that happened to the theme while the app was running, see [`ThemeManager::update`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeEvent {
+    /// The theme is `"auto"` and the system's colour scheme changed, so the theme was swapped
+    /// for the dark or light one. Styling has to be applied again for it to show.
+    ColourSchemeChanged { colour_scheme: ColourScheme },
+}
 
 /// ⚠️ Keep in mind this struct is unstable and may change soon with breaking changes.
 pub struct ThemeManager {
@@ -12,6 +19,8 @@ pub struct ThemeManager {
     fallbacks: ThemeFallbacks,
     // might make this public soon
     origin: Option<ThemeOrigin>,
+    // only watching when the theme is "auto"
+    colour_scheme_watcher: Option<ColourSchemeWatcher>,
 }
 
 impl Default for ThemeManager {
@@ -19,7 +28,8 @@ impl Default for ThemeManager {
         Self {
             theme: Theme::default_dark(&ThemeFallbacks::default()),
             fallbacks: ThemeFallbacks::default(),
-            origin: None
+            origin: None,
+            colour_scheme_watcher: None,
         }
     }
 }
@@ -28,8 +38,9 @@ impl ThemeManager {
     pub fn default_with_fallbacks(fallbacks: ThemeFallbacks) -> Self {
         Self {
             theme: Theme::default_dark(&fallbacks),
-            fallbacks: fallbacks,
+            fallbacks,
             origin: None,
+            colour_scheme_watcher: None,
         }
     }
 
@@ -37,84 +48,118 @@ impl ThemeManager {
         if let Ok(theme_name) = env::var("CTK_THEME") {
             log::debug!("Getting theme from environment variable...");
 
-            let theme_fallbacks = &self.fallbacks;
-
-            let theme: Option<Theme> = match theme_name.to_lowercase().as_str() {
-                "dark" => Some(Theme::default_dark(theme_fallbacks)),
-                "light" => Some(Theme::default_light()),
-                theme_code_name => find_theme_in_system(
-                    theme_code_name.to_string(), &theme_fallbacks
-                )
-            };
-
-            if let Some(found_theme) = theme {
-                self.theme = found_theme;
-                self.origin = Some(ThemeOrigin::EnvVar);
-                return self;
-            }
+            self.set_theme(&theme_name, ThemeOrigin::EnvVar);
         }
 
-        return self;
+        self
     }
 
     /// Fetches set theme from cloudy-org global config (~/.config/cloudy/config.toml) 
     /// and your environment (e.g: KDE / Gnome accent colour).
-    pub fn get_theme_from_system(mut self) -> Self {
+    pub fn get_theme_from_system(self) -> Self {
         if self.origin.is_some() {
             return self;
         }
 
         log::debug!("Getting theme from system...");
 
-        if let Ok(config_path) = get_user_config_cloudy_folder_path() {
-            let config_path = config_path.join("config.toml"); 
+        match get_global_config() {
+            Ok(global_config) => self.get_theme_from_global_config(&global_config),
+            Err(error) => {
+                log::error!("Failed to read the global config! \n\nError: {error}");
+                self
+            },
+        }
+    }
 
-            if !config_path.exists() {
-                return self;
-            }
+    /// Same as [`Self::get_theme_from_system`] but takes the global config already read,
+    /// use this if your app's config overrides the global config (see `GlobalConfig::with_overrides`).
+    pub fn get_theme_from_global_config(mut self, global_config: &GlobalConfig) -> Self {
+        if self.origin.is_some() {
+            return self;
+        }
 
-            // TODO: Fetch system accent colour.
-            self.fallbacks.system_derived_accent_colour = Colour::from_hex(DEFAULT_ACCENT_HEX);
+        match get_system_accent_colour() {
+            Some(colour) => {
+                self.fallbacks.system_derived_accent_colour = colour;
+                self.theme = Theme::default_dark(&self.fallbacks);
+            },
+            None => self.fallbacks.system_derived_accent_colour = Colour::from_hex(DEFAULT_ACCENT_HEX),
+        }
 
-            match find_theme_from_config(config_path, &self.fallbacks) {
-                Ok(Some(theme)) => {
-                    self.theme = theme;
-                    self.origin = Some(ThemeOrigin::Config);
+        if let Some(accent_colour) = &global_config.accent_colour {
+            match Colour::parse(accent_colour) {
+                Ok(colour) => {
+                    self.fallbacks.system_derived_accent_colour = colour;
+                    self.theme = Theme::default_dark(&self.fallbacks);
                 },
-                Ok(None) => log::debug!("No theme was set in the config."),
                 Err(error) => log::error!(
-                    "Failed to get theme from config! \n\nError: {error}"
+                    "The 'accent_colour' set in the global config is invalid! \n\nError: {error}"
                 ),
             }
-        } 
+        }
+
+        match &global_config.theme {
+            Some(theme_code_name) => {
+                log::debug!("Checking global config for set theme...");
+
+                self.set_theme(theme_code_name, ThemeOrigin::Config);
+            },
+            None => log::debug!("No theme was set in the config."),
+        }
 
         self
     }
-}
 
-fn find_theme_from_config(config_path: PathBuf, theme_fallbacks: &ThemeFallbacks) -> Result<Option<Theme>, Error> {
-    log::debug!("Checking global config toml for set theme...");
+    /// Checks if the system's colour scheme changed when the theme is `"auto"` and if so swaps the theme
+    /// for the dark or light one. Never blocks, so it's fine to call every frame.
+    pub fn update(&mut self) -> Option<ThemeEvent> {
+        let colour_scheme = self.colour_scheme_watcher.as_ref()?.poll_changed()?;
+
+        log::debug!("Switching theme to follow the system's '{:?}' colour scheme...", colour_scheme);
+
+        self.theme = theme_for_colour_scheme(colour_scheme, &self.fallbacks);
+
+        Some(ThemeEvent::ColourSchemeChanged { colour_scheme })
+    }
+
+    /// Whether the theme is `"auto"`, following the system's dark / light preference.
+    pub fn follows_system_colour_scheme(&self) -> bool {
+        self.colour_scheme_watcher.is_some()
+    }
+
+    fn set_theme(&mut self, theme_code_name: &str, origin: ThemeOrigin) {
+        let theme = match theme_code_name.to_lowercase().as_str() {
+            "auto" => {
+                let colour_scheme = get_system_colour_scheme();
 
-    // TODO: we should use the cirrus_config crate when it get's support for this global config. 
-    let toml_string = fs::read_to_string(config_path)
-        .map_err(|error| Error::GlobalConfigParseFailure { error: error.to_string() })?;
+                self.colour_scheme_watcher = Some(ColourSchemeWatcher::new(colour_scheme));
 
-    let generic_config_table: Table = toml::from_str(&toml_string)
-        .map_err(|error| Error::GlobalConfigParseFailure { error: error.to_string() })?;
+                Some(theme_for_colour_scheme(colour_scheme, &self.fallbacks))
+            },
+            theme_code_name => find_theme(theme_code_name, &self.fallbacks),
+        };
 
-    if let Some(theme_code_name_value) = generic_config_table.get("theme") {
-        if let Some(theme_code_name) = theme_code_name_value.as_str() {
-            return Ok(
-                find_theme_in_system(theme_code_name.to_string(), theme_fallbacks)
-            );
+        if let Some(theme) = theme {
+            self.theme = theme;
+            self.origin = Some(origin);
         }
+    }
+}
 
-        return Err(
-            Error::ThemeTomlParseFailure {
-                error: "The 'theme' key in the global config must be a string!".into()
-            }
-        );
-    };
+fn theme_for_colour_scheme(colour_scheme: ColourScheme, theme_fallbacks: &ThemeFallbacks) -> Theme {
+    match colour_scheme.is_dark() {
+        true => Theme::default_dark(theme_fallbacks),
+        false => Theme::default_light(),
+    }
+}
 
-    Ok(None)
-}
\ No newline at end of file
+fn find_theme(theme_code_name: &str, theme_fallbacks: &ThemeFallbacks) -> Option<Theme> {
+    match theme_code_name.to_lowercase().as_str() {
+        "dark" => Some(Theme::default_dark(theme_fallbacks)),
+        "light" => Some(Theme::default_light()),
+        theme_code_name => find_theme_in_system(
+            theme_code_name.to_string(), theme_fallbacks
+        )
+    }
+}
@@ -1,4 +1,5 @@
 mod origin;
 
+#[allow(clippy::module_inception)]
 mod manager;
 pub use manager::*;
\ No newline at end of file
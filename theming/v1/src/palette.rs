@@ -1,6 +1,5 @@
 use crate::{colour::Colour};
 
-pub(crate) static TRANSPARENT_HEX: u32 = 0xFF000000;
 pub(crate) static DEFAULT_ACCENT_HEX: u32 = 0x7afff8;
 
 #[derive(Clone)]
@@ -23,28 +22,68 @@ pub struct ColourPalette {
     /// elements like buttons, sliders, highlights and it's also sometimes used 
     /// as the stroke colour when drawing certain patterns and in loading animations.
     pub accent: Colour,
+    /// Something went well (e.g: a "saved" toast).
+    pub success: Colour,
+    /// Something may need the user's attention.
+    pub warning: Colour,
+    /// Something went wrong (e.g: error toasts and detailed errors).
+    pub error: Colour,
+    /// Neutral information.
+    pub info: Colour,
+    /// Background of selected text and selected / active widgets, derived from accent and surface if not set.
+    pub selection: Colour,
+    /// Hyperlinks, the accent colour if not set.
+    pub link: Colour,
 }
 
 impl ColourPalette {
-    pub(crate) fn default_dark(accent_colour: Colour) -> Self {
+    /// A palette with the semantic colours (success, warning, error, info, selection and link) derived from the rest.
+    pub fn new(is_dark: bool, primary: Colour, interactive: Colour, surface: Colour, text: Colour, accent: Colour) -> Self {
+        let (success, warning, error, info) = match is_dark {
+            // lighter for dark backgrounds, darker for light ones so they stay readable
+            true => (0x5fb971, 0xe5b454, 0xe5534b, 0x58a6ff),
+            false => (0x2d8a3e, 0xa8680b, 0xc4312b, 0x1f6feb),
+        };
+
         Self {
-            is_dark: true,
-            primary: Colour::from_hex(0x0A0A0A),
-            interactive: Colour::from_hex(0x383838),
-            surface: Colour::from_hex(0x3C3939),
-            text: Colour::from_hex(0xb5b5b5),
-            accent: accent_colour
+            is_dark,
+            primary,
+            interactive,
+            surface,
+            text,
+            accent,
+            success: Colour::from_hex(success),
+            warning: Colour::from_hex(warning),
+            error: Colour::from_hex(error),
+            info: Colour::from_hex(info),
+            selection: Self::derive_selection(accent, surface),
+            link: accent,
         }
     }
 
+    pub(crate) fn default_dark(accent_colour: Colour) -> Self {
+        Self::new(
+            true,
+            Colour::from_hex(0x0A0A0A),
+            Colour::from_hex(0x383838),
+            Colour::from_hex(0x3C3939),
+            Colour::from_hex(0xb5b5b5),
+            accent_colour
+        )
+    }
+
     pub(crate) fn default_light() -> Self {
-        Self {
-            is_dark: false,
-            primary: Colour::from_hex(0xEDFFFF),
-            interactive: Colour::from_hex(0xF0F2F2),
-            surface: Colour::from_hex(0xC9C9C9),
-            text: Colour::from_hex(0x242424),
-            accent: Colour::from_hex(DEFAULT_ACCENT_HEX),
-        }
+        Self::new(
+            false,
+            Colour::from_hex(0xEDFFFF),
+            Colour::from_hex(0xF0F2F2),
+            Colour::from_hex(0xC9C9C9),
+            Colour::from_hex(0x242424),
+            Colour::from_hex(DEFAULT_ACCENT_HEX)
+        )
+    }
+
+    pub(crate) fn derive_selection(accent: Colour, surface: Colour) -> Colour {
+        accent.mix(surface, 0.3)
     }
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use zbus::{blocking::{connection::Builder, Connection, MessageIterator}, message::Type, zvariant::{OwnedValue, Value}, MatchRule};
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SETTINGS_INTERFACE: &str = "org.freedesktop.portal.Settings";
+
+/// Errors meaning there's no portal to ask or it doesn't have the setting.
+const MISSING_ERROR_NAMES: [&str; 2] = ["org.freedesktop.portal.Error.NotFound", "org.freedesktop.DBus.Error.ServiceUnknown"];
+
+pub(crate) const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
+/// The XDG Desktop Portal's settings interface (`org.freedesktop.portal.Settings`) over D-Bus.
+pub(crate) struct PortalSettings {
+    connection: Option<Connection>,
+}
+
+impl PortalSettings {
+    /// Connects to the session bus, if there's no session bus every setting reads as unset.
+    pub(crate) fn session() -> Self {
+        match Builder::session().and_then(|builder| builder.method_timeout(Duration::from_secs(1)).build()) {
+            Ok(connection) => Self { connection: Some(connection) },
+            Err(error) => {
+                log::debug!("Failed to connect to the D-Bus session bus! Error: {error}");
+                Self { connection: None }
+            },
+        }
+    }
+
+    pub(crate) fn with_connection(connection: Connection) -> Self {
+        Self { connection: Some(connection) }
+    }
+
+    /// `Ok(None)` if there's no portal or it doesn't have the setting.
+    pub(crate) fn read(&self, namespace: &str, key: &str) -> zbus::Result<Option<OwnedValue>> {
+        let Some(connection) = &self.connection else {
+            return Ok(None);
+        };
+
+        let message = match call_settings_method(connection, "ReadOne", namespace, key) {
+            Ok(message) => message,
+            // 'ReadOne' was added in version 2 of the settings portal, 'Read' wraps the value in another variant
+            Err(zbus::Error::MethodError(error_name, _, _)) if error_name == "org.freedesktop.DBus.Error.UnknownMethod" => {
+                match call_settings_method(connection, "Read", namespace, key) {
+                    Ok(message) => message,
+                    Err(error) => return none_if_missing(error, namespace, key),
+                }
+            },
+            Err(error) => return none_if_missing(error, namespace, key),
+        };
+
+        let value = message.body().deserialize::<OwnedValue>()?;
+
+        let mut inner_value: &Value = &value;
+
+        while let Value::Value(value) = inner_value {
+            inner_value = value;
+        }
+
+        Ok(Some(inner_value.try_to_owned()?))
+    }
+
+    /// The portal's `SettingChanged` signals for the setting, `Ok(None)` if there's no session bus.
+    ///
+    /// The signals stop (the iterator ends) once the returned connection is closed.
+    pub(crate) fn watch(&self, namespace: &str, key: &str) -> zbus::Result<Option<(MessageIterator, Connection)>> {
+        let Some(connection) = &self.connection else {
+            return Ok(None);
+        };
+
+        let rule = MatchRule::builder()
+            .msg_type(Type::Signal)
+            .path(PORTAL_PATH)?
+            .interface(SETTINGS_INTERFACE)?
+            .member("SettingChanged")?
+            .arg(0, namespace)?
+            .arg(1, key)?
+            .build();
+
+        let changes = MessageIterator::for_match_rule(rule, connection, None)?;
+
+        Ok(Some((changes, connection.clone())))
+    }
+}
+
+fn call_settings_method(connection: &Connection, method: &str, namespace: &str, key: &str) -> zbus::Result<zbus::Message> {
+    connection.call_method(Some(PORTAL_DESTINATION), PORTAL_PATH, Some(SETTINGS_INTERFACE), method, &(namespace, key))
+}
+
+/// The portal isn't running or its desktop doesn't have the setting, any other error is the portal failing.
+fn none_if_missing(error: zbus::Error, namespace: &str, key: &str) -> zbus::Result<Option<OwnedValue>> {
+    match error {
+        zbus::Error::MethodError(error_name, _, _) if MISSING_ERROR_NAMES.contains(&error_name.as_str()) => {
+            log::debug!("The portal has no '{namespace} {key}' setting ({error_name}).");
+            Ok(None)
+        },
+        error => Err(error),
+    }
+}
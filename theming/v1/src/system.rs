@@ -7,17 +7,7 @@ use crate::{fallbacks::{ThemeFallbacks}, theme::Theme};
 pub fn find_theme_in_system(theme_code_name: String, theme_fallbacks: &ThemeFallbacks) -> Option<Theme> {
     log::debug!("Searching for the cloudy-org theme '{}' on this system...", theme_code_name);
 
-    let mut themes_paths: Vec<PathBuf> = Vec::new();
-
-    if let Ok(user_themes_path) = get_user_cloudy_themes_folder_path() {
-        themes_paths.push(user_themes_path);
-    }
-
-    themes_paths.extend(
-        get_system_cloudy_themes_folder_paths().unwrap_or_default()
-    );
-
-    for themes_path in themes_paths {
+    for themes_path in get_themes_folder_paths() {
         match fs::read_dir(&themes_path) {
             Ok(read_dir) => {
                 for theme_pack_folder_entry in read_dir {
@@ -75,5 +65,29 @@ pub fn find_theme_in_system(theme_code_name: String, theme_fallbacks: &ThemeFall
         "The theme '{theme_code_name}' was not found in the system!"
     );
 
-    return None;
-}
\ No newline at end of file
+    None
+}
+
+/// Path of the theme `theme_code_name` in the theme pack `theme_pack_name` (e.g: `my-pack/rose`),
+/// the user's themes folder is searched first then the system's.
+pub(crate) fn find_theme_path_in_system(theme_pack_name: &str, theme_code_name: &str) -> Option<PathBuf> {
+    log::debug!("Searching for the cloudy-org theme '{theme_pack_name}/{theme_code_name}' on this system...");
+
+    get_themes_folder_paths().into_iter()
+        .map(|themes_path| themes_path.join(theme_pack_name).join(theme_code_name.to_lowercase()))
+        .find(|theme_path| theme_path.is_dir())
+}
+
+fn get_themes_folder_paths() -> Vec<PathBuf> {
+    let mut themes_paths: Vec<PathBuf> = Vec::new();
+
+    if let Ok(user_themes_path) = get_user_cloudy_themes_folder_path() {
+        themes_paths.push(user_themes_path);
+    }
+
+    themes_paths.extend(
+        get_system_cloudy_themes_folder_paths().unwrap_or_default()
+    );
+
+    themes_paths
+}
@@ -31,7 +31,17 @@ impl Theme {
         }
     }
 
-    pub(crate) fn parse_from_path(theme_path: PathBuf, fallbacks: &ThemeFallbacks) -> Result<Self> {
+    /// Parses the theme in the folder at `theme_path` (the folder with its `theme.toml`, e.g: `themes/my-pack/rose`).
+    pub fn parse_from_path(theme_path: PathBuf, fallbacks: &ThemeFallbacks) -> Result<Self> {
+        Self::parse_from_path_extended_by(theme_path, fallbacks, &mut Vec::new())
+    }
+
+    /// `extended_by` holds the paths of the themes that (indirectly) extend this one so cycles can be caught.
+    pub(crate) fn parse_from_path_extended_by(
+        theme_path: PathBuf,
+        fallbacks: &ThemeFallbacks,
+        extended_by: &mut Vec<PathBuf>
+    ) -> Result<Self> {
         log::debug!("Parsing theme from path '{}'...", &theme_path.display());
 
         let theme_code_name = theme_path.file_name()
@@ -48,7 +58,7 @@ impl Theme {
                 |error| Error::ThemeTomlParseFailure {
                     error: format!(
                         "Failed to read '{theme_code_name}' theme.toml! \n\nError: {}",
-                        error.to_string()
+                        error
                     )
                 }
             )?;
@@ -64,7 +74,7 @@ impl Theme {
                 |error| Error::ThemeTomlParseFailure {
                     error: format!(
                         "Failed to parse '{theme_code_name}' toml into toml.rs generic table! \n\nError: {}",
-                        error.to_string()
+                        error
                     )
                 }
             )?;
@@ -73,6 +83,7 @@ impl Theme {
             Some(theme_version) => {
                 match theme_version.as_integer() {
                     Some(1) => Ok(config::v1::parse(&toml_string, fallbacks)?),
+                    Some(2) => Ok(config::v2::parse(&toml_string, &theme_path, fallbacks, extended_by)?),
                     _ => Err(Error::ThemeTomlUnsupported { version: theme_version.to_string() }),
                 }
             },
@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+pub fn fixture_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures").join(file_name)
+}
+
+/// A stand-in for the XDG Desktop Portal so the portal backends can be tested without a session bus.
+#[cfg(all(target_os = "linux", feature = "portal"))]
+pub mod portal {
+    use std::{collections::HashMap, os::unix::net::UnixStream, thread};
+
+    use zbus::{blocking::{connection::Builder, Connection}, zvariant::{OwnedValue, Value}, Guid};
+
+    /// The errors the portal replies with.
+    #[derive(Debug, zbus::DBusError)]
+    #[zbus(prefix = "org.freedesktop.portal.Error")]
+    pub enum PortalError {
+        #[zbus(error)]
+        ZBus(zbus::Error),
+        Failed(String),
+        NotFound(String),
+    }
+
+    /// The portal's settings interface, serving `settings` keyed by `(namespace, key)`.
+    pub struct FakeSettings {
+        pub settings: HashMap<(String, String), OwnedValue>,
+        /// Fails every read like a broken portal would.
+        pub is_broken: bool,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.portal.Settings")]
+    impl FakeSettings {
+        fn read_one(&self, namespace: &str, key: &str) -> Result<OwnedValue, PortalError> {
+            if self.is_broken {
+                return Err(PortalError::Failed("The portal is broken".to_string()));
+            }
+
+            match self.settings.get(&(namespace.to_string(), key.to_string())) {
+                Some(value) => value.try_clone().map_err(|error| PortalError::Failed(error.to_string())),
+                None => Err(PortalError::NotFound("Requested setting not found".to_string())),
+            }
+        }
+    }
+
+    /// A peer to peer connection to a fake portal with the `org.freedesktop.appearance` `settings`,
+    /// returns the server too as it has to be kept alive.
+    pub fn connect_to_fake_portal(settings: Vec<(&str, OwnedValue)>) -> (Connection, Connection) {
+        serve(
+            FakeSettings {
+                settings: settings.into_iter()
+                    .map(|(key, value)| (("org.freedesktop.appearance".to_string(), key.to_string()), value))
+                    .collect(),
+                is_broken: false,
+            }
+        )
+    }
+
+    /// Same as [`connect_to_fake_portal`] but every read fails.
+    pub fn connect_to_broken_portal() -> (Connection, Connection) {
+        serve(FakeSettings { settings: HashMap::new(), is_broken: true })
+    }
+
+    /// Changes an `org.freedesktop.appearance` setting of the fake portal and tells the client about it.
+    pub fn change_setting(server: &Connection, key: &str, value: OwnedValue) {
+        let settings = server.object_server()
+            .interface::<_, FakeSettings>("/org/freedesktop/portal/desktop")
+            .unwrap();
+
+        let signal_value = Value::from(value.try_clone().unwrap());
+
+        settings.get_mut().settings.insert(("org.freedesktop.appearance".to_string(), key.to_string()), value);
+
+        server.emit_signal(
+            None::<&str>,
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+            "SettingChanged",
+            &("org.freedesktop.appearance", key, signal_value)
+        ).unwrap();
+    }
+
+    fn serve(settings: FakeSettings) -> (Connection, Connection) {
+        let (server_stream, client_stream) = UnixStream::pair().unwrap();
+
+        let server = thread::spawn(move || {
+            Builder::async_io_unix_stream(server_stream)
+                .server(Guid::generate()).unwrap()
+                .p2p()
+                .serve_at("/org/freedesktop/portal/desktop", settings).unwrap()
+                .build()
+                .unwrap()
+        });
+
+        let client = Builder::async_io_unix_stream(client_stream).p2p().build().unwrap();
+
+        (server.join().unwrap(), client)
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use cirrus_theming_v1::{accent::{find_accent_colour, AccentColourBackend, GnomeAccentBackend, KdeAccentBackend}, error::Error};
+
+use common::fixture_path;
+
+#[test]
+fn test_kdeglobals_accent_colour() -> Result<(), Error> {
+    let accent_colour = KdeAccentBackend::from_path(fixture_path("kdeglobals")).accent_colour()?;
+    assert_eq!(accent_colour.map(|colour| colour.hex), Some(0xe9643d));
+
+    // without a custom accent colour the colour scheme's selection colour is the accent
+    let accent_colour = KdeAccentBackend::from_path(fixture_path("kdeglobals_colour_scheme")).accent_colour()?;
+    assert_eq!(accent_colour.map(|colour| colour.hex), Some(0x3daee9));
+
+    let accent_colour = KdeAccentBackend::from_path(fixture_path("missing_kdeglobals")).accent_colour()?;
+    assert!(accent_colour.is_none());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_gnome_accent_colour() -> Result<(), Error> {
+    let backend = GnomeAccentBackend::with_command("cat", [fixture_path("gnome_accent_color").display().to_string()]);
+    assert_eq!(backend.accent_colour()?.map(|colour| colour.hex), Some(0x9141ac));
+
+    let backend = GnomeAccentBackend::with_command("false", Vec::<String>::new());
+    assert!(backend.accent_colour()?.is_none());
+
+    let backend = GnomeAccentBackend::with_command("cirrus-missing-gsettings", Vec::<String>::new());
+    assert!(backend.accent_colour()?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_first_backend_with_accent_colour_wins() {
+    let backends: Vec<Box<dyn AccentColourBackend>> = vec![
+        Box::new(KdeAccentBackend::from_path(fixture_path("missing_kdeglobals"))),
+        Box::new(KdeAccentBackend::from_path(fixture_path("kdeglobals_colour_scheme"))),
+        Box::new(KdeAccentBackend::from_path(fixture_path("kdeglobals"))),
+    ];
+
+    assert_eq!(find_accent_colour(&backends).map(|colour| colour.hex), Some(0x3daee9));
+    assert!(find_accent_colour(&[]).is_none());
+}
+
+#[cfg(all(target_os = "linux", feature = "portal"))]
+#[test]
+fn test_portal_accent_colour() {
+    use cirrus_theming_v1::accent::PortalAccentBackend;
+    use common::portal::{connect_to_broken_portal, connect_to_fake_portal};
+    use zbus::zvariant::{OwnedValue, Value};
+
+    let accent_colour = |accent_colour: (f64, f64, f64)| OwnedValue::try_from(Value::from(accent_colour)).unwrap();
+
+    let (_server, client) = connect_to_fake_portal(vec![("accent-color", accent_colour((0.2, 0.4, 1.0)))]);
+    let backend = PortalAccentBackend::with_connection(client);
+
+    assert_eq!(backend.accent_colour().unwrap().map(|colour| colour.hex), Some(0x3366ff));
+
+    // the portal uses values outside of 0-1 when no accent colour is set
+    let (_server, client) = connect_to_fake_portal(vec![("accent-color", accent_colour((-1.0, -1.0, -1.0)))]);
+    assert!(PortalAccentBackend::with_connection(client).accent_colour().unwrap().is_none());
+
+    let (_server, client) = connect_to_fake_portal(Vec::new());
+    assert!(PortalAccentBackend::with_connection(client).accent_colour().unwrap().is_none());
+
+    // a portal that fails isn't the same as one without an accent colour
+    let (_server, client) = connect_to_broken_portal();
+
+    assert!(matches!(
+        PortalAccentBackend::with_connection(client).accent_colour(),
+        Err(Error::AccentColourReadFailure { .. })
+    ));
+}
@@ -0,0 +1,104 @@
+use cirrus_theming_v1::{colour::{Colour, Hsl, Hsv, Oklch}, error::Error};
+
+#[test]
+fn test_parse_hex_colours() -> Result<(), Error> {
+    assert_eq!(Colour::parse("#3daee9")?, Colour::from_rgb(0x3d, 0xae, 0xe9));
+    assert_eq!(Colour::parse("3DAEE9")?, Colour::from_hex(0x3daee9));
+    assert_eq!(Colour::parse("#fa0")?, Colour::from_hex(0xffaa00));
+    assert_eq!(Colour::parse("#fa08")?, Colour::from_rgba(0xff, 0xaa, 0x00, 0x88));
+    assert_eq!(Colour::parse("#3daee980")?, Colour::from_hex(0x3daee9).with_alpha(0x80));
+
+    assert!(matches!(Colour::parse("#3dae"), Ok(colour) if colour.alpha == 0xee));
+    assert!(matches!(Colour::parse("#3dae9"), Err(Error::HexCodeParseFailure { .. })));
+    assert!(matches!(Colour::parse("#3daeez"), Err(Error::HexCodeParseFailure { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_css_colours() -> Result<(), Error> {
+    assert_eq!(Colour::parse("rgb(61, 174, 233)")?, Colour::from_hex(0x3daee9));
+    assert_eq!(Colour::parse("rgb(61 174 233 / 50%)")?, Colour::from_rgba(61, 174, 233, 128));
+    assert_eq!(Colour::parse("RGBA(100%, 0%, 0%, 0.2)")?, Colour::from_rgba(255, 0, 0, 51));
+
+    assert_eq!(Colour::parse("hsl(120, 100%, 25%)")?, Colour::from_hex(0x008000));
+    assert_eq!(Colour::parse("hsl(0deg 0% 100% / 0)")?, Colour::WHITE.with_alpha(0));
+    assert_eq!(Colour::parse("hsla(-120, 100%, 50%, 1)")?, Colour::from_hex(0x0000ff));
+
+    for invalid_colour in ["rgb(256, 0, 0)", "rgb(0, 0)", "rgb(0, 0, 0, 2)", "hsl(0, 120%, 50%)", "lab(50 0 0)", "rgb(a, b, c)"] {
+        assert!(
+            matches!(Colour::parse(invalid_colour), Err(Error::ColourParseFailure { .. })),
+            "'{invalid_colour}' should not parse!"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_colour_hex_string_round_trip() -> Result<(), Error> {
+    assert_eq!(Colour::from_hex(0x3daee9).to_hex_string(), "#3daee9");
+    assert_eq!(Colour::TRANSPARENT.to_hex_string(), "#00000000");
+
+    let colour = Colour::from_rgba(1, 2, 3, 4);
+    assert_eq!(Colour::parse(&colour.to_hex_string())?, colour);
+
+    Ok(())
+}
+
+#[test]
+fn test_colour_space_conversions() {
+    let colour = Colour::from_hex(0x3daee9);
+
+    let hsl = colour.to_hsl();
+    assert!((hsl.hue - 200.6).abs() < 0.1);
+    assert!((hsl.saturation - 0.797).abs() < 0.01);
+    assert!((hsl.lightness - 0.576).abs() < 0.01);
+
+    let hsv = colour.to_hsv();
+    assert!((hsv.saturation - 0.738).abs() < 0.01);
+    assert!((hsv.value - 0.914).abs() < 0.01);
+
+    assert_eq!(Colour::from_hsl(hsl), colour);
+    assert_eq!(Colour::from_hsv(hsv), colour);
+    assert_eq!(Colour::from_hsl(Hsl { hue: 0.0, saturation: 1.0, lightness: 0.5 }), Colour::from_hex(0xff0000));
+    assert_eq!(Colour::from_hsv(Hsv { hue: 240.0, saturation: 1.0, value: 1.0 }), Colour::from_hex(0x0000ff));
+
+    // pure red is oklch(0.628 0.258 29.2)
+    let oklch = Colour::from_hex(0xff0000).to_oklch();
+    assert!((oklch.lightness - 0.628).abs() < 0.001);
+    assert!((oklch.chroma - 0.258).abs() < 0.001);
+    assert!((oklch.hue - 29.23).abs() < 0.1);
+
+    assert_eq!(Colour::from_oklch(colour.to_oklch()), colour);
+    assert_eq!(Colour::from_oklch(Oklch { lightness: 1.0, chroma: 0.0, hue: 0.0 }), Colour::WHITE);
+    assert_eq!(Colour::from_hex(0x808080).to_oklch().hue, 0.0);
+}
+
+#[test]
+fn test_colour_operations() {
+    let colour = Colour::from_hex(0x3daee9).with_alpha(200);
+
+    assert!(colour.lighten(0.1).to_hsl().lightness > colour.to_hsl().lightness);
+    assert!(colour.darken(0.1).to_hsl().lightness < colour.to_hsl().lightness);
+    assert!(colour.desaturate(0.2).to_hsl().saturation < colour.to_hsl().saturation);
+    assert_eq!(colour.lighten(1.0), Colour::WHITE.with_alpha(200));
+    assert_eq!(colour.darken(1.0), Colour::BLACK.with_alpha(200));
+    assert_eq!(colour.desaturate(1.0).to_hsl().saturation, 0.0);
+    assert!((Colour::from_hex(0x808080).saturate(0.5).to_hsl().saturation - 0.5).abs() < 0.01);
+
+    assert_eq!(Colour::BLACK.mix(Colour::WHITE, 0.5), Colour::from_hex(0x808080));
+    assert_eq!(Colour::BLACK.mix(Colour::TRANSPARENT, 0.5), Colour::BLACK.with_alpha(128));
+}
+
+#[test]
+fn test_colour_contrast() {
+    assert_eq!(Colour::BLACK.relative_luminance(), 0.0);
+    assert_eq!(Colour::WHITE.relative_luminance(), 1.0);
+
+    assert!((Colour::BLACK.contrast_ratio(Colour::WHITE) - 21.0).abs() < 0.001);
+    assert_eq!(Colour::WHITE.contrast_ratio(Colour::BLACK), Colour::BLACK.contrast_ratio(Colour::WHITE));
+    assert_eq!(Colour::from_hex(0x3daee9).contrast_ratio(Colour::from_hex(0x3daee9)), 1.0);
+    // #767676 is the lightest grey that passes WCAG AA (4.5:1) on white
+    assert!((Colour::from_hex(0x767676).contrast_ratio(Colour::WHITE) - 4.54).abs() < 0.01);
+}
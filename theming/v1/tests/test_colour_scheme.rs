@@ -0,0 +1,154 @@
+mod common;
+
+use std::{sync::{Arc, Mutex}, thread, time::{Duration, Instant}};
+
+use cirrus_theming_v1::{colour_scheme::{find_colour_scheme, ColourScheme, ColourSchemeBackend, ColourSchemeWatcher, GtkColourSchemeBackend, KdeColourSchemeBackend}, error::{Error, Result}};
+
+use common::fixture_path;
+
+#[test]
+fn test_gtk_colour_scheme() -> Result<(), Error> {
+    let gtk_settings_path = |gtk_folder: &str| fixture_path(gtk_folder).join("settings.ini");
+
+    // gtk-4.0 wins over gtk-3.0 and prefers dark
+    let backend = GtkColourSchemeBackend::from_paths([gtk_settings_path("gtk-4.0"), gtk_settings_path("gtk-3.0")]);
+    assert_eq!(backend.colour_scheme()?, ColourScheme::Dark);
+
+    // a dark theme variant
+    let backend = GtkColourSchemeBackend::from_paths([gtk_settings_path("gtk-5.0"), gtk_settings_path("gtk-3.0")]);
+    assert_eq!(backend.colour_scheme()?, ColourScheme::Dark);
+
+    let backend = GtkColourSchemeBackend::from_paths([fixture_path("gtk_light_settings.ini")]);
+    assert_eq!(backend.colour_scheme()?, ColourScheme::Light);
+
+    let backend = GtkColourSchemeBackend::from_paths([gtk_settings_path("gtk-5.0")]);
+    assert_eq!(backend.colour_scheme()?, ColourScheme::NoPreference);
+
+    Ok(())
+}
+
+#[test]
+fn test_kdeglobals_colour_scheme() -> Result<(), Error> {
+    assert_eq!(KdeColourSchemeBackend::from_path(fixture_path("kdeglobals")).colour_scheme()?, ColourScheme::Dark);
+    assert_eq!(KdeColourSchemeBackend::from_path(fixture_path("kdeglobals_colour_scheme")).colour_scheme()?, ColourScheme::Light);
+    assert_eq!(KdeColourSchemeBackend::from_path(fixture_path("missing_kdeglobals")).colour_scheme()?, ColourScheme::NoPreference);
+
+    let backends: Vec<Box<dyn ColourSchemeBackend>> = vec![
+        Box::new(KdeColourSchemeBackend::from_path(fixture_path("missing_kdeglobals"))),
+        Box::new(KdeColourSchemeBackend::from_path(fixture_path("kdeglobals_colour_scheme"))),
+    ];
+
+    assert_eq!(find_colour_scheme(&backends), ColourScheme::Light);
+
+    Ok(())
+}
+
+#[cfg(all(target_os = "linux", feature = "portal"))]
+#[test]
+fn test_portal_colour_scheme() {
+    use cirrus_theming_v1::colour_scheme::PortalColourSchemeBackend;
+    use common::portal::connect_to_fake_portal;
+    use zbus::zvariant::OwnedValue;
+
+    for (value, colour_scheme) in [(0_u32, ColourScheme::NoPreference), (1, ColourScheme::Dark), (2, ColourScheme::Light)] {
+        let (_server, client) = connect_to_fake_portal(vec![("color-scheme", OwnedValue::from(value))]);
+
+        assert_eq!(PortalColourSchemeBackend::with_connection(client).colour_scheme().unwrap(), colour_scheme);
+    }
+
+    let (_server, client) = connect_to_fake_portal(Vec::new());
+    assert_eq!(PortalColourSchemeBackend::with_connection(client).colour_scheme().unwrap(), ColourScheme::NoPreference);
+}
+
+/// A colour scheme preference the test can change.
+struct SharedColourSchemeBackend(Arc<Mutex<ColourScheme>>);
+
+impl ColourSchemeBackend for SharedColourSchemeBackend {
+    fn name(&self) -> &'static str {
+        "test"
+    }
+
+    fn colour_scheme(&self) -> Result<ColourScheme> {
+        Ok(*self.0.lock().unwrap())
+    }
+}
+
+#[test]
+fn test_colour_scheme_watcher() {
+    let colour_scheme = Arc::new(Mutex::new(ColourScheme::Dark));
+
+    let watcher = {
+        let colour_scheme = colour_scheme.clone();
+
+        ColourSchemeWatcher::with_backends(
+            ColourScheme::Dark,
+            move || vec![Box::new(SharedColourSchemeBackend(colour_scheme)) as Box<dyn ColourSchemeBackend>],
+            Duration::from_millis(10)
+        )
+    };
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(watcher.poll_changed(), None);
+
+    *colour_scheme.lock().unwrap() = ColourScheme::Light;
+
+    let started = Instant::now();
+    let mut changed_colour_scheme = None;
+
+    while changed_colour_scheme.is_none() && started.elapsed() < Duration::from_secs(5) {
+        thread::sleep(Duration::from_millis(10));
+        changed_colour_scheme = watcher.poll_changed();
+    }
+
+    assert_eq!(changed_colour_scheme, Some(ColourScheme::Light));
+    assert_eq!(watcher.poll_changed(), None);
+}
+
+#[cfg(all(target_os = "linux", feature = "portal"))]
+#[test]
+fn test_colour_scheme_watcher_with_portal() {
+    use cirrus_theming_v1::colour_scheme::PortalColourSchemeBackend;
+    use common::portal::{change_setting, connect_to_fake_portal};
+    use zbus::zvariant::OwnedValue;
+
+    let colour_scheme = Arc::new(Mutex::new(ColourScheme::Dark));
+    let (server, client) = connect_to_fake_portal(vec![("color-scheme", OwnedValue::from(1_u32))]);
+
+    let watcher = {
+        let colour_scheme = colour_scheme.clone();
+
+        ColourSchemeWatcher::with_portal(
+            ColourScheme::Dark,
+            PortalColourSchemeBackend::with_connection(client),
+            move || vec![Box::new(SharedColourSchemeBackend(colour_scheme)) as Box<dyn ColourSchemeBackend>]
+        ).unwrap()
+    };
+
+    // nothing is read until the portal says something changed
+    *colour_scheme.lock().unwrap() = ColourScheme::Light;
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(watcher.poll_changed(), None);
+
+    change_setting(&server, "color-scheme", OwnedValue::from(2_u32));
+
+    let started = Instant::now();
+    let mut changed_colour_scheme = None;
+
+    while changed_colour_scheme.is_none() && started.elapsed() < Duration::from_secs(5) {
+        thread::sleep(Duration::from_millis(10));
+        changed_colour_scheme = watcher.poll_changed();
+    }
+
+    assert_eq!(changed_colour_scheme, Some(ColourScheme::Light));
+
+    // without the preference the portal won't say when it changes
+    let (_server, client) = connect_to_fake_portal(Vec::new());
+
+    assert!(
+        matches!(
+            ColourSchemeWatcher::with_portal(ColourScheme::Dark, PortalColourSchemeBackend::with_connection(client), Vec::new),
+            Err(Error::ColourSchemeReadFailure { .. })
+        )
+    );
+}
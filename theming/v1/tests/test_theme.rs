@@ -0,0 +1,76 @@
+mod common;
+
+use cirrus_theming_v1::{error::Error, fallbacks::ThemeFallbacks, theme::Theme};
+
+use common::fixture_path;
+
+fn parse_theme(theme_pack_and_code_name: &str) -> Result<Theme, Error> {
+    Theme::parse_from_path(fixture_path("themes").join(theme_pack_and_code_name), &ThemeFallbacks::default())
+}
+
+#[test]
+fn test_theme_extends_theme() -> Result<(), Error> {
+    let theme = parse_theme("my-pack/rose")?;
+
+    assert_eq!(theme.name, "Rose");
+    assert!(theme.palette.is_dark);
+    assert_eq!(theme.palette.accent.hex, 0xff66cc);
+    // the rest comes from "my-pack/base", a version 1 theme
+    assert_eq!(theme.palette.primary.hex, 0x101010);
+    assert_eq!(theme.palette.text.hex, 0xd0d0d0);
+
+    Ok(())
+}
+
+#[test]
+fn test_partial_theme_falls_back_to_default_theme() -> Result<(), Error> {
+    let theme = parse_theme("my-pack/partial")?;
+    let light_theme = Theme::default_light();
+
+    assert!(!theme.palette.is_dark);
+    assert_eq!(theme.palette.primary.hex, 0xfafafa);
+    assert_eq!(theme.palette.surface.hex, light_theme.palette.surface.hex);
+    assert_eq!(theme.palette.text.hex, light_theme.palette.text.hex);
+
+    Ok(())
+}
+
+#[test]
+fn test_theme_extends_errors() {
+    assert!(matches!(parse_theme("my-pack/lost"), Err(Error::ThemeExtendsNotFound { extends }) if extends == "other-pack/missing"));
+
+    match parse_theme("loop-pack/a") {
+        Err(Error::ThemeExtendsCycle { theme_code_names }) => assert_eq!(
+            theme_code_names, vec!["loop-pack/a", "loop-pack/b", "loop-pack/a"]
+        ),
+        _ => panic!("Expected the 'extends' cycle to be caught!"),
+    }
+}
+
+#[test]
+fn test_theme_semantic_colours() -> Result<(), Error> {
+    let theme = parse_theme("my-pack/rose")?;
+    let dark_theme = Theme::default_dark(&ThemeFallbacks::default());
+
+    assert_eq!(theme.palette.warning.hex, 0xffaa00);
+    // "my-pack/base" doesn't set any so the rest are the default dark theme's
+    assert_eq!(theme.palette.success.hex, dark_theme.palette.success.hex);
+    assert_eq!(theme.palette.error.hex, dark_theme.palette.error.hex);
+    // selection and link follow "rose"'s accent rather than the parent's
+    assert_eq!(theme.palette.selection.hex, 0xc1569d);
+    assert_eq!(theme.palette.link.hex, 0xff66cc);
+
+    Ok(())
+}
+
+#[test]
+fn test_theme_inherits_selection_and_link() -> Result<(), Error> {
+    let theme = parse_theme("my-pack/night-soft")?;
+
+    assert_eq!(theme.palette.text.hex, 0xc8c8c8);
+    // neither the accent nor the surface changed so "my-pack/night"'s are kept
+    assert_eq!(theme.palette.selection.hex, 0x224488);
+    assert_eq!(theme.palette.link.hex, 0x88aaff);
+
+    Ok(())
+}
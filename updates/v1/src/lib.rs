@@ -18,7 +18,23 @@ struct GitHubData {
     tag_name: String,
 }
 
+/// Returns `false` if the user opted out of update checks in the cloudy-org global config.
+pub fn is_update_check_enabled() -> bool {
+    cirrus_config::global::get_global_config()
+        .map(|global_config| global_config.check_for_updates)
+        .unwrap_or(true)
+}
+
+/// Fetches the latest release's version from GitHub.
+///
+/// Returns `None` without making a request if the user opted out of
+/// update checks (see [`is_update_check_enabled`]).
+#[allow(clippy::result_large_err)]
 pub fn get_latest_version(username: &str, repository: &str) -> Result<Option<Version>, Error> {
+    if !is_update_check_enabled() {
+        return Ok(None);
+    }
+
     let github_api = format!("https://api.github.com/repos/{}/{}/releases", username, repository);
 
     let client = blocking::Client::new();